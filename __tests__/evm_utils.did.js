@@ -1,5 +1,106 @@
 export const idlFactory = ({ IDL }) => {
+  const AbiValue = IDL.Rec();
   const List = IDL.Rec();
+  const AccessList = IDL.Record({
+    'storage_keys' : IDL.Vec(IDL.Vec(IDL.Nat8)),
+    'address' : IDL.Vec(IDL.Nat8),
+  });
+  const CreatedAccessList = IDL.Record({
+    'gas_used' : IDL.Vec(IDL.Nat8),
+    'access_list' : IDL.Vec(AccessList),
+  });
+  const Result = IDL.Variant({ 'Ok' : CreatedAccessList, 'Err' : IDL.Text });
+  const BlobSchedule = IDL.Record({
+    'target_blob_gas_per_block' : IDL.Nat64,
+    'blob_base_cost' : IDL.Opt(IDL.Nat64),
+    'max_blob_gas_per_block' : IDL.Nat64,
+    'base_fee_update_fraction' : IDL.Nat64,
+  });
+  const Chain = IDL.Record({
+    'eip155_required' : IDL.Bool,
+    'name' : IDL.Text,
+    'native_currency_decimals' : IDL.Nat8,
+    'tx_types' : IDL.Vec(IDL.Nat8),
+    'chain_id' : IDL.Nat64,
+    'blob_schedule' : IDL.Opt(BlobSchedule),
+  });
+  const Result_1 = IDL.Variant({ 'Ok' : IDL.Null, 'Err' : IDL.Text });
+  const Result_2 = IDL.Variant({ 'Ok' : IDL.Nat64, 'Err' : IDL.Text });
+  const StorageLocation = IDL.Record({
+    'slot' : IDL.Vec(IDL.Nat8),
+    'offset' : IDL.Nat32,
+  });
+  const Result_3 = IDL.Variant({ 'Ok' : StorageLocation, 'Err' : IDL.Text });
+  const Result_4 = IDL.Variant({ 'Ok' : BlobSchedule, 'Err' : IDL.Text });
+  const Result_5 = IDL.Variant({ 'Ok' : IDL.Bool, 'Err' : IDL.Text });
+  const SiweMessage = IDL.Record({
+    'uri' : IDL.Text,
+    'request_id' : IDL.Opt(IDL.Text),
+    'not_before' : IDL.Opt(IDL.Text),
+    'issued_at' : IDL.Text,
+    'domain' : IDL.Text,
+    'resources' : IDL.Vec(IDL.Text),
+    'statement' : IDL.Opt(IDL.Text),
+    'scheme' : IDL.Opt(IDL.Text),
+    'version' : IDL.Text,
+    'chain_id' : IDL.Nat64,
+    'address' : IDL.Vec(IDL.Nat8),
+    'nonce' : IDL.Text,
+    'expiration_time' : IDL.Opt(IDL.Text),
+  });
+  const Result_6 = IDL.Variant({ 'Ok' : IDL.Text, 'Err' : IDL.Text });
+  const TokenCall = IDL.Variant({
+    'SafeTransferFrom' : IDL.Record({
+      'to' : IDL.Vec(IDL.Nat8),
+      'token_id' : IDL.Vec(IDL.Nat8),
+      'data' : IDL.Opt(IDL.Vec(IDL.Nat8)),
+      'from' : IDL.Vec(IDL.Nat8),
+    }),
+    'Approve' : IDL.Record({
+      'amount' : IDL.Vec(IDL.Nat8),
+      'spender' : IDL.Vec(IDL.Nat8),
+    }),
+    'Permit' : IDL.Record({
+      'r' : IDL.Vec(IDL.Nat8),
+      's' : IDL.Vec(IDL.Nat8),
+      'v' : IDL.Nat8,
+      'value' : IDL.Vec(IDL.Nat8),
+      'owner' : IDL.Vec(IDL.Nat8),
+      'deadline' : IDL.Vec(IDL.Nat8),
+      'spender' : IDL.Vec(IDL.Nat8),
+    }),
+    'SetApprovalForAll' : IDL.Record({
+      'operator' : IDL.Vec(IDL.Nat8),
+      'approved' : IDL.Bool,
+    }),
+    'Transfer' : IDL.Record({
+      'to' : IDL.Vec(IDL.Nat8),
+      'amount' : IDL.Vec(IDL.Nat8),
+    }),
+    'Erc1155SafeTransferFrom' : IDL.Record({
+      'id' : IDL.Vec(IDL.Nat8),
+      'to' : IDL.Vec(IDL.Nat8),
+      'data' : IDL.Vec(IDL.Nat8),
+      'from' : IDL.Vec(IDL.Nat8),
+      'amount' : IDL.Vec(IDL.Nat8),
+    }),
+    'TransferFrom' : IDL.Record({
+      'to' : IDL.Vec(IDL.Nat8),
+      'from' : IDL.Vec(IDL.Nat8),
+      'amount' : IDL.Vec(IDL.Nat8),
+    }),
+  });
+  const TransactionRequest = IDL.Record({
+    'to' : IDL.Opt(IDL.Vec(IDL.Nat8)),
+    'value' : IDL.Vec(IDL.Nat8),
+    'max_priority_fee_per_gas' : IDL.Opt(IDL.Vec(IDL.Nat8)),
+    'data' : IDL.Vec(IDL.Nat8),
+    'max_fee_per_gas' : IDL.Opt(IDL.Vec(IDL.Nat8)),
+    'nonce' : IDL.Vec(IDL.Nat8),
+    'gas_limit' : IDL.Vec(IDL.Nat8),
+    'access_list' : IDL.Vec(AccessList),
+    'gas_price' : IDL.Opt(IDL.Vec(IDL.Nat8)),
+  });
   const Signature = IDL.Record({
     'r' : IDL.Vec(IDL.Nat8),
     's' : IDL.Vec(IDL.Nat8),
@@ -7,12 +108,8 @@ export const idlFactory = ({ IDL }) => {
     'from' : IDL.Opt(IDL.Vec(IDL.Nat8)),
     'hash' : IDL.Vec(IDL.Nat8),
   });
-  const AccessList = IDL.Record({
-    'storage_keys' : IDL.Vec(IDL.Vec(IDL.Nat8)),
-    'address' : IDL.Vec(IDL.Nat8),
-  });
   const Transaction1559 = IDL.Record({
-    'to' : IDL.Vec(IDL.Nat8),
+    'to' : IDL.Opt(IDL.Vec(IDL.Nat8)),
     'value' : IDL.Vec(IDL.Nat8),
     'max_priority_fee_per_gas' : IDL.Vec(IDL.Nat8),
     'data' : IDL.Vec(IDL.Nat8),
@@ -24,7 +121,7 @@ export const idlFactory = ({ IDL }) => {
     'access_list' : IDL.Vec(AccessList),
   });
   const Transaction2930 = IDL.Record({
-    'to' : IDL.Vec(IDL.Nat8),
+    'to' : IDL.Opt(IDL.Vec(IDL.Nat8)),
     'value' : IDL.Vec(IDL.Nat8),
     'data' : IDL.Vec(IDL.Nat8),
     'sign' : IDL.Opt(Signature),
@@ -35,7 +132,7 @@ export const idlFactory = ({ IDL }) => {
     'gas_price' : IDL.Vec(IDL.Nat8),
   });
   const TransactionLegacy = IDL.Record({
-    'to' : IDL.Vec(IDL.Nat8),
+    'to' : IDL.Opt(IDL.Vec(IDL.Nat8)),
     'value' : IDL.Vec(IDL.Nat8),
     'data' : IDL.Vec(IDL.Nat8),
     'sign' : IDL.Opt(Signature),
@@ -49,13 +146,402 @@ export const idlFactory = ({ IDL }) => {
     'EIP2930' : Transaction2930,
     'Legacy' : TransactionLegacy,
   });
-  const Result = IDL.Variant({
+  const Result_7 = IDL.Variant({ 'Ok' : Transaction, 'Err' : IDL.Text });
+  const ReplacementFees = IDL.Record({
+    'max_priority_fee_per_gas' : IDL.Opt(IDL.Vec(IDL.Nat8)),
+    'max_fee_per_gas' : IDL.Opt(IDL.Vec(IDL.Nat8)),
+    'gas_price' : IDL.Opt(IDL.Vec(IDL.Nat8)),
+  });
+  const Replacement = IDL.Record({
+    'transaction' : Transaction,
+    'signing_hash' : IDL.Vec(IDL.Nat8),
+  });
+  const Result_8 = IDL.Variant({ 'Ok' : Replacement, 'Err' : IDL.Text });
+  const FactoryCall = IDL.Record({
+    'data' : IDL.Vec(IDL.Nat8),
+    'factory' : IDL.Vec(IDL.Nat8),
+  });
+  const Erc1271Call = IDL.Record({
+    'to' : IDL.Vec(IDL.Nat8),
+    'data' : IDL.Vec(IDL.Nat8),
+    'deploy' : IDL.Opt(FactoryCall),
+  });
+  const SignatureCheck = IDL.Variant({
+    'Contract' : Erc1271Call,
+    'Ecdsa' : IDL.Null,
+  });
+  const Result_9 = IDL.Variant({ 'Ok' : SignatureCheck, 'Err' : IDL.Text });
+  const Result_10 = IDL.Variant({ 'Ok' : IDL.Vec(IDL.Nat8), 'Err' : IDL.Text });
+  const Result_11 = IDL.Variant({
     'Ok' : IDL.Tuple(IDL.Vec(IDL.Nat8), IDL.Vec(IDL.Nat8)),
     'Err' : IDL.Text,
   });
-  const Result_1 = IDL.Variant({ 'Ok' : IDL.Null, 'Err' : IDL.Text });
-  const Result_2 = IDL.Variant({ 'Ok' : Transaction, 'Err' : IDL.Text });
-  const Result_3 = IDL.Variant({ 'Ok' : IDL.Vec(IDL.Nat8), 'Err' : IDL.Text });
+  const UserOperation = IDL.Record({
+    'pre_verification_gas' : IDL.Vec(IDL.Nat8),
+    'signature' : IDL.Vec(IDL.Nat8),
+    'max_priority_fee_per_gas' : IDL.Vec(IDL.Nat8),
+    'paymaster_and_data' : IDL.Vec(IDL.Nat8),
+    'max_fee_per_gas' : IDL.Vec(IDL.Nat8),
+    'sender' : IDL.Vec(IDL.Nat8),
+    'init_code' : IDL.Vec(IDL.Nat8),
+    'nonce' : IDL.Vec(IDL.Nat8),
+    'call_gas_limit' : IDL.Vec(IDL.Nat8),
+    'call_data' : IDL.Vec(IDL.Nat8),
+    'verification_gas_limit' : IDL.Vec(IDL.Nat8),
+  });
+  const PackedUserOperation = IDL.Record({
+    'pre_verification_gas' : IDL.Vec(IDL.Nat8),
+    'signature' : IDL.Vec(IDL.Nat8),
+    'account_gas_limits' : IDL.Vec(IDL.Nat8),
+    'paymaster_and_data' : IDL.Vec(IDL.Nat8),
+    'sender' : IDL.Vec(IDL.Nat8),
+    'init_code' : IDL.Vec(IDL.Nat8),
+    'nonce' : IDL.Vec(IDL.Nat8),
+    'gas_fees' : IDL.Vec(IDL.Nat8),
+    'call_data' : IDL.Vec(IDL.Nat8),
+  });
+  const Bundle = IDL.Variant({
+    'V06' : IDL.Vec(UserOperation),
+    'V07' : IDL.Vec(PackedUserOperation),
+  });
+  const HandleOps = IDL.Record({
+    'beneficiary' : IDL.Vec(IDL.Nat8),
+    'bundle' : Bundle,
+  });
+  const Result_12 = IDL.Variant({
+    'Ok' : IDL.Vec(IDL.Vec(IDL.Nat8)),
+    'Err' : IDL.Text,
+  });
+  AbiValue.fill(
+    IDL.Variant({
+      'Int' : IDL.Vec(IDL.Nat8),
+      'FixedArray' : IDL.Vec(AbiValue),
+      'Bool' : IDL.Bool,
+      'Uint' : IDL.Vec(IDL.Nat8),
+      'String' : IDL.Text,
+      'Bytes' : IDL.Vec(IDL.Nat8),
+      'Address' : IDL.Vec(IDL.Nat8),
+      'FixedBytes' : IDL.Vec(IDL.Nat8),
+      'Tuple' : IDL.Vec(AbiValue),
+      'Array' : IDL.Vec(AbiValue),
+    })
+  );
+  const DecodedParam = IDL.Record({
+    'value' : AbiValue,
+    'kind' : IDL.Text,
+    'name' : IDL.Text,
+  });
+  const Revert = IDL.Variant({
+    'Empty' : IDL.Null,
+    'Error' : IDL.Text,
+    'Panic' : IDL.Record({
+      'code' : IDL.Vec(IDL.Nat8),
+      'description' : IDL.Text,
+    }),
+    'Custom' : IDL.Record({
+      'signature' : IDL.Text,
+      'name' : IDL.Text,
+      'params' : IDL.Vec(DecodedParam),
+    }),
+    'Unknown' : IDL.Record({
+      'data' : IDL.Vec(IDL.Nat8),
+      'selector' : IDL.Vec(IDL.Nat8),
+    }),
+  });
+  const Result_13 = IDL.Variant({ 'Ok' : Revert, 'Err' : IDL.Text });
+  const Domain = IDL.Record({
+    'name' : IDL.Opt(IDL.Text),
+    'salt' : IDL.Opt(IDL.Vec(IDL.Nat8)),
+    'version' : IDL.Opt(IDL.Text),
+    'chain_id' : IDL.Opt(IDL.Nat64),
+    'verifying_contract' : IDL.Opt(IDL.Vec(IDL.Nat8)),
+  });
+  const Log = IDL.Record({
+    'data' : IDL.Vec(IDL.Nat8),
+    'topics' : IDL.Vec(IDL.Vec(IDL.Nat8)),
+    'address' : IDL.Vec(IDL.Nat8),
+  });
+  const ReceiptOutcome = IDL.Variant({
+    'Status' : IDL.Bool,
+    'PostState' : IDL.Vec(IDL.Nat8),
+  });
+  const Receipt = IDL.Record({
+    'logs' : IDL.Vec(Log),
+    'cumulative_gas_used' : IDL.Vec(IDL.Nat8),
+    'tx_type' : IDL.Nat8,
+    'outcome' : ReceiptOutcome,
+    'logs_bloom' : IDL.Vec(IDL.Nat8),
+  });
+  const Operation = IDL.Variant({
+    'DelegateCall' : IDL.Null,
+    'Call' : IDL.Null,
+  });
+  const SafeTransaction = IDL.Record({
+    'to' : IDL.Vec(IDL.Nat8),
+    'base_gas' : IDL.Vec(IDL.Nat8),
+    'value' : IDL.Vec(IDL.Nat8),
+    'data' : IDL.Vec(IDL.Nat8),
+    'gas_token' : IDL.Vec(IDL.Nat8),
+    'safe_tx_gas' : IDL.Vec(IDL.Nat8),
+    'nonce' : IDL.Vec(IDL.Nat8),
+    'operation' : Operation,
+    'refund_receiver' : IDL.Vec(IDL.Nat8),
+    'gas_price' : IDL.Vec(IDL.Nat8),
+  });
+  const SafeSignature = IDL.Variant({
+    'Contract' : IDL.Vec(IDL.Nat8),
+    'ApprovedHash' : IDL.Null,
+    'EthSign' : IDL.Vec(IDL.Nat8),
+    'Ecdsa' : IDL.Vec(IDL.Nat8),
+  });
+  const OwnerSignature = IDL.Record({
+    'signature' : SafeSignature,
+    'owner' : IDL.Vec(IDL.Nat8),
+  });
+  const Erc2612Permit = IDL.Record({
+    'value' : IDL.Vec(IDL.Nat8),
+    'owner' : IDL.Vec(IDL.Nat8),
+    'deadline' : IDL.Vec(IDL.Nat8),
+    'nonce' : IDL.Vec(IDL.Nat8),
+    'spender' : IDL.Vec(IDL.Nat8),
+  });
+  const TransactionStatus = IDL.Variant({
+    'Confirmed' : IDL.Null,
+    'Dropped' : IDL.Null,
+    'Pending' : IDL.Null,
+  });
+  const TrackedTransaction = IDL.Record({
+    'status' : TransactionStatus,
+    'hash' : IDL.Vec(IDL.Nat8),
+    'nonce' : IDL.Nat64,
+  });
+  const NonceAccount = IDL.Record({
+    'next_nonce' : IDL.Nat64,
+    'free_nonces' : IDL.Vec(IDL.Nat64),
+    'chain_id' : IDL.Nat64,
+    'address' : IDL.Vec(IDL.Nat8),
+    'transactions' : IDL.Vec(TrackedTransaction),
+  });
+  const RpcProvider = IDL.Record({ 'url' : IDL.Text, 'name' : IDL.Text });
+  const RpcConfig = IDL.Record({
+    'max_response_bytes' : IDL.Opt(IDL.Nat64),
+    'chain_id' : IDL.Nat64,
+    'max_cycles' : IDL.Nat,
+    'providers' : IDL.Vec(RpcProvider),
+    'quorum' : IDL.Nat32,
+  });
+  const Result_14 = IDL.Variant({ 'Ok' : RpcConfig, 'Err' : IDL.Text });
+  const SiweConfig = IDL.Record({
+    'uri' : IDL.Text,
+    'login_ttl' : IDL.Nat64,
+    'domain' : IDL.Text,
+    'session_ttl' : IDL.Nat64,
+    'statement' : IDL.Opt(IDL.Text),
+    'chain_id' : IDL.Nat64,
+  });
+  const SiweSession = IDL.Record({
+    'principal' : IDL.Principal,
+    'address' : IDL.Vec(IDL.Nat8),
+    'expires_at' : IDL.Nat64,
+  });
+  const MappingKey = IDL.Variant({
+    'Word' : IDL.Vec(IDL.Nat8),
+    'Bytes' : IDL.Vec(IDL.Nat8),
+    'FixedBytes' : IDL.Vec(IDL.Nat8),
+  });
+  const Result_15 = IDL.Variant({ 'Ok' : AccessList, 'Err' : IDL.Text });
+  const MultiSendTransaction = IDL.Record({
+    'to' : IDL.Vec(IDL.Nat8),
+    'value' : IDL.Vec(IDL.Nat8),
+    'data' : IDL.Vec(IDL.Nat8),
+    'operation' : Operation,
+  });
+  const Header = IDL.Record({
+    'receipts_root' : IDL.Vec(IDL.Nat8),
+    'parent_beacon_block_root' : IDL.Opt(IDL.Vec(IDL.Nat8)),
+    'base_fee_per_gas' : IDL.Opt(IDL.Vec(IDL.Nat8)),
+    'beneficiary' : IDL.Vec(IDL.Nat8),
+    'difficulty' : IDL.Vec(IDL.Nat8),
+    'mix_hash' : IDL.Vec(IDL.Nat8),
+    'withdrawals_root' : IDL.Opt(IDL.Vec(IDL.Nat8)),
+    'requests_hash' : IDL.Opt(IDL.Vec(IDL.Nat8)),
+    'transactions_root' : IDL.Vec(IDL.Nat8),
+    'ommers_hash' : IDL.Vec(IDL.Nat8),
+    'nonce' : IDL.Vec(IDL.Nat8),
+    'number' : IDL.Nat64,
+    'timestamp' : IDL.Nat64,
+    'gas_limit' : IDL.Nat64,
+    'gas_used' : IDL.Nat64,
+    'state_root' : IDL.Vec(IDL.Nat8),
+    'extra_data' : IDL.Vec(IDL.Nat8),
+    'parent_hash' : IDL.Vec(IDL.Nat8),
+    'blob_gas_used' : IDL.Opt(IDL.Nat64),
+    'logs_bloom' : IDL.Vec(IDL.Nat8),
+    'excess_blob_gas' : IDL.Opt(IDL.Nat64),
+  });
+  const Withdrawal = IDL.Record({
+    'address' : IDL.Vec(IDL.Nat8),
+    'validator_index' : IDL.Nat64,
+    'index' : IDL.Nat64,
+    'amount' : IDL.Vec(IDL.Nat8),
+  });
+  const TokenIntent = IDL.Record({
+    'token' : IDL.Vec(IDL.Nat8),
+    'call' : TokenCall,
+  });
+  const ParsedTransaction = IDL.Record({
+    'eip155' : IDL.Bool,
+    'from' : IDL.Opt(IDL.Vec(IDL.Nat8)),
+    'hash' : IDL.Vec(IDL.Nat8),
+    'transaction' : Transaction,
+    'y_parity' : IDL.Opt(IDL.Nat8),
+    'tx_type' : IDL.Nat8,
+    'token_call' : IDL.Opt(TokenIntent),
+    'signing_hash' : IDL.Vec(IDL.Nat8),
+  });
+  const BlockTransaction = IDL.Variant({
+    'Parsed' : ParsedTransaction,
+    'Unsupported' : IDL.Record({
+      'hash' : IDL.Vec(IDL.Nat8),
+      'tx_type' : IDL.Nat8,
+    }),
+  });
+  const Block = IDL.Record({
+    'hash' : IDL.Vec(IDL.Nat8),
+    'ommers' : IDL.Vec(Header),
+    'withdrawals' : IDL.Opt(IDL.Vec(Withdrawal)),
+    'transactions' : IDL.Vec(BlockTransaction),
+    'header' : Header,
+  });
+  const Result_16 = IDL.Variant({ 'Ok' : Block, 'Err' : IDL.Text });
+  const Result_17 = IDL.Variant({ 'Ok' : Receipt, 'Err' : IDL.Text });
+  const BlockTag = IDL.Variant({
+    'Earliest' : IDL.Null,
+    'Safe' : IDL.Null,
+    'Finalized' : IDL.Null,
+    'Latest' : IDL.Null,
+    'Number' : IDL.Nat64,
+    'Pending' : IDL.Null,
+  });
+  const RpcRequest = IDL.Variant({
+    'GetProof' : IDL.Record({
+      'storage_keys' : IDL.Vec(IDL.Vec(IDL.Nat8)),
+      'address' : IDL.Vec(IDL.Nat8),
+      'block' : BlockTag,
+    }),
+    'GetTransactionCount' : IDL.Record({
+      'address' : IDL.Vec(IDL.Nat8),
+      'block' : BlockTag,
+    }),
+    'GetTransactionByHash' : IDL.Record({ 'hash' : IDL.Vec(IDL.Nat8) }),
+    'Call' : IDL.Record({
+      'to' : IDL.Vec(IDL.Nat8),
+      'data' : IDL.Vec(IDL.Nat8),
+      'from' : IDL.Opt(IDL.Vec(IDL.Nat8)),
+      'block' : BlockTag,
+    }),
+    'GetBalance' : IDL.Record({
+      'address' : IDL.Vec(IDL.Nat8),
+      'block' : BlockTag,
+    }),
+    'FeeHistory' : IDL.Record({
+      'block_count' : IDL.Nat64,
+      'newest_block' : BlockTag,
+      'reward_percentiles' : IDL.Vec(IDL.Float64),
+    }),
+    'CreateAccessList' : IDL.Record({
+      'to' : IDL.Opt(IDL.Vec(IDL.Nat8)),
+      'data' : IDL.Vec(IDL.Nat8),
+      'from' : IDL.Opt(IDL.Vec(IDL.Nat8)),
+      'block' : BlockTag,
+    }),
+    'GetTransactionReceipt' : IDL.Record({ 'hash' : IDL.Vec(IDL.Nat8) }),
+    'SendRawTransaction' : IDL.Record({ 'data' : IDL.Vec(IDL.Nat8) }),
+  });
+  const StorageProof = IDL.Record({
+    'key' : IDL.Vec(IDL.Nat8),
+    'value' : IDL.Vec(IDL.Nat8),
+    'proof' : IDL.Vec(IDL.Vec(IDL.Nat8)),
+  });
+  const AccountProof = IDL.Record({
+    'balance' : IDL.Vec(IDL.Nat8),
+    'address' : IDL.Vec(IDL.Nat8),
+    'nonce' : IDL.Vec(IDL.Nat8),
+    'account_proof' : IDL.Vec(IDL.Vec(IDL.Nat8)),
+    'storage_proof' : IDL.Vec(StorageProof),
+    'code_hash' : IDL.Vec(IDL.Nat8),
+    'storage_hash' : IDL.Vec(IDL.Nat8),
+  });
+  const FeeHistory = IDL.Record({
+    'reward' : IDL.Vec(IDL.Vec(IDL.Vec(IDL.Nat8))),
+    'base_fee_per_gas' : IDL.Vec(IDL.Vec(IDL.Nat8)),
+    'oldest_block' : IDL.Nat64,
+    'gas_used_ratio' : IDL.Vec(IDL.Float64),
+  });
+  const RpcResponse = IDL.Variant({
+    'TransactionCount' : IDL.Nat64,
+    'Call' : IDL.Vec(IDL.Nat8),
+    'Transaction' : IDL.Opt(Transaction),
+    'AccessList' : CreatedAccessList,
+    'Proof' : AccountProof,
+    'TransactionHash' : IDL.Vec(IDL.Nat8),
+    'FeeHistory' : FeeHistory,
+    'Balance' : IDL.Vec(IDL.Nat8),
+    'Receipt' : IDL.Opt(Receipt),
+  });
+  const Result_18 = IDL.Variant({ 'Ok' : RpcResponse, 'Err' : IDL.Text });
+  const Result_19 = IDL.Variant({ 'Ok' : SiweMessage, 'Err' : IDL.Text });
+  const Result_20 = IDL.Variant({ 'Ok' : ParsedTransaction, 'Err' : IDL.Text });
+  const PermitDetails = IDL.Record({
+    'token' : IDL.Vec(IDL.Nat8),
+    'expiration' : IDL.Nat64,
+    'nonce' : IDL.Nat64,
+    'amount' : IDL.Vec(IDL.Nat8),
+  });
+  const TokenPermissions = IDL.Record({
+    'token' : IDL.Vec(IDL.Nat8),
+    'amount' : IDL.Vec(IDL.Nat8),
+  });
+  const Permit2 = IDL.Variant({
+    'Batch' : IDL.Record({
+      'sig_deadline' : IDL.Vec(IDL.Nat8),
+      'details' : IDL.Vec(PermitDetails),
+      'spender' : IDL.Vec(IDL.Nat8),
+    }),
+    'TransferFrom' : IDL.Record({
+      'deadline' : IDL.Vec(IDL.Nat8),
+      'nonce' : IDL.Vec(IDL.Nat8),
+      'permitted' : TokenPermissions,
+      'spender' : IDL.Vec(IDL.Nat8),
+    }),
+    'Single' : IDL.Record({
+      'sig_deadline' : IDL.Vec(IDL.Nat8),
+      'details' : PermitDetails,
+      'spender' : IDL.Vec(IDL.Nat8),
+    }),
+  });
+  const WithdrawalRequest = IDL.Record({
+    'validator_pubkey' : IDL.Vec(IDL.Nat8),
+    'source_address' : IDL.Vec(IDL.Nat8),
+    'amount' : IDL.Nat64,
+  });
+  const ConsolidationRequest = IDL.Record({
+    'target_pubkey' : IDL.Vec(IDL.Nat8),
+    'source_pubkey' : IDL.Vec(IDL.Nat8),
+    'source_address' : IDL.Vec(IDL.Nat8),
+  });
+  const DepositRequest = IDL.Record({
+    'signature' : IDL.Vec(IDL.Nat8),
+    'pubkey' : IDL.Vec(IDL.Nat8),
+    'index' : IDL.Nat64,
+    'amount' : IDL.Nat64,
+    'withdrawal_credentials' : IDL.Vec(IDL.Nat8),
+  });
+  const ExecutionRequests = IDL.Record({
+    'withdrawals' : IDL.Vec(WithdrawalRequest),
+    'consolidations' : IDL.Vec(ConsolidationRequest),
+    'deposits' : IDL.Vec(DepositRequest),
+  });
   const Item = IDL.Variant({
     'Num' : IDL.Nat64,
     'Raw' : IDL.Vec(IDL.Nat8),
@@ -64,31 +550,448 @@ export const idlFactory = ({ IDL }) => {
     'Text' : IDL.Text,
   });
   List.fill(IDL.Record({ 'values' : IDL.Vec(Item) }));
-  const Result_4 = IDL.Variant({ 'Ok' : List, 'Err' : IDL.Text });
-  const Result_5 = IDL.Variant({
+  const Result_21 = IDL.Variant({ 'Ok' : List, 'Err' : IDL.Text });
+  const Result_22 = IDL.Variant({ 'Ok' : IDL.Nat, 'Err' : IDL.Text });
+  const ProvenAccount = IDL.Record({
+    'code' : IDL.Vec(IDL.Nat8),
+    'proof' : AccountProof,
+  });
+  const CallRequest = IDL.Record({
+    'to' : IDL.Opt(IDL.Vec(IDL.Nat8)),
+    'value' : IDL.Vec(IDL.Nat8),
+    'data' : IDL.Vec(IDL.Nat8),
+    'from' : IDL.Opt(IDL.Vec(IDL.Nat8)),
+    'gas_limit' : IDL.Opt(IDL.Nat64),
+    'gas_price' : IDL.Opt(IDL.Vec(IDL.Nat8)),
+  });
+  const ExecutionResult = IDL.Record({
+    'output' : IDL.Vec(IDL.Nat8),
+    'created_address' : IDL.Opt(IDL.Vec(IDL.Nat8)),
+    'logs' : IDL.Vec(Log),
+    'revert_reason' : IDL.Opt(IDL.Text),
+    'halt_reason' : IDL.Opt(IDL.Text),
+    'success' : IDL.Bool,
+    'gas_used' : IDL.Nat64,
+  });
+  const Result_23 = IDL.Variant({ 'Ok' : ExecutionResult, 'Err' : IDL.Text });
+  const Result_24 = IDL.Variant({ 'Ok' : SiweSession, 'Err' : IDL.Text });
+  const Result_25 = IDL.Variant({
+    'Ok' : IDL.Vec(StorageLocation),
+    'Err' : IDL.Text,
+  });
+  const FeeSuggestion = IDL.Record({
+    'base_fee_per_gas' : IDL.Vec(IDL.Nat8),
+    'max_priority_fee_per_gas' : IDL.Vec(IDL.Nat8),
+    'max_fee_per_gas' : IDL.Vec(IDL.Nat8),
+  });
+  const Result_26 = IDL.Variant({ 'Ok' : FeeSuggestion, 'Err' : IDL.Text });
+  const GasCost = IDL.Record({
+    'effective_gas_price' : IDL.Opt(IDL.Vec(IDL.Nat8)),
+    'max_cost' : IDL.Vec(IDL.Nat8),
+    'below_intrinsic_gas' : IDL.Bool,
+    'intrinsic_gas' : IDL.Nat64,
+  });
+  const Result_27 = IDL.Variant({ 'Ok' : GasCost, 'Err' : IDL.Text });
+  const HttpHeader = IDL.Record({ 'value' : IDL.Text, 'name' : IDL.Text });
+  const HttpResponse = IDL.Record({
+    'status' : IDL.Nat,
+    'body' : IDL.Vec(IDL.Nat8),
+    'headers' : IDL.Vec(HttpHeader),
+  });
+  const TransformArgs = IDL.Record({
+    'context' : IDL.Vec(IDL.Nat8),
+    'response' : HttpResponse,
+  });
+  const Erc6492Signature = IDL.Record({
+    'signature' : IDL.Vec(IDL.Nat8),
+    'deploy' : FactoryCall,
+  });
+  const Result_28 = IDL.Variant({
+    'Ok' : IDL.Opt(Erc6492Signature),
+    'Err' : IDL.Text,
+  });
+  const Violation = IDL.Variant({
+    'ChainIdMismatch' : IDL.Record({
+      'actual' : IDL.Nat64,
+      'expected' : IDL.Nat64,
+    }),
+    'GasLimitTooLarge' : IDL.Null,
+    'NotReplayProtected' : IDL.Null,
+    'InvalidSignature' : IDL.Null,
+    'PriorityFeeAboveMaxFee' : IDL.Null,
+    'InvalidR' : IDL.Null,
+    'InvalidS' : IDL.Null,
+    'HighS' : IDL.Null,
+    'InitCodeTooLarge' : IDL.Record({
+      'size' : IDL.Nat64,
+      'limit' : IDL.Nat64,
+    }),
+    'NonceTooLarge' : IDL.Null,
+    'UnsupportedType' : IDL.Record({ 'tx_type' : IDL.Nat8 }),
+  });
+  const Result_29 = IDL.Variant({
+    'Ok' : IDL.Vec(Violation),
+    'Err' : IDL.Text,
+  });
+  const ValidationRules = IDL.Record({
+    'eip155_required' : IDL.Bool,
+    'chain_id' : IDL.Opt(IDL.Nat64),
+    'max_init_code_size' : IDL.Opt(IDL.Nat64),
+  });
+  const Result_30 = IDL.Variant({
     'Ok' : IDL.Opt(IDL.Vec(IDL.Nat8)),
     'Err' : IDL.Text,
   });
   return IDL.Service({
-    'create_transaction' : IDL.Func([Transaction], [Result], ['query']),
-    'encode_signed_transaction' : IDL.Func([Transaction], [Result], ['query']),
+    'access_list_from_json' : IDL.Func(
+        [IDL.Vec(IDL.Nat8)],
+        [Result],
+        ['query'],
+      ),
+    'access_list_gas' : IDL.Func([IDL.Vec(AccessList)], [IDL.Nat64], ['query']),
+    'add_chain' : IDL.Func([Chain], [Result_1], []),
+    'allocate_nonce' : IDL.Func([IDL.Nat64, IDL.Vec(IDL.Nat8)], [Result_2], []),
+    'array_element_slot' : IDL.Func(
+        [IDL.Vec(IDL.Nat8), IDL.Vec(IDL.Nat8), IDL.Nat32],
+        [Result_3],
+        ['query'],
+      ),
+    'blob_schedule' : IDL.Func([IDL.Text], [Result_4], ['query']),
+    'bloom_contains' : IDL.Func(
+        [IDL.Vec(IDL.Nat8), IDL.Vec(IDL.Nat8)],
+        [Result_5],
+        ['query'],
+      ),
+    'build_siwe_message' : IDL.Func([SiweMessage], [Result_6], ['query']),
+    'build_token_transaction' : IDL.Func(
+        [IDL.Nat64, IDL.Vec(IDL.Nat8), TokenCall, TransactionRequest],
+        [Result_7],
+        ['query'],
+      ),
+    'build_transaction' : IDL.Func(
+        [IDL.Nat64, TransactionRequest],
+        [Result_7],
+        ['query'],
+      ),
+    'build_transaction_with_nonce' : IDL.Func(
+        [IDL.Nat64, IDL.Vec(IDL.Nat8), TransactionRequest],
+        [Result_7],
+        [],
+      ),
+    'cancel_transaction' : IDL.Func(
+        [Transaction, IDL.Vec(IDL.Nat8), ReplacementFees],
+        [Result_8],
+        ['query'],
+      ),
+    'check_signature' : IDL.Func(
+        [IDL.Vec(IDL.Nat8), IDL.Vec(IDL.Nat8), IDL.Vec(IDL.Nat8)],
+        [Result_9],
+        ['query'],
+      ),
+    'compress_public_key' : IDL.Func(
+        [IDL.Vec(IDL.Nat8)],
+        [Result_10],
+        ['query'],
+      ),
+    'create2_address' : IDL.Func(
+        [IDL.Vec(IDL.Nat8), IDL.Vec(IDL.Nat8), IDL.Vec(IDL.Nat8)],
+        [IDL.Vec(IDL.Nat8)],
+        ['query'],
+      ),
+    'create2_address_from_hash' : IDL.Func(
+        [IDL.Vec(IDL.Nat8), IDL.Vec(IDL.Nat8), IDL.Vec(IDL.Nat8)],
+        [IDL.Vec(IDL.Nat8)],
+        ['query'],
+      ),
+    'create_address' : IDL.Func(
+        [IDL.Vec(IDL.Nat8), IDL.Vec(IDL.Nat8)],
+        [IDL.Vec(IDL.Nat8)],
+        ['query'],
+      ),
+    'create_transaction' : IDL.Func([Transaction], [Result_11], ['query']),
+    'decode_handle_ops' : IDL.Func(
+        [IDL.Vec(IDL.Nat8)],
+        [IDL.Opt(HandleOps)],
+        ['query'],
+      ),
+    'decode_packed_slot' : IDL.Func(
+        [IDL.Vec(IDL.Nat8), IDL.Vec(IDL.Nat32)],
+        [Result_12],
+        ['query'],
+      ),
+    'decode_revert' : IDL.Func(
+        [IDL.Vec(IDL.Nat8), IDL.Opt(IDL.Text)],
+        [Result_13],
+        ['query'],
+      ),
+    'decode_token_call' : IDL.Func(
+        [IDL.Vec(IDL.Nat8)],
+        [IDL.Opt(TokenCall)],
+        ['query'],
+      ),
+    'decompress_public_key' : IDL.Func(
+        [IDL.Vec(IDL.Nat8)],
+        [Result_10],
+        ['query'],
+      ),
+    'domain_separator' : IDL.Func([Domain], [IDL.Vec(IDL.Nat8)], ['query']),
+    'eip1967_slot' : IDL.Func([IDL.Text], [IDL.Vec(IDL.Nat8)], ['query']),
+    'encode_receipt' : IDL.Func([Receipt], [Result_10], ['query']),
+    'encode_safe_signatures' : IDL.Func(
+        [
+          IDL.Vec(IDL.Nat8),
+          IDL.Nat64,
+          SafeTransaction,
+          IDL.Vec(OwnerSignature),
+        ],
+        [Result_10],
+        ['query'],
+      ),
+    'encode_signed_transaction' : IDL.Func(
+        [Transaction],
+        [Result_11],
+        ['query'],
+      ),
+    'erc2612_permit_digest' : IDL.Func(
+        [Domain, Erc2612Permit],
+        [IDL.Vec(IDL.Nat8)],
+        ['query'],
+      ),
+    'erc7201_slot' : IDL.Func([IDL.Text], [IDL.Vec(IDL.Nat8)], ['query']),
+    'event_topic' : IDL.Func([IDL.Text], [IDL.Vec(IDL.Nat8)], ['query']),
+    'get_chain' : IDL.Func([IDL.Nat64], [IDL.Opt(Chain)], ['query']),
+    'get_nonce_account' : IDL.Func(
+        [IDL.Nat64, IDL.Vec(IDL.Nat8)],
+        [IDL.Opt(NonceAccount)],
+        ['query'],
+      ),
+    'get_rpc_config' : IDL.Func([IDL.Nat64], [Result_14], ['query']),
+    'get_siwe_config' : IDL.Func([], [IDL.Opt(SiweConfig)], ['query']),
+    'get_siwe_session' : IDL.Func(
+        [IDL.Principal],
+        [IDL.Opt(SiweSession)],
+        ['query'],
+      ),
+    'handle_ops_call_data' : IDL.Func(
+        [HandleOps],
+        [IDL.Vec(IDL.Nat8)],
+        ['query'],
+      ),
     'is_valid_public' : IDL.Func([IDL.Vec(IDL.Nat8)], [Result_1], ['query']),
     'is_valid_signature' : IDL.Func([IDL.Vec(IDL.Nat8)], [Result_1], ['query']),
+    'is_valid_signature_call' : IDL.Func(
+        [IDL.Vec(IDL.Nat8), IDL.Vec(IDL.Nat8), IDL.Vec(IDL.Nat8)],
+        [Erc1271Call],
+        ['query'],
+      ),
+    'is_valid_signature_result' : IDL.Func(
+        [IDL.Vec(IDL.Nat8)],
+        [IDL.Bool],
+        ['query'],
+      ),
     'keccak256' : IDL.Func([IDL.Vec(IDL.Nat8)], [IDL.Vec(IDL.Nat8)], ['query']),
-    'parse_transaction' : IDL.Func([IDL.Vec(IDL.Nat8)], [Result_2], ['query']),
-    'pub_to_address' : IDL.Func([IDL.Vec(IDL.Nat8)], [Result_3], ['query']),
+    'list_chains' : IDL.Func([], [IDL.Vec(Chain)], ['query']),
+    'logs_bloom' : IDL.Func([IDL.Vec(Log)], [IDL.Vec(IDL.Nat8)], ['query']),
+    'mapping_access_list' : IDL.Func(
+        [IDL.Vec(IDL.Nat8), IDL.Vec(IDL.Nat8), IDL.Vec(MappingKey)],
+        [Result_15],
+        ['query'],
+      ),
+    'mapping_slot' : IDL.Func(
+        [MappingKey, IDL.Vec(IDL.Nat8)],
+        [Result_10],
+        ['query'],
+      ),
+    'merge_access_lists' : IDL.Func(
+        [IDL.Vec(IDL.Vec(AccessList))],
+        [IDL.Vec(AccessList)],
+        ['query'],
+      ),
+    'merge_blooms' : IDL.Func(
+        [IDL.Vec(IDL.Vec(IDL.Nat8))],
+        [Result_10],
+        ['query'],
+      ),
+    'multi_send_call_data' : IDL.Func(
+        [IDL.Vec(MultiSendTransaction)],
+        [IDL.Vec(IDL.Nat8)],
+        ['query'],
+      ),
+    'nested_mapping_slot' : IDL.Func(
+        [IDL.Vec(MappingKey), IDL.Vec(IDL.Nat8)],
+        [Result_10],
+        ['query'],
+      ),
+    'next_base_fee' : IDL.Func(
+        [IDL.Nat64, IDL.Nat64, IDL.Vec(IDL.Nat8)],
+        [Result_10],
+        ['query'],
+      ),
+    'next_blob_base_fee' : IDL.Func(
+        [IDL.Nat64, IDL.Nat64, IDL.Vec(IDL.Nat8), BlobSchedule],
+        [Result_10],
+        ['query'],
+      ),
+    'normalize_access_list' : IDL.Func(
+        [IDL.Vec(AccessList)],
+        [IDL.Vec(AccessList)],
+        ['query'],
+      ),
+    'pack_gas_values' : IDL.Func(
+        [IDL.Vec(IDL.Nat8), IDL.Vec(IDL.Nat8)],
+        [Result_10],
+        ['query'],
+      ),
+    'packed_user_operation_hash' : IDL.Func(
+        [PackedUserOperation, IDL.Opt(IDL.Vec(IDL.Nat8)), IDL.Nat64],
+        [IDL.Vec(IDL.Nat8)],
+        ['query'],
+      ),
+    'parse_block' : IDL.Func([IDL.Vec(IDL.Nat8)], [Result_16], ['query']),
+    'parse_receipt' : IDL.Func([IDL.Vec(IDL.Nat8)], [Result_17], ['query']),
+    'parse_rpc_response' : IDL.Func(
+        [RpcRequest, IDL.Vec(IDL.Nat8)],
+        [Result_18],
+        ['query'],
+      ),
+    'parse_siwe_message' : IDL.Func([IDL.Text], [Result_19], ['query']),
+    'parse_transaction' : IDL.Func([IDL.Vec(IDL.Nat8)], [Result_20], ['query']),
+    'permit2_digest' : IDL.Func(
+        [IDL.Nat64, Permit2],
+        [IDL.Vec(IDL.Nat8)],
+        ['query'],
+      ),
+    'pub_to_address' : IDL.Func([IDL.Vec(IDL.Nat8)], [Result_10], ['query']),
+    'raw_public_key' : IDL.Func([IDL.Vec(IDL.Nat8)], [Result_10], ['query']),
     'recover_public_key' : IDL.Func(
         [IDL.Vec(IDL.Nat8), IDL.Vec(IDL.Nat8)],
-        [Result_3],
+        [Result_10],
+        ['query'],
+      ),
+    'remove_chain' : IDL.Func([IDL.Nat64], [Result_1], []),
+    'remove_rpc_config' : IDL.Func([IDL.Nat64], [Result_1], []),
+    'requests_hash' : IDL.Func([ExecutionRequests], [Result_10], ['query']),
+    'resync_nonce' : IDL.Func(
+        [IDL.Nat64, IDL.Vec(IDL.Nat8), IDL.Nat64],
+        [Result_1],
+        [],
+      ),
+    'rlp_decode' : IDL.Func([IDL.Vec(IDL.Nat8)], [Result_21], ['query']),
+    'rlp_encode' : IDL.Func([List], [Result_10], ['query']),
+    'rpc_call' : IDL.Func([IDL.Nat64, RpcRequest], [Result_18], []),
+    'rpc_call_cycles' : IDL.Func(
+        [IDL.Nat64, RpcRequest],
+        [Result_22],
+        ['query'],
+      ),
+    'rpc_request' : IDL.Func(
+        [RpcRequest, IDL.Nat64],
+        [IDL.Vec(IDL.Nat8)],
+        ['query'],
+      ),
+    'safe_exec_transaction' : IDL.Func(
+        [
+          IDL.Vec(IDL.Nat8),
+          IDL.Nat64,
+          SafeTransaction,
+          IDL.Vec(OwnerSignature),
+        ],
+        [Result_10],
+        ['query'],
+      ),
+    'safe_transaction_hash' : IDL.Func(
+        [IDL.Vec(IDL.Nat8), IDL.Nat64, SafeTransaction],
+        [IDL.Vec(IDL.Nat8)],
+        ['query'],
+      ),
+    'set_rpc_config' : IDL.Func([RpcConfig], [Result_1], []),
+    'set_siwe_config' : IDL.Func([SiweConfig], [Result_1], []),
+    'set_transaction_status' : IDL.Func(
+        [IDL.Nat64, IDL.Vec(IDL.Nat8), IDL.Vec(IDL.Nat8), TransactionStatus],
+        [Result_1],
+        [],
+      ),
+    'simulate_call' : IDL.Func(
+        [Header, IDL.Nat64, IDL.Vec(ProvenAccount), CallRequest],
+        [Result_23],
+        ['query'],
+      ),
+    'siwe_login' : IDL.Func([IDL.Text, IDL.Vec(IDL.Nat8)], [Result_24], []),
+    'siwe_logout' : IDL.Func([], [], []),
+    'siwe_prepare_login' : IDL.Func([IDL.Vec(IDL.Nat8)], [Result_6], []),
+    'speed_up_transaction' : IDL.Func(
+        [Transaction, ReplacementFees],
+        [Result_8],
+        ['query'],
+      ),
+    'struct_field_slots' : IDL.Func(
+        [IDL.Vec(IDL.Nat8), IDL.Vec(IDL.Nat32)],
+        [Result_25],
+        ['query'],
+      ),
+    'suggest_fees' : IDL.Func(
+        [FeeHistory, IDL.Vec(IDL.Float64), IDL.Float64],
+        [Result_26],
+        ['query'],
+      ),
+    'token_call_data' : IDL.Func([TokenCall], [IDL.Vec(IDL.Nat8)], ['query']),
+    'track_transaction' : IDL.Func([IDL.Vec(IDL.Nat8)], [Result_10], []),
+    'transaction_cost' : IDL.Func(
+        [Transaction, IDL.Opt(IDL.Vec(IDL.Nat8))],
+        [Result_27],
+        ['query'],
+      ),
+    'transaction_hash' : IDL.Func([IDL.Vec(IDL.Nat8)], [Result_10], ['query']),
+    'transform_rpc_response' : IDL.Func(
+        [TransformArgs],
+        [HttpResponse],
+        ['query'],
+      ),
+    'unwrap_erc6492_signature' : IDL.Func(
+        [IDL.Vec(IDL.Nat8)],
+        [Result_28],
+        ['query'],
+      ),
+    'user_operation_hash' : IDL.Func(
+        [UserOperation, IDL.Opt(IDL.Vec(IDL.Nat8)), IDL.Nat64],
+        [IDL.Vec(IDL.Nat8)],
+        ['query'],
+      ),
+    'validate_chain_transaction' : IDL.Func(
+        [IDL.Nat64, IDL.Vec(IDL.Nat8)],
+        [Result_29],
+        ['query'],
+      ),
+    'validate_transaction' : IDL.Func(
+        [IDL.Vec(IDL.Nat8), ValidationRules],
+        [Result_29],
+        ['query'],
+      ),
+    'verify_permit_signature' : IDL.Func(
+        [IDL.Vec(IDL.Nat8), IDL.Vec(IDL.Nat8), IDL.Vec(IDL.Nat8)],
+        [Result_5],
         ['query'],
       ),
-    'rlp_decode' : IDL.Func([IDL.Vec(IDL.Nat8)], [Result_4], ['query']),
-    'rlp_encode' : IDL.Func([List], [Result_3], ['query']),
     'verify_proof' : IDL.Func(
         [IDL.Vec(IDL.Nat8), IDL.Vec(IDL.Nat8), IDL.Vec(IDL.Vec(IDL.Nat8))],
-        [Result_5],
+        [Result_30],
+        ['query'],
+      ),
+    'verify_siwe_message' : IDL.Func(
+        [IDL.Text, IDL.Vec(IDL.Nat8), IDL.Text],
+        [Result_19],
+        ['query'],
+      ),
+    'withdrawals_root' : IDL.Func(
+        [IDL.Vec(Withdrawal)],
+        [Result_10],
+        ['query'],
+      ),
+    'wrap_erc6492_signature' : IDL.Func(
+        [Erc6492Signature],
+        [IDL.Vec(IDL.Nat8)],
         ['query'],
       ),
   });
 };
-export const init = ({ IDL }) => { return []; };
+export const init = ({ IDL }) => { return []; };
\ No newline at end of file
@@ -11,7 +11,7 @@ test("create_transaction", async () => {
 
     let item = {
         Legacy: {
-            to: ethers.utils.arrayify(ethers.utils.hexlify("0xe94f1fa4f27d9d288ffea234bb62e1fbc086ca0c")),
+            to: [ethers.utils.arrayify(ethers.utils.hexlify("0xe94f1fa4f27d9d288ffea234bb62e1fbc086ca0c"))],
             value: arr,
             data: [],
             sign: [],
@@ -56,7 +56,7 @@ test("encode_signed_transaction", async () => {
 
     let item = {
         Legacy: {
-            to: ethers.utils.arrayify(ethers.utils.hexlify("0xe94f1fa4f27d9d288ffea234bb62e1fbc086ca0c")),
+            to: [ethers.utils.arrayify(ethers.utils.hexlify("0xe94f1fa4f27d9d288ffea234bb62e1fbc086ca0c"))],
             value: arr,
             data: [],
             sign: [
@@ -123,9 +123,14 @@ test("parse_transaction", async () => {
 
     let decoded = await can.parse_transaction(ethers.utils.arrayify(raw_tx));
 
-    expect(decoded.Ok.Legacy).not.toBeUndefined();
-    expect(BigNumber.from(decoded.Ok.Legacy.gas_limit)).toStrictEqual(BigNumber.from(1));
-    expect(BigNumber.from(decoded.Ok.Legacy.gas_price)).toStrictEqual(BigNumber.from(10_000));
-    expect(BigNumber.from(decoded.Ok.Legacy.nonce)).toStrictEqual(BigNumber.from(1));
-    expect(BigNumber.from(decoded.Ok.Legacy.chain_id)).toStrictEqual(BigNumber.from(1));
+    let legacy = decoded.Ok.transaction.Legacy;
+
+    expect(legacy).not.toBeUndefined();
+    expect(BigNumber.from(legacy.gas_limit)).toStrictEqual(BigNumber.from(1));
+    expect(BigNumber.from(legacy.gas_price)).toStrictEqual(BigNumber.from(10_000));
+    expect(BigNumber.from(legacy.nonce)).toStrictEqual(BigNumber.from(1));
+    expect(BigNumber.from(legacy.chain_id)).toStrictEqual(BigNumber.from(1));
+    expect(decoded.Ok.tx_type).toBe(0);
+    expect(decoded.Ok.from).toStrictEqual([]);
+    expect(ethers.utils.hexlify(decoded.Ok.hash)).toBe(ethers.utils.keccak256(raw_tx));
 });
@@ -0,0 +1,72 @@
+use candid::candid_method;
+use ic_cdk_macros::query;
+use serde_json::Value;
+
+use crate::types::{
+    access_list::AccessList,
+    address::Address,
+    num::H256,
+    rpc::{to_created_access_list, CreatedAccessList},
+    storage::{self, MappingKey},
+};
+
+/// Merges entries of the same address, removes duplicated storage keys and sorts entries
+#[query]
+#[candid_method(query)]
+fn normalize_access_list(list: Vec<AccessList>) -> Vec<AccessList> {
+    AccessList::normalize(list)
+}
+
+/// Merges multiple access lists in to one normalized list
+#[query]
+#[candid_method(query)]
+fn merge_access_lists(lists: Vec<Vec<AccessList>>) -> Vec<AccessList> {
+    AccessList::normalize(lists.into_iter().flatten().collect())
+}
+
+/// Returns intrinsic gas paid for access list
+#[query]
+#[candid_method(query)]
+fn access_list_gas(list: Vec<AccessList>) -> u64 {
+    AccessList::gas(&list)
+}
+
+/// Converts eth_createAccessList response, either whole JSON-RPC response or its result
+#[query]
+#[candid_method(query)]
+fn access_list_from_json(body: Vec<u8>) -> Result<CreatedAccessList, String> {
+    let body: Value = serde_json::from_slice(&body).map_err(|x| format!("Invalid JSON {x}"))?;
+    let result = body.get("result").unwrap_or(&body);
+
+    to_created_access_list(result).map_err(|x| format!("Error while parsing access list {x}"))
+}
+
+/// Returns storage slot of a mapping value, keccak256(key . slot)
+#[query]
+#[candid_method(query)]
+fn mapping_slot(key: MappingKey, slot: H256) -> Result<H256, String> {
+    storage::mapping_slot(&key, &slot).map_err(|x| format!("Invalid mapping key {x}"))
+}
+
+/// Builds access list entry of a contract with values of a mapping under given keys,
+/// like balances of ERC20 token holders
+#[query]
+#[candid_method(query)]
+fn mapping_access_list(
+    address: Address,
+    slot: H256,
+    keys: Vec<MappingKey>,
+) -> Result<AccessList, String> {
+    let storage_keys = keys
+        .iter()
+        .map(|key| storage::mapping_slot(key, &slot))
+        .collect::<Result<Vec<H256>, _>>()
+        .map_err(|x| format!("Invalid mapping key {x}"))?;
+
+    let mut list = AccessList::normalize(vec![AccessList {
+        address,
+        storage_keys,
+    }]);
+
+    Ok(list.remove(0))
+}
@@ -0,0 +1,12 @@
+use candid::candid_method;
+use ic_cdk_macros::query;
+
+use crate::types::block::Block;
+
+/// Parses rlp encoded block with its transactions, ommers and withdrawals.
+/// Fails if transactions root, ommers hash or withdrawals root do not match the header
+#[query]
+#[candid_method(query)]
+fn parse_block(data: Vec<u8>) -> Result<Block, String> {
+    Block::decode(&data).map_err(|x| format!("Error while decoding block {x}"))
+}
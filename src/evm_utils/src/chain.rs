@@ -0,0 +1,108 @@
+use candid::candid_method;
+use ic_cdk_macros::{query, update};
+
+use crate::{
+    state::{ensure_owner, with_state, with_state_mut},
+    types::{
+        chain::{builtin_chains, Chain, TransactionRequest},
+        transaction::Transaction,
+        validation::Violation,
+    },
+};
+
+/// Finds chain by its id, chains added by the owner take precedence over built in ones
+pub fn find_chain(chain_id: u64) -> Option<Chain> {
+    with_state(|x| x.chains.iter().find(|c| c.chain_id == chain_id).cloned()).or_else(|| {
+        builtin_chains()
+            .into_iter()
+            .find(|c| c.chain_id == chain_id)
+    })
+}
+
+/// Returns all chains known to the canister
+#[query]
+#[candid_method(query)]
+fn list_chains() -> Vec<Chain> {
+    let mut chains = with_state(|x| x.chains.clone());
+    for chain in builtin_chains() {
+        if !chains.iter().any(|c| c.chain_id == chain.chain_id) {
+            chains.push(chain);
+        }
+    }
+
+    chains
+}
+
+#[query]
+#[candid_method(query)]
+fn get_chain(chain_id: u64) -> Option<Chain> {
+    find_chain(chain_id)
+}
+
+/// Builds unsigned transaction of a type supported by the chain, ready for create_transaction
+#[query]
+#[candid_method(query)]
+fn build_transaction(chain_id: u64, req: TransactionRequest) -> Result<Transaction, String> {
+    let chain = find_chain(chain_id).ok_or(format!("Unknown chain {chain_id}"))?;
+
+    chain
+        .build_transaction(req)
+        .map_err(|x| format!("Error while building transaction for {} {x}", chain.name))
+}
+
+/// Validates raw transaction against rules of given chain
+#[query]
+#[candid_method(query)]
+fn validate_chain_transaction(chain_id: u64, data: Vec<u8>) -> Result<Vec<Violation>, String> {
+    let chain = find_chain(chain_id).ok_or(format!("Unknown chain {chain_id}"))?;
+    let tx = Transaction::decode_unchecked(&data)
+        .map_err(|x| format!("Error while decoding transaction {x}"))?;
+
+    Ok(chain.validate(&tx))
+}
+
+/// Adds custom chain or replaces existing one, can be called only by the owner
+#[update]
+#[candid_method(update)]
+fn add_chain(chain: Chain) -> Result<(), String> {
+    ensure_owner(&ic_cdk::caller())?;
+
+    with_state_mut(|x| {
+        x.chains.retain(|c| c.chain_id != chain.chain_id);
+        x.chains.push(chain);
+    });
+
+    Ok(())
+}
+
+/// Removes custom chain, can be called only by the owner
+#[update]
+#[candid_method(update)]
+fn remove_chain(chain_id: u64) -> Result<(), String> {
+    ensure_owner(&ic_cdk::caller())?;
+
+    with_state_mut(|x| x.chains.retain(|c| c.chain_id != chain_id));
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{find_chain, list_chains};
+    use crate::{state::with_state_mut, types::chain::Chain};
+
+    #[test]
+    fn custom_chain_overrides_builtin() {
+        assert_eq!(find_chain(1).unwrap().name, "Ethereum Mainnet");
+        assert!(find_chain(31337).is_none());
+
+        with_state_mut(|x| {
+            x.chains.push(Chain::new(1, "Custom Mainnet", &[0]));
+            x.chains.push(Chain::new(31337, "Anvil", &[0, 1, 2]));
+        });
+
+        assert_eq!(find_chain(1).unwrap().name, "Custom Mainnet");
+        assert_eq!(find_chain(31337).unwrap().name, "Anvil");
+        assert_eq!(list_chains().iter().filter(|c| c.chain_id == 1).count(), 1);
+    }
+}
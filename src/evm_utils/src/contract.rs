@@ -0,0 +1,33 @@
+use candid::candid_method;
+use ic_cdk::query;
+
+use crate::{
+    types::{
+        address::Address,
+        num::{H256, U256},
+    },
+    utils::keccak256,
+};
+
+/// Returns address of a contract deployed with CREATE by sender at given nonce
+#[query]
+#[candid_method(query)]
+fn create_address(sender: Address, nonce: U256) -> Address {
+    Address::from_create(&sender, &nonce)
+}
+
+/// Returns address of a contract deployed with CREATE2 from given init code
+#[query]
+#[candid_method(query)]
+fn create2_address(deployer: Address, salt: H256, init_code: Vec<u8>) -> Address {
+    let init_code_hash = keccak256(&[&init_code]);
+
+    Address::from_create2(&deployer, &salt, &init_code_hash)
+}
+
+/// Returns address of a contract deployed with CREATE2, when only hash of init code is known
+#[query]
+#[candid_method(query)]
+fn create2_address_from_hash(deployer: Address, salt: H256, init_code_hash: U256) -> Address {
+    Address::from_create2(&deployer, &salt, &init_code_hash)
+}
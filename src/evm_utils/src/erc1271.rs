@@ -0,0 +1,51 @@
+use candid::candid_method;
+use ic_cdk_macros::query;
+
+use crate::types::{
+    address::Address,
+    erc1271::{self, Erc1271Call, Erc6492Signature, SignatureCheck},
+    num::H256,
+};
+
+/// Verifies signature of a hash by the signer. ECDSA signatures are verified directly, otherwise
+/// returns `isValidSignature(bytes32,bytes)` call (ERC-1271) to be made with `eth_call`, together
+/// with factory call when signature is wrapped with ERC-6492
+#[query]
+#[candid_method(query)]
+fn check_signature(
+    signer: Address,
+    hash: H256,
+    signature: Vec<u8>,
+) -> Result<SignatureCheck, String> {
+    SignatureCheck::new(&signer, &hash, &signature)
+        .map_err(|x| format!("Error while reading signature {x}"))
+}
+
+/// Builds `isValidSignature(bytes32,bytes)` call of the signer contract
+#[query]
+#[candid_method(query)]
+fn is_valid_signature_call(signer: Address, hash: H256, signature: Vec<u8>) -> Erc1271Call {
+    Erc1271Call::new(&signer, &hash, &signature)
+}
+
+/// Decodes ERC-6492 wrapper, returns None when signature is not wrapped
+#[query]
+#[candid_method(query)]
+fn unwrap_erc6492_signature(signature: Vec<u8>) -> Result<Option<Erc6492Signature>, String> {
+    Erc6492Signature::unwrap(&signature)
+        .map_err(|x| format!("Error while unwrapping signature {x}"))
+}
+
+/// Wraps signature of a contract that is not deployed yet with its factory call (ERC-6492)
+#[query]
+#[candid_method(query)]
+fn wrap_erc6492_signature(signature: Erc6492Signature) -> Vec<u8> {
+    signature.wrap()
+}
+
+/// Checks that output of `isValidSignature` call is the 0x1626ba7e magic value
+#[query]
+#[candid_method(query)]
+fn is_valid_signature_result(output: Vec<u8>) -> bool {
+    erc1271::is_valid_result(&output)
+}
@@ -0,0 +1,55 @@
+pub const BASE: u64 = 2;
+pub const VERY_LOW: u64 = 3;
+pub const LOW: u64 = 5;
+pub const MID: u64 = 8;
+pub const HIGH: u64 = 10;
+pub const JUMPDEST: u64 = 1;
+pub const BLOCKHASH: u64 = 20;
+
+pub const EXP: u64 = 10;
+pub const EXP_BYTE: u64 = 50;
+pub const KECCAK256: u64 = 30;
+pub const KECCAK256_WORD: u64 = 6;
+pub const COPY_WORD: u64 = 3;
+pub const MEMORY_WORD: u64 = 3;
+pub const QUAD_COEFF_DIV: u64 = 512;
+
+pub const LOG: u64 = 375;
+pub const LOG_TOPIC: u64 = 375;
+pub const LOG_DATA: u64 = 8;
+
+/// EIP-2929 access costs
+pub const WARM_ACCESS: u64 = 100;
+pub const COLD_ACCOUNT_ACCESS: u64 = 2_600;
+pub const COLD_SLOAD: u64 = 2_100;
+
+/// EIP-2200 and EIP-3529 storage costs
+pub const SSTORE_SET: u64 = 20_000;
+pub const SSTORE_RESET: u64 = 2_900;
+pub const SSTORE_CLEARS_REFUND: i64 = 4_800;
+pub const SSTORE_STIPEND: u64 = 2_300;
+pub const MAX_REFUND_QUOTIENT: u64 = 5;
+
+pub const CALL_VALUE: u64 = 9_000;
+pub const CALL_STIPEND: u64 = 2_300;
+pub const NEW_ACCOUNT: u64 = 25_000;
+pub const CREATE: u64 = 32_000;
+pub const CODE_DEPOSIT: u64 = 200;
+pub const SELFDESTRUCT: u64 = 5_000;
+
+pub const MAX_CODE_SIZE: usize = 24_576;
+pub const MAX_INIT_CODE_SIZE: usize = 2 * MAX_CODE_SIZE;
+
+pub fn words(len: usize) -> u64 {
+    (len as u64).div_ceil(32)
+}
+
+/// Cost of memory of given size in words
+pub fn memory(words: u64) -> u64 {
+    MEMORY_WORD * words + words * words / QUAD_COEFF_DIV
+}
+
+/// Gas that can be passed to a sub call, all but one 64th of available gas (EIP-150)
+pub fn all_but_one_64th(gas: u64) -> u64 {
+    gas - gas / 64
+}
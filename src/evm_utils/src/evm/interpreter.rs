@@ -0,0 +1,1213 @@
+use std::rc::Rc;
+
+use primitive_types::{U256 as Uint, U512};
+
+use super::{gas, precompiles, state::State, Env};
+use crate::{
+    types::{
+        address::Address,
+        errors::ExecutionError,
+        gas::INIT_CODE_WORD_GAS,
+        log::Log,
+        num::{H256, U256},
+    },
+    utils::keccak256,
+};
+
+pub const MAX_STACK: usize = 1024;
+pub const MAX_DEPTH: usize = 1024;
+/// Memory above this size could not be paid for with any realistic gas limit
+const MAX_MEMORY: u64 = u32::MAX as u64;
+
+/// Exceptional halt of a frame, all gas passed to it is consumed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Halt {
+    OutOfGas,
+    StackUnderflow,
+    StackOverflow,
+    InvalidJump,
+    InvalidOpcode(u8),
+    StaticStateChange,
+    InvalidReturnDataAccess,
+    CreateCollision,
+    InvalidCodePrefix,
+    CodeSizeLimit,
+    InitCodeSizeLimit,
+}
+
+/// Stops execution of a frame, either exceptional halt or missing state
+enum Interrupt {
+    Halt(Halt),
+    Error(ExecutionError),
+}
+
+impl From<Halt> for Interrupt {
+    fn from(halt: Halt) -> Self {
+        Interrupt::Halt(halt)
+    }
+}
+
+impl From<ExecutionError> for Interrupt {
+    fn from(error: ExecutionError) -> Self {
+        Interrupt::Error(error)
+    }
+}
+
+/// Result of a message call or contract creation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    pub success: bool,
+    pub gas_left: u64,
+    /// Returned or reverted data
+    pub output: Vec<u8>,
+    pub halt: Option<Halt>,
+}
+
+impl Outcome {
+    /// Call was not executed, gas is returned to the caller
+    fn rejected(gas: u64) -> Self {
+        Self {
+            success: false,
+            gas_left: gas,
+            output: vec![],
+            halt: None,
+        }
+    }
+
+    fn halted(halt: Halt) -> Self {
+        Self {
+            success: false,
+            gas_left: 0,
+            output: vec![],
+            halt: Some(halt),
+        }
+    }
+}
+
+pub struct Message {
+    pub caller: Address,
+    /// Account which storage and balance is used
+    pub address: Address,
+    /// Account which code is executed, differs from address for DELEGATECALL and CALLCODE
+    pub code_address: Address,
+    pub value: Uint,
+    pub input: Vec<u8>,
+    pub gas: u64,
+    pub is_static: bool,
+    pub depth: usize,
+    /// Value is moved from caller to address, false for DELEGATECALL
+    pub transfer: bool,
+}
+
+pub fn to_word(address: &Address) -> Uint {
+    Uint::from_big_endian(&address.0)
+}
+
+pub fn to_address(word: Uint) -> Address {
+    let mut buf = [0u8; 32];
+    word.to_big_endian(&mut buf);
+
+    let mut address = Address([0u8; 20]);
+    address.0.copy_from_slice(&buf[12..]);
+    address
+}
+
+fn to_h256(word: Uint) -> H256 {
+    U256::from(word).into()
+}
+
+/// Converts offset or size to usize, values too large to be paid for cause out of gas
+fn to_usize(word: Uint) -> Result<usize, Halt> {
+    if word > Uint::from(MAX_MEMORY) {
+        return Err(Halt::OutOfGas);
+    }
+
+    Ok(word.as_usize())
+}
+
+/// Converts word to u64, saturating at u64::MAX
+fn saturating_u64(word: Uint) -> u64 {
+    if word > Uint::from(u64::MAX) {
+        return u64::MAX;
+    }
+
+    word.as_u64()
+}
+
+fn is_negative(x: &Uint) -> bool {
+    x.bit(255)
+}
+
+fn negate(x: Uint) -> Uint {
+    (!x).overflowing_add(Uint::one()).0
+}
+
+fn abs(x: Uint) -> Uint {
+    if is_negative(&x) {
+        negate(x)
+    } else {
+        x
+    }
+}
+
+fn sdiv(a: Uint, b: Uint) -> Uint {
+    if b.is_zero() {
+        return Uint::zero();
+    }
+
+    let result = abs(a) / abs(b);
+    if is_negative(&a) != is_negative(&b) {
+        negate(result)
+    } else {
+        result
+    }
+}
+
+fn smod(a: Uint, b: Uint) -> Uint {
+    if b.is_zero() {
+        return Uint::zero();
+    }
+
+    let result = abs(a) % abs(b);
+    if is_negative(&a) {
+        negate(result)
+    } else {
+        result
+    }
+}
+
+fn slt(a: Uint, b: Uint) -> bool {
+    let sign = Uint::one() << 255;
+    (a ^ sign) < (b ^ sign)
+}
+
+fn sar(shift: Uint, value: Uint) -> Uint {
+    let negative = is_negative(&value);
+    if shift >= Uint::from(256) {
+        return if negative { Uint::MAX } else { Uint::zero() };
+    }
+
+    let shift = shift.as_usize();
+    if negative {
+        !((!value) >> shift)
+    } else {
+        value >> shift
+    }
+}
+
+fn signextend(size: Uint, value: Uint) -> Uint {
+    if size >= Uint::from(31) {
+        return value;
+    }
+
+    let bit = size.as_usize() * 8 + 7;
+    let mask = (Uint::one() << (bit + 1)) - 1;
+    if value.bit(bit) {
+        value | !mask
+    } else {
+        value & mask
+    }
+}
+
+fn modular(a: Uint, b: Uint, n: Uint, op: fn(U512, U512) -> U512) -> Uint {
+    if n.is_zero() {
+        return Uint::zero();
+    }
+
+    let result = op(U512::from(a), U512::from(b)) % U512::from(n);
+    Uint::try_from(result).unwrap_or_default()
+}
+
+fn bool_word(x: bool) -> Uint {
+    if x {
+        Uint::one()
+    } else {
+        Uint::zero()
+    }
+}
+
+/// Marks positions of JUMPDEST instructions, skipping push data
+fn jump_destinations(code: &[u8]) -> Vec<bool> {
+    let mut result = vec![false; code.len()];
+    let mut i = 0;
+    while i < code.len() {
+        let op = code[i];
+        if op == 0x5b {
+            result[i] = true;
+        } else if (0x60..=0x7f).contains(&op) {
+            i += (op - 0x5f) as usize;
+        }
+        i += 1;
+    }
+
+    result
+}
+
+/// Copies slice of data padded with zeros, used by CALLDATACOPY, CODECOPY and EXTCODECOPY
+fn padded(data: &[u8], offset: Uint, len: usize) -> Vec<u8> {
+    let mut buf = vec![0u8; len];
+    if offset < Uint::from(data.len()) {
+        let offset = offset.as_usize();
+        let end = data.len().min(offset + len);
+        buf[..end - offset].copy_from_slice(&data[offset..end]);
+    }
+
+    buf
+}
+
+struct Frame<'a> {
+    code: &'a [u8],
+    jump_destinations: Vec<bool>,
+    pc: usize,
+    stack: Vec<Uint>,
+    memory: Vec<u8>,
+    gas: u64,
+    return_data: Vec<u8>,
+}
+
+impl<'a> Frame<'a> {
+    fn new(code: &'a [u8], gas: u64) -> Self {
+        Self {
+            code,
+            jump_destinations: jump_destinations(code),
+            pc: 0,
+            stack: Vec::with_capacity(MAX_STACK),
+            memory: vec![],
+            gas,
+            return_data: vec![],
+        }
+    }
+
+    fn charge(&mut self, cost: u64) -> Result<(), Halt> {
+        if cost > self.gas {
+            return Err(Halt::OutOfGas);
+        }
+        self.gas -= cost;
+
+        Ok(())
+    }
+
+    fn pop(&mut self) -> Result<Uint, Halt> {
+        self.stack.pop().ok_or(Halt::StackUnderflow)
+    }
+
+    fn push(&mut self, value: Uint) -> Result<(), Halt> {
+        if self.stack.len() >= MAX_STACK {
+            return Err(Halt::StackOverflow);
+        }
+        self.stack.push(value);
+
+        Ok(())
+    }
+
+    /// Charges memory expansion for a range, returns offset and length of the range
+    fn expand(&mut self, offset: Uint, len: Uint) -> Result<(usize, usize), Halt> {
+        let len = to_usize(len)?;
+        if len == 0 {
+            return Ok((0, 0));
+        }
+
+        let offset = to_usize(offset)?;
+        let end = offset as u64 + len as u64;
+        if end > MAX_MEMORY {
+            return Err(Halt::OutOfGas);
+        }
+
+        let current = gas::words(self.memory.len());
+        let required = end.div_ceil(32);
+        if required > current {
+            self.charge(gas::memory(required) - gas::memory(current))?;
+            self.memory.resize(required as usize * 32, 0);
+        }
+
+        Ok((offset, len))
+    }
+
+    fn read(&self, offset: usize, len: usize) -> Vec<u8> {
+        self.memory[offset..offset + len].to_vec()
+    }
+
+    fn write(&mut self, offset: usize, data: &[u8]) {
+        self.memory[offset..offset + data.len()].copy_from_slice(data);
+    }
+
+    fn jump(&mut self, destination: Uint) -> Result<(), Halt> {
+        if destination >= Uint::from(self.code.len())
+            || !self.jump_destinations[destination.as_usize()]
+        {
+            return Err(Halt::InvalidJump);
+        }
+        self.pc = destination.as_usize();
+
+        Ok(())
+    }
+}
+
+pub struct Interpreter<'a> {
+    env: &'a Env,
+    pub state: State,
+}
+
+impl<'a> Interpreter<'a> {
+    pub fn new(env: &'a Env, state: State) -> Self {
+        Self { env, state }
+    }
+
+    /// Executes message call, changes to state are reverted if it does not succeed
+    pub fn call(&mut self, message: Message) -> Result<Outcome, ExecutionError> {
+        if message.depth > MAX_DEPTH {
+            return Ok(Outcome::rejected(message.gas));
+        }
+
+        let snapshot = self.state.clone();
+        if message.transfer
+            && !self
+                .state
+                .transfer(&message.caller, &message.address, message.value)?
+        {
+            return Ok(Outcome::rejected(message.gas));
+        }
+
+        let outcome = if precompiles::precompile(&message.code_address).is_some() {
+            match precompiles::execute(&message.code_address, &message.input, message.gas)? {
+                Some((used, output)) => Outcome {
+                    success: true,
+                    gas_left: message.gas - used,
+                    output,
+                    halt: None,
+                },
+                None => Outcome::halted(Halt::OutOfGas),
+            }
+        } else {
+            let code = self.state.code(&message.code_address)?;
+            self.run(&message, &code)?
+        };
+
+        if !outcome.success {
+            self.state = snapshot;
+        }
+
+        Ok(outcome)
+    }
+
+    /// Creates a contract, address is derived from nonce of the creator or from salt (CREATE2)
+    pub fn create(
+        &mut self,
+        creator: &Address,
+        value: Uint,
+        init_code: Vec<u8>,
+        gas: u64,
+        depth: usize,
+        salt: Option<Uint>,
+    ) -> Result<(Outcome, Option<Address>), ExecutionError> {
+        if depth > MAX_DEPTH || self.state.balance(creator)? < value {
+            return Ok((Outcome::rejected(gas), None));
+        }
+
+        let account = self.state.account_mut(creator)?;
+        let nonce = account.nonce;
+        if nonce == u64::MAX {
+            return Ok((Outcome::rejected(gas), None));
+        }
+        account.nonce += 1;
+
+        let address = match salt {
+            Some(salt) => Address::from_create2(creator, &to_h256(salt), &keccak256(&[&init_code])),
+            None => Address::from_create(creator, &U256::from(nonce)),
+        };
+        self.state.warm_account(&address);
+
+        // Addresses of new contracts are not expected to be proven, unknown account is
+        // treated as not existing
+        if let Some(x) = self.state.get(&address) {
+            if x.nonce != 0 || !x.code.is_empty() {
+                return Ok((Outcome::halted(Halt::CreateCollision), None));
+            }
+        }
+
+        let snapshot = self.state.clone();
+        self.state.create_account(&address);
+        self.state.transfer(creator, &address, value)?;
+
+        let message = Message {
+            caller: creator.clone(),
+            address: address.clone(),
+            code_address: address.clone(),
+            value,
+            input: vec![],
+            gas,
+            is_static: false,
+            depth,
+            transfer: false,
+        };
+        let mut outcome = self.run(&message, &init_code)?;
+
+        if outcome.success {
+            let deposit = gas::CODE_DEPOSIT * outcome.output.len() as u64;
+            let halt = if outcome.output.len() > gas::MAX_CODE_SIZE {
+                Some(Halt::CodeSizeLimit)
+            } else if outcome.output.first() == Some(&0xef) {
+                Some(Halt::InvalidCodePrefix)
+            } else if deposit > outcome.gas_left {
+                Some(Halt::OutOfGas)
+            } else {
+                None
+            };
+
+            match halt {
+                Some(halt) => outcome = Outcome::halted(halt),
+                None => {
+                    outcome.gas_left -= deposit;
+                    self.state.account_mut(&address)?.code = Rc::new(outcome.output.clone());
+                }
+            }
+        }
+
+        if !outcome.success {
+            self.state = snapshot;
+            return Ok((outcome, None));
+        }
+
+        Ok((outcome, Some(address)))
+    }
+
+    fn run(&mut self, message: &Message, code: &[u8]) -> Result<Outcome, ExecutionError> {
+        let mut frame = Frame::new(code, message.gas);
+
+        match self.execute(message, &mut frame) {
+            Ok((success, output)) => Ok(Outcome {
+                success,
+                gas_left: frame.gas,
+                output,
+                halt: None,
+            }),
+            Err(Interrupt::Halt(halt)) => Ok(Outcome::halted(halt)),
+            Err(Interrupt::Error(error)) => Err(error),
+        }
+    }
+
+    /// Charges EIP-2929 account access, returns cost
+    fn access_account(&mut self, address: &Address) -> u64 {
+        if self.state.warm_account(address) {
+            gas::COLD_ACCOUNT_ACCESS
+        } else {
+            gas::WARM_ACCESS
+        }
+    }
+
+    /// Runs instructions of a frame, returns success flag and output
+    fn execute(
+        &mut self,
+        message: &Message,
+        frame: &mut Frame,
+    ) -> Result<(bool, Vec<u8>), Interrupt> {
+        loop {
+            let op = match frame.code.get(frame.pc) {
+                Some(x) => *x,
+                None => return Ok((true, vec![])),
+            };
+            frame.pc += 1;
+
+            match op {
+                // STOP
+                0x00 => return Ok((true, vec![])),
+                // ADD, MUL, SUB, DIV, SDIV, MOD, SMOD
+                0x01..=0x07 => {
+                    frame.charge(if op == 0x01 || op == 0x03 {
+                        gas::VERY_LOW
+                    } else {
+                        gas::LOW
+                    })?;
+                    let a = frame.pop()?;
+                    let b = frame.pop()?;
+                    frame.push(match op {
+                        0x01 => a.overflowing_add(b).0,
+                        0x02 => a.overflowing_mul(b).0,
+                        0x03 => a.overflowing_sub(b).0,
+                        0x04 => a.checked_div(b).unwrap_or_default(),
+                        0x05 => sdiv(a, b),
+                        0x06 => a.checked_rem(b).unwrap_or_default(),
+                        _ => smod(a, b),
+                    })?;
+                }
+                // ADDMOD, MULMOD
+                0x08 | 0x09 => {
+                    frame.charge(gas::MID)?;
+                    let a = frame.pop()?;
+                    let b = frame.pop()?;
+                    let n = frame.pop()?;
+                    frame.push(if op == 0x08 {
+                        modular(a, b, n, |x, y| x + y)
+                    } else {
+                        modular(a, b, n, |x, y| x * y)
+                    })?;
+                }
+                // EXP
+                0x0a => {
+                    let base = frame.pop()?;
+                    let exponent = frame.pop()?;
+                    let bytes = exponent.bits().div_ceil(8) as u64;
+                    frame.charge(gas::EXP + gas::EXP_BYTE * bytes)?;
+                    frame.push(base.overflowing_pow(exponent).0)?;
+                }
+                // SIGNEXTEND
+                0x0b => {
+                    frame.charge(gas::LOW)?;
+                    let size = frame.pop()?;
+                    let value = frame.pop()?;
+                    frame.push(signextend(size, value))?;
+                }
+                // LT, GT, SLT, SGT, EQ
+                0x10..=0x14 => {
+                    frame.charge(gas::VERY_LOW)?;
+                    let a = frame.pop()?;
+                    let b = frame.pop()?;
+                    frame.push(bool_word(match op {
+                        0x10 => a < b,
+                        0x11 => a > b,
+                        0x12 => slt(a, b),
+                        0x13 => slt(b, a),
+                        _ => a == b,
+                    }))?;
+                }
+                // ISZERO
+                0x15 => {
+                    frame.charge(gas::VERY_LOW)?;
+                    let a = frame.pop()?;
+                    frame.push(bool_word(a.is_zero()))?;
+                }
+                // AND, OR, XOR
+                0x16..=0x18 => {
+                    frame.charge(gas::VERY_LOW)?;
+                    let a = frame.pop()?;
+                    let b = frame.pop()?;
+                    frame.push(match op {
+                        0x16 => a & b,
+                        0x17 => a | b,
+                        _ => a ^ b,
+                    })?;
+                }
+                // NOT
+                0x19 => {
+                    frame.charge(gas::VERY_LOW)?;
+                    let a = frame.pop()?;
+                    frame.push(!a)?;
+                }
+                // BYTE
+                0x1a => {
+                    frame.charge(gas::VERY_LOW)?;
+                    let index = frame.pop()?;
+                    let value = frame.pop()?;
+                    frame.push(if index < Uint::from(32) {
+                        Uint::from(value.byte(31 - index.as_usize()))
+                    } else {
+                        Uint::zero()
+                    })?;
+                }
+                // SHL, SHR, SAR
+                0x1b..=0x1d => {
+                    frame.charge(gas::VERY_LOW)?;
+                    let shift = frame.pop()?;
+                    let value = frame.pop()?;
+                    frame.push(match op {
+                        0x1d => sar(shift, value),
+                        _ if shift >= Uint::from(256) => Uint::zero(),
+                        0x1b => value << shift.as_usize(),
+                        _ => value >> shift.as_usize(),
+                    })?;
+                }
+                // KECCAK256
+                0x20 => {
+                    let offset = frame.pop()?;
+                    let len = frame.pop()?;
+                    let (offset, len) = frame.expand(offset, len)?;
+                    frame.charge(gas::KECCAK256 + gas::KECCAK256_WORD * gas::words(len))?;
+                    let hash = keccak256(&[&frame.read(offset, len)]);
+                    frame.push(Uint::from(&hash))?;
+                }
+                // ADDRESS
+                0x30 => {
+                    frame.charge(gas::BASE)?;
+                    frame.push(to_word(&message.address))?;
+                }
+                // BALANCE
+                0x31 => {
+                    let address = to_address(frame.pop()?);
+                    let cost = self.access_account(&address);
+                    frame.charge(cost)?;
+                    frame.push(self.state.balance(&address)?)?;
+                }
+                // ORIGIN
+                0x32 => {
+                    frame.charge(gas::BASE)?;
+                    frame.push(to_word(&self.env.origin))?;
+                }
+                // CALLER
+                0x33 => {
+                    frame.charge(gas::BASE)?;
+                    frame.push(to_word(&message.caller))?;
+                }
+                // CALLVALUE
+                0x34 => {
+                    frame.charge(gas::BASE)?;
+                    frame.push(message.value)?;
+                }
+                // CALLDATALOAD
+                0x35 => {
+                    frame.charge(gas::VERY_LOW)?;
+                    let offset = frame.pop()?;
+                    frame.push(Uint::from_big_endian(&padded(&message.input, offset, 32)))?;
+                }
+                // CALLDATASIZE
+                0x36 => {
+                    frame.charge(gas::BASE)?;
+                    frame.push(Uint::from(message.input.len()))?;
+                }
+                // CALLDATACOPY, CODECOPY
+                0x37 | 0x39 => {
+                    let memory_offset = frame.pop()?;
+                    let offset = frame.pop()?;
+                    let len = frame.pop()?;
+                    let (memory_offset, len) = frame.expand(memory_offset, len)?;
+                    frame.charge(gas::VERY_LOW + gas::COPY_WORD * gas::words(len))?;
+                    let data = if op == 0x37 {
+                        padded(&message.input, offset, len)
+                    } else {
+                        padded(frame.code, offset, len)
+                    };
+                    frame.write(memory_offset, &data);
+                }
+                // CODESIZE
+                0x38 => {
+                    frame.charge(gas::BASE)?;
+                    frame.push(Uint::from(frame.code.len()))?;
+                }
+                // GASPRICE
+                0x3a => {
+                    frame.charge(gas::BASE)?;
+                    frame.push(self.env.gas_price)?;
+                }
+                // EXTCODESIZE
+                0x3b => {
+                    let address = to_address(frame.pop()?);
+                    let cost = self.access_account(&address);
+                    frame.charge(cost)?;
+                    frame.push(Uint::from(self.state.code(&address)?.len()))?;
+                }
+                // EXTCODECOPY
+                0x3c => {
+                    let address = to_address(frame.pop()?);
+                    let memory_offset = frame.pop()?;
+                    let offset = frame.pop()?;
+                    let len = frame.pop()?;
+                    let (memory_offset, len) = frame.expand(memory_offset, len)?;
+                    let cost = self.access_account(&address);
+                    frame.charge(cost + gas::COPY_WORD * gas::words(len))?;
+                    let code = self.state.code(&address)?;
+                    frame.write(memory_offset, &padded(&code, offset, len));
+                }
+                // RETURNDATASIZE
+                0x3d => {
+                    frame.charge(gas::BASE)?;
+                    frame.push(Uint::from(frame.return_data.len()))?;
+                }
+                // RETURNDATACOPY
+                0x3e => {
+                    let memory_offset = frame.pop()?;
+                    let offset = frame.pop()?;
+                    let len = frame.pop()?;
+                    let end = offset.checked_add(len);
+                    if end.is_none_or(|x| x > Uint::from(frame.return_data.len())) {
+                        return Err(Halt::InvalidReturnDataAccess.into());
+                    }
+                    let (memory_offset, len) = frame.expand(memory_offset, len)?;
+                    frame.charge(gas::VERY_LOW + gas::COPY_WORD * gas::words(len))?;
+                    let data = padded(&frame.return_data, offset, len);
+                    frame.write(memory_offset, &data);
+                }
+                // EXTCODEHASH
+                0x3f => {
+                    let address = to_address(frame.pop()?);
+                    let cost = self.access_account(&address);
+                    frame.charge(cost)?;
+                    let account = self.state.account(&address)?;
+                    frame.push(if account.is_empty() {
+                        Uint::zero()
+                    } else {
+                        Uint::from(&keccak256(&[&account.code]))
+                    })?;
+                }
+                // BLOCKHASH
+                0x40 => {
+                    frame.charge(gas::BLOCKHASH)?;
+                    let number = saturating_u64(frame.pop()?);
+                    let current = self.env.number;
+                    frame.push(if number >= current || current - number > 256 {
+                        Uint::zero()
+                    } else if number == current - 1 {
+                        Uint::from_big_endian(&self.env.parent_hash.0)
+                    } else {
+                        return Err(ExecutionError::MissingBlockHash(number).into());
+                    })?;
+                }
+                // COINBASE
+                0x41 => {
+                    frame.charge(gas::BASE)?;
+                    frame.push(to_word(&self.env.coinbase))?;
+                }
+                // TIMESTAMP, NUMBER, GASLIMIT, CHAINID
+                0x42 | 0x43 | 0x45 | 0x46 => {
+                    frame.charge(gas::BASE)?;
+                    frame.push(Uint::from(match op {
+                        0x42 => self.env.timestamp,
+                        0x43 => self.env.number,
+                        0x45 => self.env.gas_limit,
+                        _ => self.env.chain_id,
+                    }))?;
+                }
+                // PREVRANDAO
+                0x44 => {
+                    frame.charge(gas::BASE)?;
+                    frame.push(Uint::from_big_endian(&self.env.prevrandao.0))?;
+                }
+                // SELFBALANCE
+                0x47 => {
+                    frame.charge(gas::LOW)?;
+                    frame.push(self.state.balance(&message.address)?)?;
+                }
+                // BASEFEE
+                0x48 => {
+                    frame.charge(gas::BASE)?;
+                    frame.push(self.env.base_fee)?;
+                }
+                // BLOBHASH, simulated calls do not carry blobs
+                0x49 => {
+                    frame.charge(gas::VERY_LOW)?;
+                    frame.pop()?;
+                    frame.push(Uint::zero())?;
+                }
+                // BLOBBASEFEE
+                0x4a => {
+                    frame.charge(gas::BASE)?;
+                    frame.push(self.env.blob_base_fee)?;
+                }
+                // POP
+                0x50 => {
+                    frame.charge(gas::BASE)?;
+                    frame.pop()?;
+                }
+                // MLOAD
+                0x51 => {
+                    frame.charge(gas::VERY_LOW)?;
+                    let offset = frame.pop()?;
+                    let (offset, _) = frame.expand(offset, Uint::from(32))?;
+                    frame.push(Uint::from_big_endian(&frame.read(offset, 32)))?;
+                }
+                // MSTORE
+                0x52 => {
+                    frame.charge(gas::VERY_LOW)?;
+                    let offset = frame.pop()?;
+                    let value = frame.pop()?;
+                    let (offset, _) = frame.expand(offset, Uint::from(32))?;
+                    let mut buf = [0u8; 32];
+                    value.to_big_endian(&mut buf);
+                    frame.write(offset, &buf);
+                }
+                // MSTORE8
+                0x53 => {
+                    frame.charge(gas::VERY_LOW)?;
+                    let offset = frame.pop()?;
+                    let value = frame.pop()?;
+                    let (offset, _) = frame.expand(offset, Uint::one())?;
+                    frame.write(offset, &[value.byte(0)]);
+                }
+                // SLOAD
+                0x54 => {
+                    let slot = frame.pop()?;
+                    let cost = if self.state.warm_slot(&message.address, slot) {
+                        gas::COLD_SLOAD
+                    } else {
+                        gas::WARM_ACCESS
+                    };
+                    frame.charge(cost)?;
+                    frame.push(self.state.storage(&message.address, &slot)?)?;
+                }
+                // SSTORE
+                0x55 => {
+                    if message.is_static {
+                        return Err(Halt::StaticStateChange.into());
+                    }
+                    if frame.gas <= gas::SSTORE_STIPEND {
+                        return Err(Halt::OutOfGas.into());
+                    }
+                    let slot = frame.pop()?;
+                    let value = frame.pop()?;
+                    let cost = self.sstore(&message.address, slot, value)?;
+                    frame.charge(cost)?;
+                }
+                // JUMP
+                0x56 => {
+                    frame.charge(gas::MID)?;
+                    let destination = frame.pop()?;
+                    frame.jump(destination)?;
+                }
+                // JUMPI
+                0x57 => {
+                    frame.charge(gas::HIGH)?;
+                    let destination = frame.pop()?;
+                    let condition = frame.pop()?;
+                    if !condition.is_zero() {
+                        frame.jump(destination)?;
+                    }
+                }
+                // PC
+                0x58 => {
+                    frame.charge(gas::BASE)?;
+                    frame.push(Uint::from(frame.pc - 1))?;
+                }
+                // MSIZE
+                0x59 => {
+                    frame.charge(gas::BASE)?;
+                    frame.push(Uint::from(frame.memory.len()))?;
+                }
+                // GAS
+                0x5a => {
+                    frame.charge(gas::BASE)?;
+                    frame.push(Uint::from(frame.gas))?;
+                }
+                // JUMPDEST
+                0x5b => frame.charge(gas::JUMPDEST)?,
+                // TLOAD
+                0x5c => {
+                    frame.charge(gas::WARM_ACCESS)?;
+                    let slot = frame.pop()?;
+                    frame.push(self.state.transient(&message.address, slot))?;
+                }
+                // TSTORE
+                0x5d => {
+                    if message.is_static {
+                        return Err(Halt::StaticStateChange.into());
+                    }
+                    frame.charge(gas::WARM_ACCESS)?;
+                    let slot = frame.pop()?;
+                    let value = frame.pop()?;
+                    self.state.set_transient(&message.address, slot, value);
+                }
+                // MCOPY
+                0x5e => {
+                    let destination = frame.pop()?;
+                    let source = frame.pop()?;
+                    let len = frame.pop()?;
+                    let (source, len) = frame.expand(source, len)?;
+                    let (destination, _) = frame.expand(destination, Uint::from(len))?;
+                    frame.charge(gas::VERY_LOW + gas::COPY_WORD * gas::words(len))?;
+                    frame.memory.copy_within(source..source + len, destination);
+                }
+                // PUSH0
+                0x5f => {
+                    frame.charge(gas::BASE)?;
+                    frame.push(Uint::zero())?;
+                }
+                // PUSH1 - PUSH32
+                0x60..=0x7f => {
+                    frame.charge(gas::VERY_LOW)?;
+                    let len = (op - 0x5f) as usize;
+                    let data = padded(frame.code, Uint::from(frame.pc), len);
+                    frame.push(Uint::from_big_endian(&data))?;
+                    frame.pc += len;
+                }
+                // DUP1 - DUP16
+                0x80..=0x8f => {
+                    frame.charge(gas::VERY_LOW)?;
+                    let depth = (op - 0x7f) as usize;
+                    if frame.stack.len() < depth {
+                        return Err(Halt::StackUnderflow.into());
+                    }
+                    frame.push(frame.stack[frame.stack.len() - depth])?;
+                }
+                // SWAP1 - SWAP16
+                0x90..=0x9f => {
+                    frame.charge(gas::VERY_LOW)?;
+                    let depth = (op - 0x8f) as usize;
+                    let len = frame.stack.len();
+                    if len <= depth {
+                        return Err(Halt::StackUnderflow.into());
+                    }
+                    frame.stack.swap(len - 1, len - 1 - depth);
+                }
+                // LOG0 - LOG4
+                0xa0..=0xa4 => {
+                    if message.is_static {
+                        return Err(Halt::StaticStateChange.into());
+                    }
+                    let offset = frame.pop()?;
+                    let len = frame.pop()?;
+                    let count = (op - 0xa0) as usize;
+                    let mut topics = Vec::with_capacity(count);
+                    for _ in 0..count {
+                        topics.push(to_h256(frame.pop()?));
+                    }
+                    let (offset, len) = frame.expand(offset, len)?;
+                    frame.charge(
+                        gas::LOG + gas::LOG_TOPIC * count as u64 + gas::LOG_DATA * len as u64,
+                    )?;
+                    self.state.logs.push(Log {
+                        address: message.address.clone(),
+                        topics,
+                        data: frame.read(offset, len),
+                    });
+                }
+                // CREATE, CREATE2
+                0xf0 | 0xf5 => {
+                    if message.is_static {
+                        return Err(Halt::StaticStateChange.into());
+                    }
+                    let value = frame.pop()?;
+                    let offset = frame.pop()?;
+                    let len = frame.pop()?;
+                    let salt = if op == 0xf5 { Some(frame.pop()?) } else { None };
+                    let (offset, len) = frame.expand(offset, len)?;
+                    if len > gas::MAX_INIT_CODE_SIZE {
+                        return Err(Halt::InitCodeSizeLimit.into());
+                    }
+                    let words = gas::words(len);
+                    let hashing = if salt.is_some() {
+                        gas::KECCAK256_WORD * words
+                    } else {
+                        0
+                    };
+                    frame.charge(gas::CREATE + INIT_CODE_WORD_GAS * words + hashing)?;
+
+                    let gas = gas::all_but_one_64th(frame.gas);
+                    frame.charge(gas)?;
+                    let init_code = frame.read(offset, len);
+                    let (outcome, address) = self.create(
+                        &message.address,
+                        value,
+                        init_code,
+                        gas,
+                        message.depth + 1,
+                        salt,
+                    )?;
+
+                    frame.gas += outcome.gas_left;
+                    frame.return_data = match outcome.halt {
+                        None if !outcome.success => outcome.output,
+                        _ => vec![],
+                    };
+                    frame.push(address.as_ref().map(to_word).unwrap_or_default())?;
+                }
+                // CALL, CALLCODE, DELEGATECALL, STATICCALL
+                0xf1 | 0xf2 | 0xf4 | 0xfa => self.call_opcode(op, message, frame)?,
+                // RETURN, REVERT
+                0xf3 | 0xfd => {
+                    let offset = frame.pop()?;
+                    let len = frame.pop()?;
+                    let (offset, len) = frame.expand(offset, len)?;
+                    return Ok((op == 0xf3, frame.read(offset, len)));
+                }
+                // SELFDESTRUCT
+                0xff => {
+                    if message.is_static {
+                        return Err(Halt::StaticStateChange.into());
+                    }
+                    let beneficiary = to_address(frame.pop()?);
+                    let mut cost = gas::SELFDESTRUCT;
+                    if self.state.warm_account(&beneficiary) {
+                        cost += gas::COLD_ACCOUNT_ACCESS;
+                    }
+                    let balance = self.state.balance(&message.address)?;
+                    if !balance.is_zero() && self.state.account(&beneficiary)?.is_empty() {
+                        cost += gas::NEW_ACCOUNT;
+                    }
+                    frame.charge(cost)?;
+
+                    self.state
+                        .transfer(&message.address, &beneficiary, balance)?;
+                    // Only contracts created in the same transaction are removed (EIP-6780)
+                    if self.state.is_created(&message.address) {
+                        self.state.destroy(&message.address);
+                    }
+                    return Ok((true, vec![]));
+                }
+                _ => return Err(Halt::InvalidOpcode(op).into()),
+            }
+        }
+    }
+
+    /// Charges storage write as defined in EIP-2200 with EIP-2929 and EIP-3529 changes,
+    /// updates refund counter
+    fn sstore(&mut self, address: &Address, slot: Uint, value: Uint) -> Result<u64, Interrupt> {
+        let mut cost = 0;
+        if self.state.warm_slot(address, slot) {
+            cost += gas::COLD_SLOAD;
+        }
+
+        let current = self.state.storage(address, &slot)?;
+        let original = self.state.original_storage(address, &slot)?;
+        let reset_refund = (gas::SSTORE_RESET - gas::WARM_ACCESS) as i64;
+
+        if current == value {
+            cost += gas::WARM_ACCESS;
+        } else if original == current {
+            if original.is_zero() {
+                cost += gas::SSTORE_SET;
+            } else {
+                cost += gas::SSTORE_RESET;
+                if value.is_zero() {
+                    self.state.refund += gas::SSTORE_CLEARS_REFUND;
+                }
+            }
+        } else {
+            cost += gas::WARM_ACCESS;
+            if !original.is_zero() {
+                if current.is_zero() {
+                    self.state.refund -= gas::SSTORE_CLEARS_REFUND;
+                } else if value.is_zero() {
+                    self.state.refund += gas::SSTORE_CLEARS_REFUND;
+                }
+            }
+            if original == value {
+                self.state.refund += if original.is_zero() {
+                    (gas::SSTORE_SET - gas::WARM_ACCESS) as i64
+                } else {
+                    reset_refund
+                };
+            }
+        }
+
+        self.state.set_storage(address, slot, value)?;
+
+        Ok(cost)
+    }
+
+    fn call_opcode(
+        &mut self,
+        op: u8,
+        message: &Message,
+        frame: &mut Frame,
+    ) -> Result<(), Interrupt> {
+        let requested = saturating_u64(frame.pop()?);
+        let target = to_address(frame.pop()?);
+        let value = if op == 0xf1 || op == 0xf2 {
+            frame.pop()?
+        } else {
+            Uint::zero()
+        };
+        let input_offset = frame.pop()?;
+        let input_len = frame.pop()?;
+        let output_offset = frame.pop()?;
+        let output_len = frame.pop()?;
+
+        if op == 0xf1 && message.is_static && !value.is_zero() {
+            return Err(Halt::StaticStateChange.into());
+        }
+
+        let (input_offset, input_len) = frame.expand(input_offset, input_len)?;
+        let (output_offset, output_len) = frame.expand(output_offset, output_len)?;
+
+        let mut cost = self.access_account(&target);
+        if !value.is_zero() {
+            cost += gas::CALL_VALUE;
+            if op == 0xf1 && self.state.account(&target)?.is_empty() {
+                cost += gas::NEW_ACCOUNT;
+            }
+        }
+        frame.charge(cost)?;
+
+        let mut gas = requested.min(gas::all_but_one_64th(frame.gas));
+        frame.charge(gas)?;
+        if !value.is_zero() {
+            gas += gas::CALL_STIPEND;
+        }
+
+        let input = frame.read(input_offset, input_len);
+        let call = match op {
+            // CALL
+            0xf1 => Message {
+                caller: message.address.clone(),
+                address: target.clone(),
+                code_address: target,
+                value,
+                input,
+                gas,
+                is_static: message.is_static,
+                depth: message.depth + 1,
+                transfer: true,
+            },
+            // CALLCODE, value is sent to itself, only balance is checked
+            0xf2 => Message {
+                caller: message.address.clone(),
+                address: message.address.clone(),
+                code_address: target,
+                value,
+                input,
+                gas,
+                is_static: message.is_static,
+                depth: message.depth + 1,
+                transfer: true,
+            },
+            // DELEGATECALL
+            0xf4 => Message {
+                caller: message.caller.clone(),
+                address: message.address.clone(),
+                code_address: target,
+                value: message.value,
+                input,
+                gas,
+                is_static: message.is_static,
+                depth: message.depth + 1,
+                transfer: false,
+            },
+            // STATICCALL
+            _ => Message {
+                caller: message.address.clone(),
+                address: target.clone(),
+                code_address: target,
+                value,
+                input,
+                gas,
+                is_static: true,
+                depth: message.depth + 1,
+                transfer: false,
+            },
+        };
+
+        let outcome = self.call(call)?;
+
+        frame.gas += outcome.gas_left;
+        let len = output_len.min(outcome.output.len());
+        frame.write(output_offset, &outcome.output[..len]);
+        frame.return_data = outcome.output;
+        frame.push(bool_word(outcome.success))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use primitive_types::U256 as Uint;
+
+    use super::{negate, sar, sdiv, signextend, slt, smod};
+
+    #[test]
+    fn signed_arithmetic() {
+        let minus = |x: u64| negate(Uint::from(x));
+
+        assert_eq!(sdiv(minus(6), Uint::from(2)), minus(3));
+        assert_eq!(sdiv(minus(6), minus(2)), Uint::from(3));
+        assert_eq!(sdiv(Uint::one() << 255, Uint::MAX), Uint::one() << 255);
+        assert_eq!(smod(minus(7), Uint::from(3)), minus(1));
+        assert_eq!(smod(Uint::from(7), minus(3)), Uint::one());
+        assert_eq!(sar(Uint::one(), minus(8)), minus(4));
+        assert_eq!(sar(Uint::from(300), minus(8)), Uint::MAX);
+        assert_eq!(signextend(Uint::zero(), Uint::from(0xff)), Uint::MAX);
+        assert_eq!(
+            signextend(Uint::zero(), Uint::from(0x17f)),
+            Uint::from(0x7f)
+        );
+        assert!(slt(minus(1), Uint::zero()));
+        assert!(!slt(Uint::one(), minus(1)));
+    }
+}
@@ -0,0 +1,454 @@
+//! Interpreter of EVM bytecode up to Cancun, used to simulate calls against state proven
+//! by eth_getProof
+
+pub mod gas;
+pub mod interpreter;
+pub mod precompiles;
+pub mod state;
+
+use primitive_types::U256 as Uint;
+
+use crate::types::{
+    address::Address,
+    errors::{ExecutionError, FeeError},
+    execution::{CallRequest, ExecutionResult},
+    fee::{self, BlobSchedule},
+    gas::{INIT_CODE_WORD_GAS, TX_CREATE_GAS, TX_DATA_NON_ZERO_GAS, TX_DATA_ZERO_GAS, TX_GAS},
+    header::Header,
+    num::{H256, U256},
+    revert::Revert,
+};
+
+use self::{
+    interpreter::{Halt, Interpreter, Message},
+    state::{Account, State},
+};
+
+/// Block and transaction context of the execution
+pub struct Env {
+    pub chain_id: u64,
+    pub origin: Address,
+    pub gas_price: Uint,
+    pub coinbase: Address,
+    pub number: u64,
+    pub timestamp: u64,
+    pub gas_limit: u64,
+    pub base_fee: Uint,
+    pub prevrandao: H256,
+    pub blob_base_fee: Uint,
+    /// Hash of the parent block, the only block hash available to BLOCKHASH
+    pub parent_hash: H256,
+}
+
+impl Env {
+    /// Creates context of a call executed on top of the block, blob schedule of the chain is
+    /// required only when the block has excess blob gas
+    pub fn new(
+        header: &Header,
+        chain_id: u64,
+        request: &CallRequest,
+        schedule: Option<&BlobSchedule>,
+    ) -> Result<Self, FeeError> {
+        let blob_base_fee = match (header.excess_blob_gas.unwrap_or_default(), schedule) {
+            (0, _) => U256::from(fee::MIN_BLOB_BASE_FEE),
+            (excess_blob_gas, Some(schedule)) => fee::blob_base_fee(excess_blob_gas, schedule)?,
+            (_, None) => return Err(FeeError::UnknownBlobSchedule),
+        };
+
+        Ok(Self {
+            chain_id,
+            origin: request.from.clone().unwrap_or(Address([0u8; 20])),
+            gas_price: request
+                .gas_price
+                .as_ref()
+                .map(Uint::from)
+                .unwrap_or_default(),
+            coinbase: header.beneficiary.clone(),
+            number: header.number,
+            timestamp: header.timestamp,
+            gas_limit: header.gas_limit,
+            base_fee: header
+                .base_fee_per_gas
+                .as_ref()
+                .map(Uint::from)
+                .unwrap_or_default(),
+            prevrandao: header.mix_hash.clone(),
+            blob_base_fee: Uint::from(&blob_base_fee),
+            parent_hash: header.parent_hash.clone(),
+        })
+    }
+}
+
+fn intrinsic_gas(request: &CallRequest) -> u64 {
+    let mut gas = if request.to.is_some() {
+        TX_GAS
+    } else {
+        TX_CREATE_GAS + INIT_CODE_WORD_GAS * gas::words(request.data.len())
+    };
+
+    for byte in &request.data {
+        gas += match byte {
+            0 => TX_DATA_ZERO_GAS,
+            _ => TX_DATA_NON_ZERO_GAS,
+        };
+    }
+
+    gas
+}
+
+/// Executes a call or contract creation, gas fees are not charged. Fails if state
+/// required by execution is missing
+pub fn execute(
+    env: &Env,
+    mut state: State,
+    request: &CallRequest,
+) -> Result<ExecutionResult, ExecutionError> {
+    let origin = env.origin.clone();
+    let value = Uint::from(&request.value);
+    // like eth_call of geth, gas is capped by the block gas limit
+    let gas_limit = request
+        .gas_limit
+        .unwrap_or(env.gas_limit)
+        .min(env.gas_limit);
+
+    let intrinsic_gas = intrinsic_gas(request);
+    if gas_limit < intrinsic_gas {
+        return Err(ExecutionError::IntrinsicGas);
+    }
+
+    // Sender does not have to be proven for calls without value, as long as execution
+    // does not read it. Address of created contract depends on nonce of the sender
+    let proven = state.get(&origin).is_some();
+    if !proven {
+        if request.to.is_none() || !value.is_zero() {
+            return Err(ExecutionError::MissingAccount(origin));
+        }
+        state.insert(
+            origin.clone(),
+            Account {
+                unproven: true,
+                ..Default::default()
+            },
+        );
+    }
+    if !value.is_zero() && state.balance(&origin)? < value {
+        return Err(ExecutionError::InsufficientBalance);
+    }
+
+    state.warm_account(&origin);
+    state.warm_account(&env.coinbase);
+    if let Some(to) = &request.to {
+        state.warm_account(to);
+    }
+    for i in 1..=precompiles::LAST {
+        let mut address = Address([0u8; 20]);
+        address.0[19] = i;
+        state.warm_account(&address);
+    }
+
+    let gas = gas_limit - intrinsic_gas;
+    let mut interpreter = Interpreter::new(env, state);
+    let (outcome, created_address) = match &request.to {
+        Some(to) => {
+            if proven {
+                let sender = interpreter.state.account_mut(&origin)?;
+                sender.nonce = sender.nonce.saturating_add(1);
+            }
+
+            let message = Message {
+                caller: origin.clone(),
+                address: to.clone(),
+                code_address: to.clone(),
+                value,
+                input: request.data.clone(),
+                gas,
+                is_static: false,
+                depth: 0,
+                transfer: true,
+            };
+            (interpreter.call(message)?, None)
+        }
+        None if request.data.len() > gas::MAX_INIT_CODE_SIZE => (
+            interpreter::Outcome {
+                success: false,
+                gas_left: 0,
+                output: vec![],
+                halt: Some(Halt::InitCodeSizeLimit),
+            },
+            None,
+        ),
+        None => interpreter.create(&origin, value, request.data.clone(), gas, 0, None)?,
+    };
+
+    let state = interpreter.state;
+    let gas_used = gas_limit - outcome.gas_left;
+    let refund = (state.refund.max(0) as u64).min(gas_used / gas::MAX_REFUND_QUOTIENT);
+
+    Ok(ExecutionResult {
+        success: outcome.success,
+        revert_reason: if outcome.success || outcome.halt.is_some() {
+            None
+        } else {
+            match Revert::decode(&outcome.output, &[]) {
+                Ok(Revert::Error(x)) => Some(x),
+                _ => None,
+            }
+        },
+        halt_reason: outcome.halt.map(|x| format!("{x:?}")),
+        logs: if outcome.success { state.logs } else { vec![] },
+        output: outcome.output,
+        gas_used: gas_used - refund,
+        created_address,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use std::{collections::BTreeMap, error::Error, rc::Rc};
+
+    use primitive_types::U256 as Uint;
+
+    use super::{
+        execute,
+        state::{Account, State},
+        Env,
+    };
+    use crate::types::{
+        address::Address,
+        errors::ExecutionError,
+        execution::{CallRequest, ExecutionResult},
+        num::{H256, U256},
+    };
+
+    const SENDER: Address = Address([0xaa; 20]);
+    const CONTRACT: Address = Address([0xcc; 20]);
+    const OTHER: Address = Address([0xdd; 20]);
+
+    /// PUSH1 2 PUSH1 3 ADD PUSH1 0 MSTORE PUSH1 32 PUSH1 0 RETURN
+    const ADD_AND_RETURN: &str = "600260030160005260206000f3";
+
+    fn env() -> Env {
+        Env {
+            chain_id: 1,
+            origin: SENDER,
+            gas_price: Uint::zero(),
+            coinbase: Address([0u8; 20]),
+            number: 100,
+            timestamp: 1_700_000_000,
+            gas_limit: 30_000_000,
+            base_fee: Uint::from(7),
+            prevrandao: H256::zero(),
+            blob_base_fee: Uint::one(),
+            parent_hash: H256([0x11; 32]),
+        }
+    }
+
+    fn contract(code: &str, storage: &[(u64, u64)]) -> Account {
+        let storage: BTreeMap<Uint, Uint> = storage
+            .iter()
+            .map(|(k, v)| (Uint::from(*k), Uint::from(*v)))
+            .collect();
+
+        Account {
+            nonce: 1,
+            code: Rc::new(hex::decode(code).unwrap()),
+            original: Rc::new(storage.clone()),
+            storage,
+            ..Default::default()
+        }
+    }
+
+    fn state(code: &str, storage: &[(u64, u64)]) -> State {
+        let mut state = State::default();
+        state.insert(CONTRACT, contract(code, storage));
+        state
+    }
+
+    fn request(to: Option<Address>, data: Vec<u8>, gas_limit: Option<u64>) -> CallRequest {
+        CallRequest {
+            from: Some(SENDER),
+            to,
+            value: U256::zero(),
+            data,
+            gas_limit,
+            gas_price: None,
+        }
+    }
+
+    fn call(
+        state: State,
+        data: Vec<u8>,
+        gas_limit: Option<u64>,
+    ) -> Result<ExecutionResult, ExecutionError> {
+        execute(&env(), state, &request(Some(CONTRACT), data, gas_limit))
+    }
+
+    #[test]
+    fn add_and_return() -> Result<(), Box<dyn Error>> {
+        let result = call(state(ADD_AND_RETURN, &[]), vec![], None)?;
+
+        assert!(result.success);
+        assert_eq!(result.gas_used, 21_024);
+        assert_eq!(Uint::from_big_endian(&result.output), Uint::from(5));
+
+        Ok(())
+    }
+
+    #[test]
+    fn gas_limit_capped() -> Result<(), Box<dyn Error>> {
+        // JUMPDEST PUSH1 0 JUMP, loops until gas runs out
+        let result = call(state("5b600056", &[]), vec![], Some(u64::MAX))?;
+
+        assert!(!result.success);
+        assert_eq!(result.gas_used, env().gas_limit);
+
+        Ok(())
+    }
+
+    #[test]
+    fn storage() -> Result<(), Box<dyn Error>> {
+        // PUSH1 1 PUSH1 0 SSTORE STOP
+        let result = call(state("600160005500", &[(0, 0)]), vec![], None)?;
+        assert!(result.success);
+        assert_eq!(result.gas_used, 21_000 + 6 + 2_100 + 20_000);
+
+        // PUSH1 0 PUSH1 0 SSTORE STOP, clearing a slot is refunded
+        let result = call(state("600060005500", &[(0, 1)]), vec![], None)?;
+        assert!(result.success);
+        assert_eq!(result.gas_used, 21_000 + 6 + 2_100 + 2_900 - 4_800);
+
+        // PUSH1 0 SLOAD STOP, slot was not proven
+        let result = call(state("60005400", &[]), vec![], None);
+        assert!(matches!(result, Err(ExecutionError::MissingStorage(_, _))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn transient_storage() -> Result<(), Box<dyn Error>> {
+        // TSTORE(1, 7) TLOAD(1) and return it
+        let result = call(state("600760015d60015c60005260206000f3", &[]), vec![], None)?;
+
+        assert!(result.success);
+        assert_eq!(Uint::from_big_endian(&result.output), Uint::from(7));
+
+        Ok(())
+    }
+
+    #[test]
+    fn revert() -> Result<(), Box<dyn Error>> {
+        // Reverts with calldata
+        let data = hex::decode("08c379a0000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000000046e6f706500000000000000000000000000000000000000000000000000000000")?;
+        let result = call(state("365f5f37365ffd", &[]), data.clone(), None)?;
+
+        assert!(!result.success);
+        assert_eq!(result.output, data);
+        assert_eq!(result.revert_reason, Some("nope".to_string()));
+        assert_eq!(result.halt_reason, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn logs() -> Result<(), Box<dyn Error>> {
+        // LOG1 with topic 0xaa and empty data
+        let result = call(state("60aa60006000a100", &[]), vec![], None)?;
+
+        assert!(result.success);
+        assert_eq!(result.gas_used, 21_000 + 9 + 375 * 2);
+        assert_eq!(result.logs.len(), 1);
+        assert_eq!(result.logs[0].address, CONTRACT);
+        assert_eq!(result.logs[0].topics[0].0[31], 0xaa);
+
+        Ok(())
+    }
+
+    #[test]
+    fn static_call() -> Result<(), Box<dyn Error>> {
+        // STATICCALL(GAS, OTHER, 0, 0, 0, 32) POP RETURN(0, 32)
+        let code = format!("602060006000600073{}5afa5060206000f3", "dd".repeat(20));
+        let mut state = state(&code, &[]);
+        state.insert(OTHER, contract(ADD_AND_RETURN, &[]));
+
+        let result = call(state, vec![], None)?;
+        assert!(result.success);
+        assert_eq!(Uint::from_big_endian(&result.output), Uint::from(5));
+
+        let result = call(self::state(&code, &[]), vec![], None);
+        assert!(matches!(result, Err(ExecutionError::MissingAccount(x)) if x == OTHER));
+
+        Ok(())
+    }
+
+    #[test]
+    fn create() -> Result<(), Box<dyn Error>> {
+        // MSTORE8(0, 0) RETURN(0, 1), deploys code consisting of STOP
+        let data = hex::decode("600060005360016000f3")?;
+        let mut state = State::default();
+        state.insert(SENDER, Account::default());
+        let result = execute(&env(), state, &request(None, data, None))?;
+
+        assert!(result.success);
+        assert_eq!(result.output, vec![0]);
+        assert_eq!(
+            result.created_address,
+            Some(Address::from_create(&SENDER, &U256::zero()))
+        );
+        assert_eq!(result.gas_used, 53_000 + 2 + 7 * 16 + 3 * 4 + 18 + 200);
+
+        Ok(())
+    }
+
+    #[test]
+    fn unproven_sender() -> Result<(), Box<dyn Error>> {
+        // address of created contract depends on nonce of the sender
+        let data = hex::decode("600060005360016000f3")?;
+        let result = execute(&env(), State::default(), &request(None, data, None));
+        assert!(matches!(result, Err(ExecutionError::MissingAccount(x)) if x == SENDER));
+
+        // call without value does not need the sender
+        let result = call(state(ADD_AND_RETURN, &[]), vec![], None)?;
+        assert!(result.success);
+
+        // ORIGIN BALANCE
+        let result = call(state("323100", &[]), vec![], None);
+        assert!(matches!(result, Err(ExecutionError::MissingAccount(x)) if x == SENDER));
+
+        let mut request = request(Some(CONTRACT), vec![], None);
+        request.value = U256::from(1u64);
+        let result = execute(&env(), state(ADD_AND_RETURN, &[]), &request);
+        assert!(matches!(result, Err(ExecutionError::MissingAccount(x)) if x == SENDER));
+
+        Ok(())
+    }
+
+    #[test]
+    fn exceptional_halt() -> Result<(), Box<dyn Error>> {
+        let result = call(state(ADD_AND_RETURN, &[]), vec![], Some(21_010))?;
+        assert!(!result.success);
+        assert_eq!(result.gas_used, 21_010);
+        assert_eq!(result.halt_reason, Some("OutOfGas".to_string()));
+
+        // PUSH1 3 JUMP
+        let result = call(state("600356", &[]), vec![], None)?;
+        assert_eq!(result.halt_reason, Some("InvalidJump".to_string()));
+        assert_eq!(result.gas_used, 30_000_000);
+
+        let result = call(state(ADD_AND_RETURN, &[]), vec![], Some(20_000));
+        assert!(matches!(result, Err(ExecutionError::IntrinsicGas)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn block_hash() -> Result<(), Box<dyn Error>> {
+        // BLOCKHASH(99) returned
+        let result = call(state("60634060005260206000f3", &[]), vec![], None)?;
+        assert_eq!(result.output, vec![0x11; 32]);
+
+        let result = call(state("60624060005260206000f3", &[]), vec![], None);
+        assert!(matches!(result, Err(ExecutionError::MissingBlockHash(98))));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,245 @@
+use num_bigint::BigUint;
+use primitive_types::U256 as Uint;
+use sha2::{Digest, Sha256};
+
+use super::gas::words;
+use crate::{types::address::Address, types::errors::ExecutionError, utils::recover_from_hash};
+
+pub const ECRECOVER: u8 = 0x01;
+pub const SHA256: u8 = 0x02;
+pub const IDENTITY: u8 = 0x04;
+pub const MODEXP: u8 = 0x05;
+/// Last precompile address in Cancun, point evaluation (EIP-4844)
+pub const LAST: u8 = 0x0a;
+
+/// Returns precompile number if address belongs to a precompile
+pub fn precompile(address: &Address) -> Option<u8> {
+    let (prefix, last) = address.0.split_at(19);
+    if prefix.iter().all(|x| *x == 0) && (1..=LAST).contains(&last[0]) {
+        return Some(last[0]);
+    }
+
+    None
+}
+
+/// Runs a precompile, returns gas used and output, None if gas is not sufficient
+pub fn execute(
+    address: &Address,
+    input: &[u8],
+    gas: u64,
+) -> Result<Option<(u64, Vec<u8>)>, ExecutionError> {
+    let cost = match precompile(address) {
+        Some(ECRECOVER) => 3_000,
+        Some(SHA256) => 60 + 12 * words(input.len()),
+        Some(IDENTITY) => 15 + 3 * words(input.len()),
+        Some(MODEXP) => match modexp_lengths(input) {
+            // lengths that do not fit in memory can not be paid for
+            Some(_) => modexp_gas(input),
+            None => return Ok(None),
+        },
+        _ => return Err(ExecutionError::UnsupportedPrecompile(address.clone())),
+    };
+
+    if cost > gas {
+        return Ok(None);
+    }
+
+    let output = match precompile(address) {
+        Some(ECRECOVER) => ecrecover(input),
+        Some(SHA256) => Sha256::digest(input).to_vec(),
+        Some(MODEXP) => modexp(input),
+        _ => input.to_vec(),
+    };
+
+    Ok(Some((cost, output)))
+}
+
+/// Reads input padded with zeros to required length
+fn read(input: &[u8], offset: usize, len: usize) -> Vec<u8> {
+    let mut buf = vec![0u8; len];
+    if offset < input.len() {
+        let end = input.len().min(offset.saturating_add(len));
+        buf[..end - offset].copy_from_slice(&input[offset..end]);
+    }
+
+    buf
+}
+
+fn ecrecover(input: &[u8]) -> Vec<u8> {
+    let input = read(input, 0, 128);
+
+    let v = Uint::from_big_endian(&input[32..64]);
+    if v != Uint::from(27) && v != Uint::from(28) {
+        return vec![];
+    }
+
+    let mut hash = [0u8; 32];
+    hash.copy_from_slice(&input[..32]);
+
+    match recover_from_hash(&input[64..96], &input[96..128], v.as_u64(), &hash) {
+        Ok(key) => {
+            let mut output = vec![0u8; 12];
+            output.extend_from_slice(&Address::from(key).0);
+            output
+        }
+        Err(_) => vec![],
+    }
+}
+
+/// Reads length of modexp parameter, saturated at u64::MAX
+fn modexp_len(input: &[u8], offset: usize) -> u64 {
+    let value = Uint::from_big_endian(&read(input, offset, 32));
+    if value > Uint::from(u64::MAX) {
+        return u64::MAX;
+    }
+
+    value.as_u64()
+}
+
+/// Lengths of base, exponent and modulus, None if they do not fit in usize
+fn modexp_lengths(input: &[u8]) -> Option<(usize, usize, usize)> {
+    Some((
+        usize::try_from(modexp_len(input, 0)).ok()?,
+        usize::try_from(modexp_len(input, 32)).ok()?,
+        usize::try_from(modexp_len(input, 64)).ok()?,
+    ))
+}
+
+/// Gas of modexp as defined in EIP-2565
+fn modexp_gas(input: &[u8]) -> u64 {
+    let base_len = modexp_len(input, 0);
+    let exp_len = modexp_len(input, 32);
+    let mod_len = modexp_len(input, 64);
+
+    let words = u128::from(base_len.max(mod_len).div_ceil(8));
+    let complexity = words.saturating_mul(words);
+
+    // Only first 32 bytes of exponent are used to calculate number of iterations
+    let head_len = exp_len.min(32) as usize;
+    let head_offset = 96usize.saturating_add(base_len.min(usize::MAX as u64) as usize);
+    let head = Uint::from_big_endian(&read(input, head_offset, head_len));
+    let head_bits = head.bits().saturating_sub(1) as u128;
+
+    let iterations = if exp_len <= 32 {
+        head_bits
+    } else {
+        (8 * u128::from(exp_len - 32)).saturating_add(head_bits)
+    }
+    .max(1);
+
+    let gas = complexity.saturating_mul(iterations) / 3;
+
+    gas.clamp(200, u128::from(u64::MAX)) as u64
+}
+
+/// Modular exponentiation, gas for the lengths has been paid and execution gas is capped
+/// by the block gas limit, so they fit in memory
+fn modexp(input: &[u8]) -> Vec<u8> {
+    let (base_len, exp_len, mod_len) = match modexp_lengths(input) {
+        Some(x) => x,
+        None => return vec![],
+    };
+
+    if mod_len == 0 {
+        return vec![];
+    }
+
+    let exp_offset = 96usize.saturating_add(base_len);
+    let mod_offset = exp_offset.saturating_add(exp_len);
+
+    let base = BigUint::from_bytes_be(&read(input, 96, base_len));
+    let exp = BigUint::from_bytes_be(&read(input, exp_offset, exp_len));
+    let modulus = BigUint::from_bytes_be(&read(input, mod_offset, mod_len));
+
+    let mut output = vec![0u8; mod_len];
+    if modulus.bits() == 0 {
+        return output;
+    }
+
+    let result = base.modpow(&exp, &modulus).to_bytes_be();
+    output[mod_len - result.len()..].copy_from_slice(&result);
+
+    output
+}
+
+#[cfg(test)]
+mod test {
+    use std::error::Error;
+
+    use super::{execute, modexp_gas};
+    use crate::types::address::Address;
+
+    fn address(x: u8) -> Address {
+        let mut address = Address([0u8; 20]);
+        address.0[19] = x;
+        address
+    }
+
+    #[test]
+    fn ecrecover() -> Result<(), Box<dyn Error>> {
+        let input = hex::decode("456e9aea5e197a1f1af7a3e85a3212fa4049a3ba34c2289b4c860fc0b0c64ef3000000000000000000000000000000000000000000000000000000000000001c9242685bf161793cc25603c231bc2f568eb630ea16aa137d2664ac80388256084f8ae3bd7535248d0bd448298cc2e2071e56992d0774dc340c368ae950852ada")?;
+
+        let (gas, output) = execute(&address(1), &input, 3_000)?.ok_or("out of gas")?;
+
+        assert_eq!(gas, 3_000);
+        assert_eq!(
+            hex::encode(output),
+            "0000000000000000000000007156526fbd7a3c72969b54f64e42c10fbb768c8a"
+        );
+
+        let mut invalid = input;
+        invalid[63] = 29;
+        let (_, output) = execute(&address(1), &invalid, 3_000)?.ok_or("out of gas")?;
+        assert!(output.is_empty());
+
+        assert!(execute(&address(1), &[], 2_999)?.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn sha256_and_identity() -> Result<(), Box<dyn Error>> {
+        let (gas, output) = execute(&address(2), b"abc", 100)?.ok_or("out of gas")?;
+
+        assert_eq!(gas, 72);
+        assert_eq!(
+            hex::encode(output),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+
+        let (gas, output) = execute(&address(4), &[1u8; 33], 100)?.ok_or("out of gas")?;
+        assert_eq!(gas, 21);
+        assert_eq!(output, vec![1u8; 33]);
+
+        assert!(execute(&address(3), &[], 1_000).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn modexp() -> Result<(), Box<dyn Error>> {
+        // 3 ** (2 ** 256 - 2 ** 32 - 978) % (2 ** 256 - 2 ** 32 - 977), Fermat's little theorem
+        let input = hex::decode("00000000000000000000000000000000000000000000000000000000000000010000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000002003fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2efffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f")?;
+
+        assert_eq!(modexp_gas(&input), 1_360);
+
+        let (_, output) = execute(&address(5), &input, 10_000)?.ok_or("out of gas")?;
+        assert_eq!(
+            hex::encode(output),
+            "0000000000000000000000000000000000000000000000000000000000000001"
+        );
+
+        let input = hex::decode("000000000000000000000000000000000000000000000000000000000000000100000000000000000000000000000000000000000000000000000000000000010000000000000000000000000000000000000000000000000000000000000001020305")?;
+        let (gas, output) = execute(&address(5), &input, 10_000)?.ok_or("out of gas")?;
+        assert_eq!(gas, 200);
+        assert_eq!(output, vec![3]);
+
+        // Huge exponent length makes gas exceed any limit
+        let mut input = vec![0u8; 96];
+        input[32..64].copy_from_slice(&[0xffu8; 32]);
+        input[95] = 1;
+        assert!(execute(&address(5), &input, u64::MAX - 1)?.is_none());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,239 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    rc::Rc,
+};
+
+use primitive_types::U256 as Uint;
+
+use crate::types::{
+    address::Address,
+    errors::{ExecutionError, ProofError},
+    execution::ProvenAccount,
+    log::Log,
+    num::H256,
+    proof::EMPTY_TRIE_ROOT,
+};
+
+#[derive(Clone, Default)]
+pub struct Account {
+    pub balance: Uint,
+    pub nonce: u64,
+    pub code: Rc<Vec<u8>>,
+    pub storage: BTreeMap<Uint, Uint>,
+    /// Values at the beginning of execution, required by SSTORE gas calculation
+    pub original: Rc<BTreeMap<Uint, Uint>>,
+    /// All storage slots are known, slots that are not set are zero
+    pub complete: bool,
+    /// Sender that was not proven, none of its fields can be read
+    pub unproven: bool,
+}
+
+impl Account {
+    /// Account without code, nonce and balance (EIP-161)
+    pub fn is_empty(&self) -> bool {
+        self.nonce == 0 && self.balance.is_zero() && self.code.is_empty()
+    }
+}
+
+/// Accounts known to the execution together with changes made by it. State is cloned
+/// when entering a call, so changes of failed calls can be dropped
+#[derive(Clone, Default)]
+pub struct State {
+    accounts: BTreeMap<Address, Account>,
+    transient: BTreeMap<(Address, Uint), Uint>,
+    warm_accounts: BTreeSet<Address>,
+    warm_slots: BTreeSet<(Address, Uint)>,
+    /// Accounts created during execution, they can be destroyed by SELFDESTRUCT (EIP-6780)
+    created: BTreeSet<Address>,
+    pub logs: Vec<Log>,
+    pub refund: i64,
+}
+
+fn slot_key(slot: &Uint) -> H256 {
+    let mut buf = [0u8; 32];
+    slot.to_big_endian(&mut buf);
+
+    H256(buf)
+}
+
+impl State {
+    /// Builds state from accounts verified against state root of a block
+    pub fn from_proofs(accounts: &[ProvenAccount], state_root: &H256) -> Result<Self, ProofError> {
+        let mut state = Self::default();
+        for account in accounts {
+            account.proof.verify(state_root)?;
+            account.proof.verify_code(&account.code)?;
+
+            let storage: BTreeMap<Uint, Uint> = account
+                .proof
+                .storage_proof
+                .iter()
+                .map(|x| (Uint::from_big_endian(&x.key.0), Uint::from(&x.value)))
+                .collect();
+
+            state.insert(
+                account.proof.address.clone(),
+                Account {
+                    balance: Uint::from(&account.proof.balance),
+                    nonce: account
+                        .proof
+                        .nonce
+                        .as_u64()
+                        .ok_or(ProofError::AccountMismatch)?,
+                    code: Rc::new(account.code.clone()),
+                    original: Rc::new(storage.clone()),
+                    storage,
+                    complete: account.proof.storage_hash.0 == EMPTY_TRIE_ROOT,
+                    unproven: false,
+                },
+            );
+        }
+
+        Ok(state)
+    }
+
+    pub fn insert(&mut self, address: Address, account: Account) {
+        self.accounts.insert(address, account);
+    }
+
+    pub fn get(&self, address: &Address) -> Option<&Account> {
+        self.accounts.get(address)
+    }
+
+    pub fn account(&self, address: &Address) -> Result<&Account, ExecutionError> {
+        self.accounts
+            .get(address)
+            .filter(|x| !x.unproven)
+            .ok_or_else(|| ExecutionError::MissingAccount(address.clone()))
+    }
+
+    pub fn account_mut(&mut self, address: &Address) -> Result<&mut Account, ExecutionError> {
+        self.accounts
+            .get_mut(address)
+            .filter(|x| !x.unproven)
+            .ok_or_else(|| ExecutionError::MissingAccount(address.clone()))
+    }
+
+    pub fn balance(&self, address: &Address) -> Result<Uint, ExecutionError> {
+        Ok(self.account(address)?.balance)
+    }
+
+    pub fn code(&self, address: &Address) -> Result<Rc<Vec<u8>>, ExecutionError> {
+        Ok(self.account(address)?.code.clone())
+    }
+
+    pub fn storage(&self, address: &Address, slot: &Uint) -> Result<Uint, ExecutionError> {
+        let account = self.account(address)?;
+        match account.storage.get(slot) {
+            Some(x) => Ok(*x),
+            None if account.complete => Ok(Uint::zero()),
+            None => Err(ExecutionError::MissingStorage(
+                address.clone(),
+                slot_key(slot),
+            )),
+        }
+    }
+
+    pub fn original_storage(&self, address: &Address, slot: &Uint) -> Result<Uint, ExecutionError> {
+        let account = self.account(address)?;
+        match account.original.get(slot) {
+            Some(x) => Ok(*x),
+            None if account.complete => Ok(Uint::zero()),
+            None => Err(ExecutionError::MissingStorage(
+                address.clone(),
+                slot_key(slot),
+            )),
+        }
+    }
+
+    pub fn set_storage(
+        &mut self,
+        address: &Address,
+        slot: Uint,
+        value: Uint,
+    ) -> Result<(), ExecutionError> {
+        self.account_mut(address)?.storage.insert(slot, value);
+
+        Ok(())
+    }
+
+    pub fn transient(&self, address: &Address, slot: Uint) -> Uint {
+        self.transient
+            .get(&(address.clone(), slot))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub fn set_transient(&mut self, address: &Address, slot: Uint, value: Uint) {
+        self.transient.insert((address.clone(), slot), value);
+    }
+
+    /// Marks account as accessed, returns true if it was cold
+    pub fn warm_account(&mut self, address: &Address) -> bool {
+        self.warm_accounts.insert(address.clone())
+    }
+
+    /// Marks storage slot as accessed, returns true if it was cold
+    pub fn warm_slot(&mut self, address: &Address, slot: Uint) -> bool {
+        self.warm_slots.insert((address.clone(), slot))
+    }
+
+    /// Moves value between accounts, returns false if balance of the sender is too low.
+    /// Accounts do not have to be known if value is zero
+    pub fn transfer(
+        &mut self,
+        from: &Address,
+        to: &Address,
+        value: Uint,
+    ) -> Result<bool, ExecutionError> {
+        if value.is_zero() {
+            return Ok(true);
+        }
+
+        let sender = self.account_mut(from)?;
+        if sender.balance < value {
+            return Ok(false);
+        }
+        sender.balance -= value;
+
+        let recipient = self.account_mut(to)?;
+        recipient.balance = recipient.balance.overflowing_add(value).0;
+
+        Ok(true)
+    }
+
+    /// Creates account of a new contract, balance sent to the address before is kept
+    pub fn create_account(&mut self, address: &Address) {
+        let balance = self
+            .accounts
+            .get(address)
+            .map(|x| x.balance)
+            .unwrap_or_default();
+
+        self.accounts.insert(
+            address.clone(),
+            Account {
+                balance,
+                nonce: 1,
+                complete: true,
+                ..Default::default()
+            },
+        );
+        self.created.insert(address.clone());
+    }
+
+    pub fn is_created(&self, address: &Address) -> bool {
+        self.created.contains(address)
+    }
+
+    /// Removes account created in the same execution (EIP-6780)
+    pub fn destroy(&mut self, address: &Address) {
+        self.accounts.insert(
+            address.clone(),
+            Account {
+                complete: true,
+                ..Default::default()
+            },
+        );
+    }
+}
@@ -0,0 +1,62 @@
+use candid::candid_method;
+use ic_cdk_macros::query;
+
+use crate::types::{
+    fee::{self, BlobSchedule, FeeSuggestion},
+    gas::GasCost,
+    num::U256,
+    rpc::FeeHistory,
+    transaction::Transaction,
+};
+
+/// Calculates intrinsic gas, effective gas price and maximum upfront cost of a transaction.
+/// Base fee of the block is required to calculate effective gas price of EIP1559 transactions
+#[query]
+#[candid_method(query)]
+fn transaction_cost(data: Transaction, base_fee: Option<U256>) -> Result<GasCost, String> {
+    GasCost::calculate(&data, base_fee.as_ref())
+        .map_err(|x| format!("Error while calculating transaction cost {x}"))
+}
+
+/// Calculates base fee of the next block from parent gas used, gas limit and base fee
+#[query]
+#[candid_method(query)]
+fn next_base_fee(gas_used: u64, gas_limit: u64, base_fee: U256) -> Result<U256, String> {
+    fee::next_base_fee(gas_used, gas_limit, &base_fee)
+        .map_err(|x| format!("Error while calculating base fee {x}"))
+}
+
+/// Calculates blob base fee of the next block from parent excess blob gas, blob gas used and
+/// base fee. Blob parameters of the fork have to be given, see blob_schedule of the chain
+#[query]
+#[candid_method(query)]
+fn next_blob_base_fee(
+    excess_blob_gas: u64,
+    blob_gas_used: u64,
+    base_fee: U256,
+    schedule: BlobSchedule,
+) -> Result<U256, String> {
+    fee::next_excess_blob_gas(excess_blob_gas, blob_gas_used, &base_fee, &schedule)
+        .and_then(|x| fee::blob_base_fee(x, &schedule))
+        .map_err(|x| format!("Error while calculating blob base fee {x}"))
+}
+
+/// Returns blob parameters of a mainnet fork: cancun, prague, osaka, bpo1 or bpo2
+#[query]
+#[candid_method(query)]
+fn blob_schedule(fork: String) -> Result<BlobSchedule, String> {
+    BlobSchedule::from_fork(&fork).ok_or(format!("Unknown fork {fork}"))
+}
+
+/// Suggests EIP1559 fees from eth_feeHistory response, percentile has to be one of
+/// reward percentiles used in the request
+#[query]
+#[candid_method(query)]
+fn suggest_fees(
+    history: FeeHistory,
+    reward_percentiles: Vec<f64>,
+    percentile: f64,
+) -> Result<FeeSuggestion, String> {
+    fee::suggest_fees(&history, &reward_percentiles, percentile)
+        .map_err(|x| format!("Error while suggesting fees {x}"))
+}
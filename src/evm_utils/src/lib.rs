@@ -1,16 +1,65 @@
-use candid::export_service;
+use candid::{export_service, Principal};
+use ic_cdk::api::management_canister::http_request::{HttpResponse, TransformArgs};
 use ic_cdk::query;
 
+mod access_list;
+mod block;
+mod chain;
+mod contract;
+mod erc1271;
+mod evm;
+mod gas;
 mod hash;
+mod logs;
+mod nonce;
+mod permit;
+mod receipt;
+mod revert;
 mod rlp;
+mod rpc;
+mod safe;
+mod simulation;
+mod siwe;
+mod state;
+mod storage;
+mod token;
 mod transaction;
 mod tree;
 mod types;
+mod user_operation;
 mod utils;
+mod validation;
+mod withdrawal;
 
-use crate::types::num::U256;
+use crate::types::access_list::AccessList;
+use crate::types::address::Address;
+use crate::types::block::Block;
+use crate::types::chain::{Chain, TransactionRequest};
+use crate::types::consensus::RpcConfig;
+use crate::types::eip712::Domain;
+use crate::types::erc1271::{Erc1271Call, Erc6492Signature, SignatureCheck};
+use crate::types::execution::{CallRequest, ExecutionResult, ProvenAccount};
+use crate::types::fee::{BlobSchedule, FeeSuggestion};
+use crate::types::gas::GasCost;
+use crate::types::header::Header;
+use crate::types::log::Log;
+use crate::types::nonce::{NonceAccount, TransactionStatus};
+use crate::types::num::{H256, U256};
+use crate::types::permit::{Erc2612Permit, Permit2};
+use crate::types::receipt::Receipt;
+use crate::types::replacement::{Replacement, ReplacementFees};
+use crate::types::requests::ExecutionRequests;
+use crate::types::revert::Revert;
 use crate::types::rlp::List;
-use crate::types::transaction::Transaction;
+use crate::types::rpc::{CreatedAccessList, FeeHistory, RpcRequest, RpcResponse};
+use crate::types::safe::{MultiSendTransaction, OwnerSignature, SafeTransaction};
+use crate::types::siwe::{SiweConfig, SiweMessage, SiweSession};
+use crate::types::storage::{MappingKey, StorageLocation};
+use crate::types::token::TokenCall;
+use crate::types::transaction::{ParsedTransaction, Transaction};
+use crate::types::user_operation::{HandleOps, PackedUserOperation, UserOperation};
+use crate::types::validation::{ValidationRules, Violation};
+use crate::types::withdrawal::Withdrawal;
 
 #[query(name = "__get_candid_interface_tmp_hack")]
 fn export_candid() -> String {
@@ -24,12 +73,24 @@ mod tests {
 
     #[test]
     fn save_candid() {
+        use candid::{bindings::javascript, check_prog, IDLProg, TypeEnv};
         use std::env;
         use std::fs::write;
         use std::path::PathBuf;
 
-        let dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
-        let dir = dir.parent().unwrap().parent().unwrap().join("candid");
-        write(dir.join("utils.did"), export_candid()).expect("Write failed.");
+        let root = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+        let root = root.parent().unwrap().parent().unwrap();
+        let did = export_candid();
+        write(root.join("candid").join("utils.did"), &did).expect("Write failed.");
+
+        // bindings used by js tests, kept in sync with the interface
+        let prog: IDLProg = did.parse().expect("Invalid candid");
+        let mut env = TypeEnv::new();
+        let actor = check_prog(&mut env, &prog).expect("Invalid candid");
+        write(
+            root.join("__tests__").join("evm_utils.did.js"),
+            javascript::compile(&env, &actor),
+        )
+        .expect("Write failed.");
     }
 }
@@ -0,0 +1,60 @@
+use candid::candid_method;
+use ic_cdk_macros::query;
+
+use crate::{
+    types::{bloom::Bloom, log::Log, num::H256},
+    utils::keccak256,
+};
+
+/// Returns topic of an event, based on its signature e.g. `Transfer(address,address,uint256)`
+#[query]
+#[candid_method(query)]
+fn event_topic(signature: String) -> H256 {
+    let signature: String = signature.chars().filter(|x| !x.is_whitespace()).collect();
+
+    keccak256(&[signature.as_bytes()]).into()
+}
+
+/// Builds 2048 bit bloom filter from addresses and topics of logs
+#[query]
+#[candid_method(query)]
+fn logs_bloom(logs: Vec<Log>) -> Vec<u8> {
+    Bloom::from_logs(&logs).0.to_vec()
+}
+
+/// Checks if address or topic may be a part of the bloom, false positives are possible
+#[query]
+#[candid_method(query)]
+fn bloom_contains(bloom: Vec<u8>, data: Vec<u8>) -> Result<bool, String> {
+    let bloom = Bloom::from_slice(&bloom).map_err(|x| format!("Invalid bloom {x}"))?;
+
+    Ok(bloom.contains(&data))
+}
+
+/// Merges multiple blooms in to one
+#[query]
+#[candid_method(query)]
+fn merge_blooms(blooms: Vec<Vec<u8>>) -> Result<Vec<u8>, String> {
+    let mut result = Bloom::default();
+    for bloom in blooms {
+        let bloom = Bloom::from_slice(&bloom).map_err(|x| format!("Invalid bloom {x}"))?;
+        result.accrue_bloom(&bloom);
+    }
+
+    Ok(result.0.to_vec())
+}
+
+#[cfg(test)]
+mod test {
+    use super::event_topic;
+
+    #[test]
+    fn transfer_topic() {
+        let topic = event_topic(String::from("Transfer(address, address, uint256)"));
+
+        assert_eq!(
+            hex::encode(topic.0),
+            "ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"
+        );
+    }
+}
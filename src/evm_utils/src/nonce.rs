@@ -0,0 +1,146 @@
+use std::error::Error;
+
+use candid::candid_method;
+use ic_cdk_macros::{query, update};
+
+use crate::{
+    chain::find_chain,
+    state::{ensure_owner, with_state, with_state_mut},
+    types::{
+        address::Address,
+        chain::TransactionRequest,
+        errors::{NonceError, TransactionError},
+        nonce::{NonceAccount, TransactionStatus},
+        num::U256,
+        transaction::{ParsedTransaction, Transaction},
+    },
+};
+
+/// Runs function on nonce account, account is created by resync_nonce
+fn with_account<R>(
+    chain_id: u64,
+    address: &Address,
+    f: impl FnOnce(&mut NonceAccount) -> Result<R, Box<dyn Error>>,
+) -> Result<R, Box<dyn Error>> {
+    with_state_mut(|x| {
+        let account = x
+            .nonces
+            .iter_mut()
+            .flatten()
+            .find(|a| a.chain_id == chain_id && a.address == *address)
+            .ok_or(NonceError::NotSynced)?;
+
+        f(account)
+    })
+}
+
+#[query]
+#[candid_method(query)]
+fn get_nonce_account(chain_id: u64, address: Address) -> Option<NonceAccount> {
+    with_state(|x| {
+        x.nonces
+            .iter()
+            .flatten()
+            .find(|a| a.chain_id == chain_id && a.address == address)
+            .cloned()
+    })
+}
+
+/// Synchronizes nonces with `eth_getTransactionCount` of the address, creates account if needed.
+/// Can be called only by the owner
+#[update]
+#[candid_method(update)]
+fn resync_nonce(chain_id: u64, address: Address, count: u64) -> Result<(), String> {
+    ensure_owner(&ic_cdk::caller())?;
+
+    with_state_mut(|x| {
+        let nonces = x.nonces.get_or_insert_with(Vec::new);
+        match nonces
+            .iter_mut()
+            .find(|a| a.chain_id == chain_id && a.address == address)
+        {
+            Some(account) => account.resync(count),
+            None => nonces.push(NonceAccount::new(chain_id, address, count)),
+        }
+    });
+
+    Ok(())
+}
+
+/// Allocates nonce for a new transaction, can be called only by the owner
+#[update]
+#[candid_method(update)]
+fn allocate_nonce(chain_id: u64, address: Address) -> Result<u64, String> {
+    ensure_owner(&ic_cdk::caller())?;
+
+    with_account(chain_id, &address, |account| Ok(account.allocate()))
+        .map_err(|x| format!("Error while allocating nonce {x}"))
+}
+
+/// Builds unsigned transaction like build_transaction, with nonce allocated for the address.
+/// Nonce is not allocated if transaction can not be built. Can be called only by the owner
+#[update]
+#[candid_method(update)]
+fn build_transaction_with_nonce(
+    chain_id: u64,
+    address: Address,
+    req: TransactionRequest,
+) -> Result<Transaction, String> {
+    ensure_owner(&ic_cdk::caller())?;
+    let chain = find_chain(chain_id).ok_or(format!("Unknown chain {chain_id}"))?;
+
+    with_account(chain_id, &address, |account| {
+        let mut next = account.clone();
+        let tx = chain.build_transaction(TransactionRequest {
+            nonce: U256::from(next.allocate()),
+            ..req
+        })?;
+        *account = next;
+
+        Ok(tx)
+    })
+    .map_err(|x| format!("Error while building transaction for {} {x}", chain.name))
+}
+
+/// Starts tracking signed transaction of its sender, returns transaction hash.
+/// Can be called only by the owner
+#[update]
+#[candid_method(update)]
+fn track_transaction(data: Vec<u8>) -> Result<U256, String> {
+    ensure_owner(&ic_cdk::caller())?;
+
+    let track = || -> Result<U256, Box<dyn Error>> {
+        let tx = ParsedTransaction::decode(&data)?;
+        let from = tx.from.ok_or("Transaction is not signed")?;
+        let nonce = tx
+            .transaction
+            .nonce()
+            .as_u64()
+            .ok_or(TransactionError::ValueOverflow)?;
+
+        with_account(tx.transaction.chain_id(), &from, |account| {
+            Ok(account.track(tx.hash.clone(), nonce)?)
+        })?;
+
+        Ok(tx.hash)
+    };
+
+    track().map_err(|x| format!("Error while tracking transaction {x}"))
+}
+
+/// Marks transaction as confirmed or dropped, can be called only by the owner
+#[update]
+#[candid_method(update)]
+fn set_transaction_status(
+    chain_id: u64,
+    address: Address,
+    hash: U256,
+    status: TransactionStatus,
+) -> Result<(), String> {
+    ensure_owner(&ic_cdk::caller())?;
+
+    with_account(chain_id, &address, |account| {
+        Ok(account.set_status(&hash, status)?)
+    })
+    .map_err(|x| format!("Error while updating transaction {x}"))
+}
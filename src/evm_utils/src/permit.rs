@@ -0,0 +1,44 @@
+use candid::candid_method;
+use ic_cdk_macros::query;
+
+use crate::types::{
+    address::Address,
+    eip712::Domain,
+    num::H256,
+    permit::{self, Erc2612Permit, Permit2},
+};
+
+/// Calculates EIP-712 digest of EIP-2612 permit, domain has to match `DOMAIN_SEPARATOR` of
+/// the token. Digest can be signed with threshold ECDSA
+#[query]
+#[candid_method(query)]
+fn erc2612_permit_digest(domain: Domain, permit: Erc2612Permit) -> H256 {
+    permit.digest(&domain)
+}
+
+/// Calculates EIP-712 digest of Permit2 PermitSingle, PermitBatch or PermitTransferFrom
+#[query]
+#[candid_method(query)]
+fn permit2_digest(chain_id: u64, permit: Permit2) -> H256 {
+    permit.digest(chain_id)
+}
+
+/// Returns EIP-712 domain separator, can be compared with `DOMAIN_SEPARATOR` of a contract
+#[query]
+#[candid_method(query)]
+fn domain_separator(domain: Domain) -> H256 {
+    domain.separator()
+}
+
+/// Checks that 65 bytes signature of a permit digest was made by the owner, signatures
+/// with high s are not valid as tokens reject them
+#[query]
+#[candid_method(query)]
+fn verify_permit_signature(
+    digest: H256,
+    signature: Vec<u8>,
+    owner: Address,
+) -> Result<bool, String> {
+    permit::verify_signature(&digest, &signature, &owner)
+        .map_err(|x| format!("Error while recovering signer {x}"))
+}
@@ -0,0 +1,21 @@
+use candid::candid_method;
+use ic_cdk_macros::query;
+
+use crate::types::receipt::Receipt;
+
+/// Parses raw receipt, supports Legacy and typed (EIP2930, EIP1559, EIP4844, EIP7702) receipts
+#[query]
+#[candid_method(query)]
+fn parse_receipt(data: Vec<u8>) -> Result<Receipt, String> {
+    Receipt::decode(&data).map_err(|x| format!("Error while decoding receipt {x}"))
+}
+
+/// Encodes receipt in rlp, as it is committed to in receipts trie. Fails for unsupported
+/// types and blooms that are not 256 bytes long
+#[query]
+#[candid_method(query)]
+fn encode_receipt(data: Receipt) -> Result<Vec<u8>, String> {
+    data.encode()
+        .map(|x| x.to_vec())
+        .map_err(|x| format!("Error while encoding receipt {x}"))
+}
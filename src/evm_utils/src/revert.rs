@@ -0,0 +1,17 @@
+use candid::candid_method;
+use ic_cdk_macros::query;
+
+use crate::types::{abi::ErrorDefinition, revert::Revert};
+
+/// Decodes data returned by a failed call: `Error(string)`, `Panic(uint256)` and custom
+/// errors declared in optional ABI JSON
+#[query]
+#[candid_method(query)]
+fn decode_revert(data: Vec<u8>, abi: Option<String>) -> Result<Revert, String> {
+    let errors = match abi {
+        Some(x) => ErrorDefinition::parse_all(&x).map_err(|x| format!("Invalid ABI {x}"))?,
+        None => vec![],
+    };
+
+    Revert::decode(&data, &errors).map_err(|x| format!("Error while decoding revert data {x}"))
+}
@@ -0,0 +1,186 @@
+use std::{future::Future, pin::Pin};
+
+use candid::{candid_method, Principal};
+use ic_cdk::api::{
+    call::{call_with_payment128, msg_cycles_accept128, msg_cycles_available128},
+    management_canister::http_request::{
+        CanisterHttpRequestArgument, HttpResponse, TransformArgs, TransformContext,
+    },
+};
+use ic_cdk_macros::{query, update};
+use serde_json::Value;
+
+use crate::{
+    state::{ensure_owner, with_state, with_state_mut},
+    types::{
+        consensus::{Outcall, RpcConfig},
+        rpc::{RpcRequest, RpcResponse},
+    },
+};
+
+/// Performs HTTPS outcalls through the management canister, cycles of each outcall are
+/// accepted from the caller right before it is made
+struct IcOutcall;
+
+impl Outcall for IcOutcall {
+    fn http_request(
+        &self,
+        arg: CanisterHttpRequestArgument,
+        cycles: u128,
+    ) -> Pin<Box<dyn Future<Output = Result<HttpResponse, String>> + '_>> {
+        Box::pin(async move {
+            if msg_cycles_accept128(cycles) < cycles {
+                return Err("Not enough cycles attached".to_string());
+            }
+
+            call_with_payment128(
+                Principal::management_canister(),
+                "http_request",
+                (arg,),
+                cycles,
+            )
+            .await
+            .map(|(x,): (HttpResponse,)| x)
+            .map_err(|(code, message)| format!("{code:?} {message}"))
+        })
+    }
+}
+
+fn find_rpc_config(chain_id: u64) -> Result<RpcConfig, String> {
+    with_state(|x| {
+        x.rpc
+            .iter()
+            .flatten()
+            .find(|c| c.chain_id == chain_id)
+            .cloned()
+    })
+    .ok_or(format!("No RPC providers for chain {chain_id}"))
+}
+
+fn transform() -> TransformContext {
+    TransformContext::new(transform_rpc_response, vec![])
+}
+
+/// Returns JSON-RPC payload for the request, to be sent with HTTPS outcall
+#[query]
+#[candid_method(query)]
+fn rpc_request(request: RpcRequest, id: u64) -> Vec<u8> {
+    request.to_json(id)
+}
+
+/// Parses JSON-RPC response body returned by the node for the request
+#[query]
+#[candid_method(query)]
+fn parse_rpc_response(request: RpcRequest, body: Vec<u8>) -> Result<RpcResponse, String> {
+    request
+        .parse_response(&body)
+        .map_err(|x| format!("Error while parsing response {x}"))
+}
+
+/// Transform for HTTPS outcalls, strips headers and normalizes JSON body so all replicas agree
+#[query]
+#[candid_method(query)]
+fn transform_rpc_response(args: TransformArgs) -> HttpResponse {
+    HttpResponse {
+        status: args.response.status,
+        headers: vec![],
+        body: normalize(&args.response.body),
+    }
+}
+
+/// Returns cycles that must be attached to rpc_call, assuming that all providers are called
+#[query]
+#[candid_method(query)]
+fn rpc_call_cycles(chain_id: u64, request: RpcRequest) -> Result<u128, String> {
+    let config = find_rpc_config(chain_id)?;
+    let outcalls = config
+        .outcalls(&request, Some(transform()))
+        .map_err(|x| format!("Error while preparing outcalls {x}"))?;
+
+    Ok(outcalls.iter().map(|(_, cycles)| cycles).sum())
+}
+
+/// Sends request to providers of the chain and returns response agreed by quorum of them.
+/// Caller attaches cycles for all providers, see rpc_call_cycles. Only cycles of outcalls
+/// that were made are charged, providers are not called once quorum is reached
+#[update]
+#[candid_method(update)]
+async fn rpc_call(chain_id: u64, request: RpcRequest) -> Result<RpcResponse, String> {
+    let required = rpc_call_cycles(chain_id, request.clone())?;
+    let available = msg_cycles_available128();
+    if available < required {
+        return Err(format!(
+            "Not enough cycles attached, required {required}, available {available}"
+        ));
+    }
+
+    let config = find_rpc_config(chain_id)?;
+    config
+        .call(&request, Some(transform()), &IcOutcall)
+        .await
+        .map_err(|x| format!("Error while calling providers {x}"))
+}
+
+/// Returns RPC providers of the chain, can be called only by the owner as urls may contain API keys
+#[query]
+#[candid_method(query)]
+fn get_rpc_config(chain_id: u64) -> Result<RpcConfig, String> {
+    ensure_owner(&ic_cdk::caller())?;
+
+    find_rpc_config(chain_id)
+}
+
+/// Sets RPC providers of a chain, can be called only by the owner
+#[update]
+#[candid_method(update)]
+fn set_rpc_config(config: RpcConfig) -> Result<(), String> {
+    ensure_owner(&ic_cdk::caller())?;
+    config
+        .validate()
+        .map_err(|x| format!("Invalid RPC config {x}"))?;
+
+    with_state_mut(|x| {
+        let rpc = x.rpc.get_or_insert_with(Vec::new);
+        rpc.retain(|c| c.chain_id != config.chain_id);
+        rpc.push(config);
+    });
+
+    Ok(())
+}
+
+/// Removes RPC providers of a chain, can be called only by the owner
+#[update]
+#[candid_method(update)]
+fn remove_rpc_config(chain_id: u64) -> Result<(), String> {
+    ensure_owner(&ic_cdk::caller())?;
+
+    with_state_mut(|x| {
+        if let Some(rpc) = x.rpc.as_mut() {
+            rpc.retain(|c| c.chain_id != chain_id);
+        }
+    });
+
+    Ok(())
+}
+
+/// Re-serializes JSON with sorted keys and without whitespace, invalid JSON is returned unchanged
+pub fn normalize(body: &[u8]) -> Vec<u8> {
+    match serde_json::from_slice::<Value>(body) {
+        Ok(x) => x.to_string().into_bytes(),
+        Err(_) => body.to_vec(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::normalize;
+
+    #[test]
+    fn normalize_json() {
+        let a = normalize(br#"{"result": "0x1", "id": 1, "jsonrpc": "2.0"}"#);
+        let b = normalize(br#"{"jsonrpc":"2.0","id":1,"result":"0x1"}"#);
+
+        assert_eq!(a, b);
+        assert_eq!(normalize(b"not json"), b"not json");
+    }
+}
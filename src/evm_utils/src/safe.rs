@@ -0,0 +1,49 @@
+use candid::candid_method;
+use ic_cdk_macros::query;
+
+use crate::types::{
+    address::Address,
+    num::H256,
+    safe::{self, MultiSendTransaction, OwnerSignature, SafeTransaction},
+};
+
+/// Calculates `SafeTx` hash signed by owners of the Safe
+#[query]
+#[candid_method(query)]
+fn safe_transaction_hash(safe: Address, chain_id: u64, transaction: SafeTransaction) -> H256 {
+    transaction.hash(&safe, chain_id)
+}
+
+/// Encodes signatures of owners in order expected by the Safe
+#[query]
+#[candid_method(query)]
+fn encode_safe_signatures(
+    safe: Address,
+    chain_id: u64,
+    transaction: SafeTransaction,
+    signatures: Vec<OwnerSignature>,
+) -> Result<Vec<u8>, String> {
+    safe::encode_signatures(&transaction.hash(&safe, chain_id), &signatures)
+        .map_err(|x| format!("Error while encoding signatures {x}"))
+}
+
+/// Encodes `execTransaction` call data of the Safe with signatures of owners
+#[query]
+#[candid_method(query)]
+fn safe_exec_transaction(
+    safe: Address,
+    chain_id: u64,
+    transaction: SafeTransaction,
+    signatures: Vec<OwnerSignature>,
+) -> Result<Vec<u8>, String> {
+    let signatures = safe::encode_signatures(&transaction.hash(&safe, chain_id), &signatures)
+        .map_err(|x| format!("Error while encoding signatures {x}"))?;
+
+    Ok(transaction.exec_transaction(&signatures))
+}
+
+#[query]
+#[candid_method(query)]
+fn multi_send_call_data(transactions: Vec<MultiSendTransaction>) -> Vec<u8> {
+    safe::multi_send(&transactions)
+}
@@ -0,0 +1,32 @@
+use candid::candid_method;
+use ic_cdk_macros::query;
+
+use crate::{
+    chain::find_chain,
+    evm::{self, state::State, Env},
+    types::{
+        execution::{CallRequest, ExecutionResult, ProvenAccount},
+        header::Header,
+    },
+};
+
+/// Simulates a call or contract creation on top of the block, like eth_call. Accounts
+/// touched by execution have to be given with their proofs (eth_getProof) and code, which
+/// are verified against state root of the header. Fails if execution reads state that was
+/// not proven. Blob base fee is calculated with blob schedule of the chain from the registry
+#[query]
+#[candid_method(query)]
+fn simulate_call(
+    header: Header,
+    chain_id: u64,
+    accounts: Vec<ProvenAccount>,
+    request: CallRequest,
+) -> Result<ExecutionResult, String> {
+    let state = State::from_proofs(&accounts, &header.state_root)
+        .map_err(|x| format!("Error while verifying account proofs {x}"))?;
+    let schedule = find_chain(chain_id).and_then(|x| x.blob_schedule);
+    let env = Env::new(&header, chain_id, &request, schedule.as_ref())
+        .map_err(|x| format!("Error while calculating blob base fee {x}"))?;
+
+    evm::execute(&env, state, &request).map_err(|x| format!("Error while executing call {x}"))
+}
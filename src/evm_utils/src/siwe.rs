@@ -0,0 +1,148 @@
+use std::cell::RefCell;
+
+use candid::{candid_method, Principal};
+use ic_cdk::api::management_canister::main::raw_rand;
+use ic_cdk_macros::{query, update};
+
+use crate::{
+    state::{ensure_owner, with_state, with_state_mut},
+    types::{
+        address::Address,
+        errors::SiweError,
+        siwe::{SiweConfig, SiweMessage, SiweSession, SiweState},
+    },
+};
+
+thread_local! {
+    /// Secret mixed in to nonces, it is not saved in stable memory so it is fetched again
+    /// after install and upgrade
+    static SEED: RefCell<Option<Vec<u8>>> = RefCell::default();
+}
+
+/// Returns random seed of nonces, fetched from the management canister on first use
+async fn seed() -> Result<Vec<u8>, String> {
+    if let Some(seed) = SEED.with(|x| x.borrow().clone()) {
+        return Ok(seed);
+    }
+
+    let (seed,) = raw_rand()
+        .await
+        .map_err(|(code, message)| format!("Error while getting randomness {code:?} {message}"))?;
+    SEED.with(|x| x.replace(Some(seed.clone())));
+
+    Ok(seed)
+}
+
+#[query]
+#[candid_method(query)]
+fn parse_siwe_message(message: String) -> Result<SiweMessage, String> {
+    SiweMessage::parse(&message).map_err(|x| format!("Error while parsing message {x}"))
+}
+
+/// Builds text of EIP-4361 message, which has to be signed with `personal_sign`
+#[query]
+#[candid_method(query)]
+fn build_siwe_message(message: SiweMessage) -> Result<String, String> {
+    message
+        .validate_fields()
+        .map_err(|x| format!("Error while building message {x}"))?;
+
+    Ok(message.to_string())
+}
+
+/// Verifies message for the domain at current canister time, checks that it was signed
+/// by its address. Returns parsed message
+#[query]
+#[candid_method(query)]
+fn verify_siwe_message(
+    message: String,
+    signature: Vec<u8>,
+    domain: String,
+) -> Result<SiweMessage, String> {
+    SiweMessage::verify(&message, &signature, &domain, ic_cdk::api::time())
+        .map_err(|x| format!("Error while verifying message {x}"))
+}
+
+/// Sets domain, uri, chain and expiration times of messages prepared by the canister.
+/// Pending logins and sessions are kept. Login time can not exceed 5 minutes. Can be called
+/// only by the owner
+#[update]
+#[candid_method(update)]
+fn set_siwe_config(config: SiweConfig) -> Result<(), String> {
+    ensure_owner(&ic_cdk::caller())?;
+    config
+        .validate()
+        .map_err(|x| format!("Invalid Sign-In with Ethereum config {x}"))?;
+
+    with_state_mut(|x| match &mut x.siwe {
+        Some(siwe) => siwe.config = config,
+        None => x.siwe = Some(SiweState::new(config)),
+    });
+
+    Ok(())
+}
+
+#[query]
+#[candid_method(query)]
+fn get_siwe_config() -> Option<SiweConfig> {
+    with_state(|x| x.siwe.as_ref().map(|s| s.config.clone()))
+}
+
+/// Prepares message with a new nonce for the address, it has to be signed and passed
+/// to `siwe_login` by the same caller before it expires. Anonymous callers are rejected
+#[update]
+#[candid_method(update)]
+async fn siwe_prepare_login(address: Address) -> Result<String, String> {
+    let caller = ic_cdk::caller();
+    let seed = seed().await?;
+
+    with_state_mut(|x| {
+        let siwe = x
+            .siwe
+            .as_mut()
+            .ok_or("Sign-In with Ethereum is not configured")?;
+        let message = siwe
+            .prepare(caller, &address, ic_cdk::api::time(), &seed)
+            .map_err(|x| format!("Error while preparing login {x}"))?;
+
+        Ok(message.to_string())
+    })
+}
+
+/// Verifies signed message prepared by `siwe_prepare_login` for the caller and creates
+/// session of the caller for the address
+#[update]
+#[candid_method(update)]
+fn siwe_login(message: String, signature: Vec<u8>) -> Result<SiweSession, String> {
+    let caller = ic_cdk::caller();
+
+    with_state_mut(|x| {
+        let siwe = x.siwe.as_mut().ok_or(SiweError::InvalidNonce)?;
+        siwe.login(caller, &message, &signature, ic_cdk::api::time())
+    })
+    .map_err(|x| format!("Error while signing in {x}"))
+}
+
+/// Returns session of the principal if it has not expired
+#[query]
+#[candid_method(query)]
+fn get_siwe_session(principal: Principal) -> Option<SiweSession> {
+    with_state(|x| {
+        x.siwe
+            .as_ref()?
+            .session(&principal, ic_cdk::api::time())
+            .cloned()
+    })
+}
+
+#[update]
+#[candid_method(update)]
+fn siwe_logout() {
+    let caller = ic_cdk::caller();
+
+    with_state_mut(|x| {
+        if let Some(siwe) = &mut x.siwe {
+            siwe.logout(&caller);
+        }
+    });
+}
@@ -0,0 +1,71 @@
+use std::cell::RefCell;
+
+use candid::Principal;
+use ic_cdk::export::candid::{CandidType, Deserialize};
+use ic_cdk_macros::{init, post_upgrade, pre_upgrade};
+
+use crate::types::{chain::Chain, consensus::RpcConfig, nonce::NonceAccount, siwe::SiweState};
+
+/// Canister state, it is kept in stable memory during upgrades
+#[derive(CandidType, Deserialize, Default)]
+pub struct State {
+    /// Principal allowed to call admin methods
+    pub owner: Option<Principal>,
+    /// Chains added by the owner, they take precedence over built in chains
+    pub chains: Vec<Chain>,
+    /// RPC providers of chains, optional so state saved before it was added can be restored
+    pub rpc: Option<Vec<RpcConfig>>,
+    /// Nonces of addresses controlled by the canister
+    pub nonces: Option<Vec<NonceAccount>>,
+    /// Sign-In with Ethereum configuration, nonces and sessions
+    pub siwe: Option<SiweState>,
+}
+
+thread_local! {
+    static STATE: RefCell<State> = RefCell::default();
+}
+
+pub fn with_state<R>(f: impl FnOnce(&State) -> R) -> R {
+    STATE.with(|x| f(&x.borrow()))
+}
+
+pub fn with_state_mut<R>(f: impl FnOnce(&mut State) -> R) -> R {
+    STATE.with(|x| f(&mut x.borrow_mut()))
+}
+
+/// Returns error if caller is not the owner of the canister
+pub fn ensure_owner(caller: &Principal) -> Result<(), String> {
+    with_state(|x| match x.owner {
+        Some(owner) if owner == *caller => Ok(()),
+        _ => Err(String::from("Caller is not the owner of the canister")),
+    })
+}
+
+#[init]
+fn init() {
+    with_state_mut(|x| x.owner = Some(ic_cdk::caller()));
+}
+
+#[pre_upgrade]
+fn pre_upgrade() {
+    let state = STATE.with(|x| x.take());
+    ic_cdk::storage::stable_save((state,)).expect("Error while saving state");
+}
+
+#[post_upgrade]
+fn post_upgrade() {
+    // stable memory is empty when upgrading from a version without state, any other
+    // failure traps so the upgrade is rolled back instead of losing the state
+    let mut state = if ic_cdk::api::stable::stable_size() == 0 {
+        State::default()
+    } else {
+        let (state,): (State,) =
+            ic_cdk::storage::stable_restore().expect("Error while restoring state");
+        state
+    };
+    if state.owner.is_none() {
+        state.owner = Some(ic_cdk::caller());
+    }
+
+    STATE.with(|x| x.replace(state));
+}
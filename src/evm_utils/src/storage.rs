@@ -0,0 +1,59 @@
+use candid::candid_method;
+use ic_cdk_macros::query;
+
+use crate::types::{
+    num::{H256, U256},
+    storage::{self, MappingKey, StorageLocation},
+};
+
+/// Returns storage slot of a value in nested mapping, keys are given from the outermost one.
+/// Slot can be passed as a key to verify_proof
+#[query]
+#[candid_method(query)]
+fn nested_mapping_slot(keys: Vec<MappingKey>, slot: H256) -> Result<H256, String> {
+    storage::nested_mapping_slot(&keys, &slot).map_err(|x| format!("Invalid mapping key {x}"))
+}
+
+/// Returns location of an element of a dynamic array declared at slot. Structs and arrays
+/// elements should be given with size of 32 bytes times number of their slots
+#[query]
+#[candid_method(query)]
+fn array_element_slot(
+    slot: H256,
+    index: U256,
+    element_size: u32,
+) -> Result<StorageLocation, String> {
+    storage::array_element(&slot, &index, element_size)
+        .map_err(|x| format!("Error while calculating slot {x}"))
+}
+
+/// Returns locations of struct fields (or consecutive state variables) starting at slot,
+/// based on sizes of fields in bytes
+#[query]
+#[candid_method(query)]
+fn struct_field_slots(slot: H256, field_sizes: Vec<u32>) -> Result<Vec<StorageLocation>, String> {
+    storage::struct_fields(&slot, &field_sizes)
+        .map_err(|x| format!("Error while calculating slots {x}"))
+}
+
+/// Splits value of a slot in to packed fields, first field is stored in the lowest order bytes
+#[query]
+#[candid_method(query)]
+fn decode_packed_slot(value: H256, field_sizes: Vec<u32>) -> Result<Vec<Vec<u8>>, String> {
+    storage::decode_packed(&value, &field_sizes)
+        .map_err(|x| format!("Error while decoding slot {x}"))
+}
+
+/// Returns proxy slot defined by EIP-1967, e.g. for `eip1967.proxy.implementation`
+#[query]
+#[candid_method(query)]
+fn eip1967_slot(name: String) -> H256 {
+    storage::eip1967_slot(&name)
+}
+
+/// Returns root slot of a namespace defined by EIP-7201, e.g. for `openzeppelin.storage.ERC20`
+#[query]
+#[candid_method(query)]
+fn erc7201_slot(namespace: String) -> H256 {
+    storage::erc7201_slot(&namespace)
+}
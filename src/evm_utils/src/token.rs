@@ -0,0 +1,48 @@
+use candid::candid_method;
+use ic_cdk_macros::query;
+
+use crate::{
+    chain::find_chain,
+    types::{
+        address::Address, chain::TransactionRequest, num::U256, token::TokenCall,
+        transaction::Transaction,
+    },
+};
+
+/// Encodes call data of ERC-20, ERC-721 or ERC-1155 method
+#[query]
+#[candid_method(query)]
+fn token_call_data(call: TokenCall) -> Vec<u8> {
+    call.encode()
+}
+
+/// Recognizes token method from call data, returns None for other calls
+#[query]
+#[candid_method(query)]
+fn decode_token_call(data: Vec<u8>) -> Option<TokenCall> {
+    TokenCall::decode(&data)
+}
+
+/// Builds unsigned transaction calling token contract. Recipient, value and data of the
+/// request are replaced with the token, zero and encoded call
+#[query]
+#[candid_method(query)]
+fn build_token_transaction(
+    chain_id: u64,
+    token: Address,
+    call: TokenCall,
+    req: TransactionRequest,
+) -> Result<Transaction, String> {
+    let chain = find_chain(chain_id).ok_or(format!("Unknown chain {chain_id}"))?;
+
+    let req = TransactionRequest {
+        to: Some(token),
+        value: U256::zero(),
+        data: call.encode(),
+        ..req
+    };
+
+    chain
+        .build_transaction(req)
+        .map_err(|x| format!("Error while building transaction for {} {x}", chain.name))
+}
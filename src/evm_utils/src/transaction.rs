@@ -2,7 +2,12 @@ use candid::candid_method;
 use ic_cdk_macros::query;
 
 use crate::{
-    types::{num::U256, transaction::Transaction},
+    types::{
+        address::Address,
+        num::U256,
+        replacement::{Replacement, ReplacementFees},
+        transaction::{ParsedTransaction, Transaction},
+    },
     utils::keccak256,
 };
 
@@ -27,11 +32,41 @@ fn encode_signed_transaction(data: Transaction) -> Result<(Vec<u8>, U256), Strin
 }
 
 /// Parses raw transaction, supports Legacy, EIP1559, EIP2930
+/// Returns decoded transaction along with its hash, signing hash and sender
 #[query]
 #[candid_method(query)]
-fn parse_transaction(data: Vec<u8>) -> Result<Transaction, String> {
-    let item =
-        Transaction::decode(&data).map_err(|x| format!("Error while decoding transaction {x}"))?;
+fn parse_transaction(data: Vec<u8>) -> Result<ParsedTransaction, String> {
+    let item = ParsedTransaction::decode(&data)
+        .map_err(|x| format!("Error while decoding transaction {x}"))?;
 
     Ok(item)
 }
+
+/// Returns hash of raw transaction, without decoding its content
+#[query]
+#[candid_method(query)]
+fn transaction_hash(data: Vec<u8>) -> Result<U256, String> {
+    Transaction::envelope_type(&data).map_err(|x| format!("Invalid transaction {x}"))?;
+
+    Ok(keccak256(&[&data]))
+}
+
+/// Builds replacement of a pending transaction with the same nonce, fees are bumped by
+/// at least 10% or raised to provided ones. Returns unsigned transaction and its signing hash
+#[query]
+#[candid_method(query)]
+fn speed_up_transaction(tx: Transaction, fees: ReplacementFees) -> Result<Replacement, String> {
+    Replacement::speed_up(&tx, &fees).map_err(|x| format!("Error while bumping fees {x}"))
+}
+
+/// Builds zero value transfer to the sender with the same nonce and bumped fees, which cancels
+/// pending transaction. Returns unsigned transaction and its signing hash
+#[query]
+#[candid_method(query)]
+fn cancel_transaction(
+    tx: Transaction,
+    from: Address,
+    fees: ReplacementFees,
+) -> Result<Replacement, String> {
+    Replacement::cancel(&tx, &from, &fees).map_err(|x| format!("Error while bumping fees {x}"))
+}
@@ -1,8 +1,27 @@
+use std::{error::Error, sync::Arc};
+
 use candid::candid_method;
+use cita_trie::{MemoryDB, PatriciaTrie, Trie};
 use hasher::HasherKeccak;
 use ic_cdk::query;
 
-use crate::utils::keccak256;
+use crate::{types::num::U256, utils::keccak256};
+
+/// Calculates root of a trie where values are keyed by rlp encoded index in the list,
+/// as used for transactions, receipts and withdrawals roots
+pub fn ordered_trie_root(values: &[Vec<u8>]) -> Result<U256, Box<dyn Error>> {
+    let mut trie = PatriciaTrie::new(Arc::new(MemoryDB::new(true)), Arc::new(HasherKeccak::new()));
+
+    for (i, value) in values.iter().enumerate() {
+        trie.insert(rlp::encode(&i).to_vec(), value.clone())?;
+    }
+
+    let root = trie.root()?;
+    let mut buf = [0u8; 32];
+    buf.copy_from_slice(&root);
+
+    Ok(U256(buf))
+}
 
 #[query]
 #[candid_method(query)]
@@ -22,6 +41,7 @@ fn verify_proof(
 
 #[cfg(test)]
 mod test {
+    use super::ordered_trie_root;
     use crate::{types::num::U256, utils::keccak256};
     use hasher::HasherKeccak;
     use std::error::Error;
@@ -59,4 +79,24 @@ mod test {
             None => Err(Box::new(TestError::NotFound)),
         }
     }
+
+    #[test]
+    fn ordered_trie_root_test() -> Result<(), Box<dyn Error>> {
+        let root = ordered_trie_root(&[])?;
+        assert_eq!(
+            format!("{root}"),
+            "0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421"
+        );
+
+        // Single value is stored in a leaf node, with path of rlp encoded 0 (0x80)
+        let value = vec![0xabu8; 40];
+        let mut leaf = rlp::RlpStream::new_list(2);
+        leaf.append(&vec![0x20u8, 0x80]);
+        leaf.append(&value);
+
+        let root = ordered_trie_root(&[value])?;
+        assert_eq!(root, keccak256(&[&leaf.out()]));
+
+        Ok(())
+    }
 }
@@ -0,0 +1,516 @@
+use ic_cdk::export::candid::{CandidType, Deserialize};
+use serde_json::Value;
+
+use super::{address::Address, errors::AbiError, num::U256};
+use crate::utils::keccak256;
+
+/// Solidity type of a parameter
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum ParamType {
+    Uint(usize),
+    Int(usize),
+    Address,
+    Bool,
+    FixedBytes(usize),
+    Bytes,
+    String,
+    Array(Box<ParamType>),
+    FixedArray(Box<ParamType>, usize),
+    Tuple(Vec<ParamType>),
+}
+
+impl ParamType {
+    /// Parses type as used in ABI JSON, components are required for tuples
+    pub fn parse(name: &str, components: Option<Vec<ParamType>>) -> Result<Self, AbiError> {
+        if let Some(inner) = name.strip_suffix(']') {
+            let start = inner.rfind('[').ok_or(AbiError::UnknownType)?;
+            let item = Box::new(Self::parse(&inner[..start], components)?);
+
+            return match &inner[start + 1..] {
+                "" => Ok(ParamType::Array(item)),
+                size => Ok(ParamType::FixedArray(
+                    item,
+                    size.parse().map_err(|_| AbiError::UnknownType)?,
+                )),
+            };
+        }
+
+        let bits = |x: &str| -> Result<usize, AbiError> {
+            let bits = match x {
+                "" => 256,
+                x => x.parse().map_err(|_| AbiError::UnknownType)?,
+            };
+            if bits % 8 != 0 || !(8..=256).contains(&bits) {
+                return Err(AbiError::UnknownType);
+            }
+            Ok(bits)
+        };
+
+        match name {
+            "address" => Ok(ParamType::Address),
+            "bool" => Ok(ParamType::Bool),
+            "bytes" => Ok(ParamType::Bytes),
+            "string" => Ok(ParamType::String),
+            "tuple" => Ok(ParamType::Tuple(components.ok_or(AbiError::UnknownType)?)),
+            x if x.starts_with("uint") => Ok(ParamType::Uint(bits(&x[4..])?)),
+            x if x.starts_with("int") => Ok(ParamType::Int(bits(&x[3..])?)),
+            x if x.starts_with("bytes") => {
+                let size: usize = x[5..].parse().map_err(|_| AbiError::UnknownType)?;
+                if !(1..=32).contains(&size) {
+                    return Err(AbiError::UnknownType);
+                }
+                Ok(ParamType::FixedBytes(size))
+            }
+            _ => Err(AbiError::UnknownType),
+        }
+    }
+
+    /// Canonical name used in signatures
+    pub fn signature(&self) -> String {
+        match self {
+            ParamType::Uint(x) => format!("uint{x}"),
+            ParamType::Int(x) => format!("int{x}"),
+            ParamType::Address => "address".to_string(),
+            ParamType::Bool => "bool".to_string(),
+            ParamType::FixedBytes(x) => format!("bytes{x}"),
+            ParamType::Bytes => "bytes".to_string(),
+            ParamType::String => "string".to_string(),
+            ParamType::Array(x) => format!("{}[]", x.signature()),
+            ParamType::FixedArray(x, size) => format!("{}[{size}]", x.signature()),
+            ParamType::Tuple(x) => format!(
+                "({})",
+                x.iter()
+                    .map(|x| x.signature())
+                    .collect::<Vec<_>>()
+                    .join(",")
+            ),
+        }
+    }
+
+    pub fn is_dynamic(&self) -> bool {
+        match self {
+            ParamType::Bytes | ParamType::String | ParamType::Array(_) => true,
+            ParamType::FixedArray(x, _) => x.is_dynamic(),
+            ParamType::Tuple(x) => x.iter().any(|x| x.is_dynamic()),
+            _ => false,
+        }
+    }
+
+    /// Size of the value in the head of encoding
+    fn head_size(&self) -> usize {
+        match self {
+            x if x.is_dynamic() => 32,
+            ParamType::FixedArray(x, size) => x.head_size() * size,
+            ParamType::Tuple(x) => x.iter().map(|x| x.head_size()).sum(),
+            _ => 32,
+        }
+    }
+}
+
+/// Named parameter of a function, event or error
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Param {
+    pub name: String,
+    pub kind: ParamType,
+}
+
+impl Param {
+    fn from_json(value: &Value) -> Result<Self, AbiError> {
+        let components = match value.get("components") {
+            Some(Value::Array(x)) => Some(
+                x.iter()
+                    .map(|x| Ok(Self::from_json(x)?.kind))
+                    .collect::<Result<Vec<_>, AbiError>>()?,
+            ),
+            _ => None,
+        };
+        let kind = value
+            .get("type")
+            .and_then(|x| x.as_str())
+            .ok_or(AbiError::InvalidAbi)?;
+
+        Ok(Self {
+            name: value
+                .get("name")
+                .and_then(|x| x.as_str())
+                .unwrap_or_default()
+                .to_string(),
+            kind: ParamType::parse(kind, components)?,
+        })
+    }
+}
+
+/// Custom error declared in ABI
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ErrorDefinition {
+    pub name: String,
+    pub inputs: Vec<Param>,
+}
+
+impl ErrorDefinition {
+    pub fn signature(&self) -> String {
+        let inputs: Vec<String> = self.inputs.iter().map(|x| x.kind.signature()).collect();
+        format!("{}({})", self.name, inputs.join(","))
+    }
+
+    pub fn selector(&self) -> [u8; 4] {
+        selector(&self.signature())
+    }
+
+    /// Reads errors from ABI JSON, other items are skipped
+    pub fn parse_all(abi: &str) -> Result<Vec<Self>, AbiError> {
+        let abi: Value = serde_json::from_str(abi).map_err(|_| AbiError::InvalidAbi)?;
+        let items = abi.as_array().ok_or(AbiError::InvalidAbi)?;
+
+        items
+            .iter()
+            .filter(|x| x.get("type").and_then(|x| x.as_str()) == Some("error"))
+            .map(|x| {
+                let inputs = match x.get("inputs") {
+                    Some(Value::Array(x)) => {
+                        x.iter()
+                            .map(Param::from_json)
+                            .collect::<Result<Vec<_>, AbiError>>()?
+                    }
+                    _ => vec![],
+                };
+                Ok(Self {
+                    name: x
+                        .get("name")
+                        .and_then(|x| x.as_str())
+                        .ok_or(AbiError::InvalidAbi)?
+                        .to_string(),
+                    inputs,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Decoded ABI value
+#[derive(CandidType, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub enum AbiValue {
+    Uint(U256),
+    /// Two's complement representation
+    Int(U256),
+    Address(Address),
+    Bool(bool),
+    FixedBytes(Vec<u8>),
+    Bytes(Vec<u8>),
+    String(String),
+    Array(Vec<AbiValue>),
+    FixedArray(Vec<AbiValue>),
+    Tuple(Vec<AbiValue>),
+}
+
+impl AbiValue {
+    pub fn is_dynamic(&self) -> bool {
+        match self {
+            AbiValue::Bytes(_) | AbiValue::String(_) | AbiValue::Array(_) => true,
+            AbiValue::FixedArray(x) | AbiValue::Tuple(x) => x.iter().any(|x| x.is_dynamic()),
+            _ => false,
+        }
+    }
+}
+
+/// First 4 bytes of keccak256 of a signature, identifies functions and errors
+pub fn selector(signature: &str) -> [u8; 4] {
+    let hash = keccak256(&[signature.as_bytes()]);
+
+    let mut selector = [0u8; 4];
+    selector.copy_from_slice(&hash.0[..4]);
+    selector
+}
+
+fn usize_word(value: usize) -> [u8; 32] {
+    U256::from(value as u64).0
+}
+
+/// Pads data with zeros to a multiple of 32 bytes
+fn padded(data: &[u8]) -> Vec<u8> {
+    let mut buf = data.to_vec();
+    buf.resize(data.len().div_ceil(32) * 32, 0);
+    buf
+}
+
+/// ABI encodes values, like parameters of a call without selector
+pub fn encode(values: &[AbiValue]) -> Vec<u8> {
+    let parts: Vec<(bool, Vec<u8>)> = values
+        .iter()
+        .map(|x| (x.is_dynamic(), encode_value(x)))
+        .collect();
+    let head_size: usize = parts
+        .iter()
+        .map(|(dynamic, x)| if *dynamic { 32 } else { x.len() })
+        .sum();
+
+    let mut head = Vec::with_capacity(head_size);
+    let mut tail = vec![];
+    for (dynamic, part) in parts {
+        if dynamic {
+            head.extend_from_slice(&usize_word(head_size + tail.len()));
+            tail.extend(part);
+        } else {
+            head.extend(part);
+        }
+    }
+    head.extend(tail);
+
+    head
+}
+
+fn encode_value(value: &AbiValue) -> Vec<u8> {
+    match value {
+        AbiValue::Uint(x) | AbiValue::Int(x) => x.0.to_vec(),
+        AbiValue::Address(x) => {
+            let mut buf = vec![0u8; 12];
+            buf.extend_from_slice(&x.0);
+            buf
+        }
+        AbiValue::Bool(x) => usize_word(*x as usize).to_vec(),
+        AbiValue::FixedBytes(x) => padded(x),
+        AbiValue::Bytes(x) => [&usize_word(x.len())[..], &padded(x)].concat(),
+        AbiValue::String(x) => [&usize_word(x.len())[..], &padded(x.as_bytes())].concat(),
+        AbiValue::Array(x) => [usize_word(x.len()).to_vec(), encode(x)].concat(),
+        AbiValue::FixedArray(x) | AbiValue::Tuple(x) => encode(x),
+    }
+}
+
+fn word(data: &[u8], offset: usize) -> Result<[u8; 32], AbiError> {
+    let mut word = [0u8; 32];
+    word.copy_from_slice(data.get(offset..offset + 32).ok_or(AbiError::InvalidData)?);
+
+    Ok(word)
+}
+
+/// Reads offset or length, it has to point inside of data
+fn read_usize(data: &[u8], offset: usize) -> Result<usize, AbiError> {
+    let word = word(data, offset)?;
+    if word[..24].iter().any(|x| *x != 0) {
+        return Err(AbiError::InvalidData);
+    }
+
+    let mut buf = [0u8; 8];
+    buf.copy_from_slice(&word[24..]);
+    let value = u64::from_be_bytes(buf) as usize;
+    if value > data.len() {
+        return Err(AbiError::InvalidData);
+    }
+
+    Ok(value)
+}
+
+/// Decodes ABI encoded values, like parameters of a call without selector
+pub fn decode(types: &[ParamType], data: &[u8]) -> Result<Vec<AbiValue>, AbiError> {
+    let mut values = Vec::with_capacity(types.len());
+    let mut head = 0;
+    for kind in types {
+        let value = if kind.is_dynamic() {
+            let offset = read_usize(data, head)?;
+            decode_value(kind, &data[offset..])?
+        } else {
+            decode_value(kind, data.get(head..).ok_or(AbiError::InvalidData)?)?
+        };
+        values.push(value);
+        head += kind.head_size();
+    }
+
+    Ok(values)
+}
+
+fn decode_value(kind: &ParamType, data: &[u8]) -> Result<AbiValue, AbiError> {
+    match kind {
+        ParamType::Uint(bits) => {
+            let word = word(data, 0)?;
+            if word[..32 - bits / 8].iter().any(|x| *x != 0) {
+                return Err(AbiError::InvalidData);
+            }
+            Ok(AbiValue::Uint(U256(word)))
+        }
+        ParamType::Int(bits) => {
+            let word = word(data, 0)?;
+            let sign = match word[32 - bits / 8] & 0x80 {
+                0 => 0,
+                _ => 0xff,
+            };
+            if word[..32 - bits / 8].iter().any(|x| *x != sign) {
+                return Err(AbiError::InvalidData);
+            }
+            Ok(AbiValue::Int(U256(word)))
+        }
+        ParamType::Address => {
+            let word = word(data, 0)?;
+            if word[..12].iter().any(|x| *x != 0) {
+                return Err(AbiError::InvalidData);
+            }
+            let mut address = Address([0u8; 20]);
+            address.0.copy_from_slice(&word[12..]);
+            Ok(AbiValue::Address(address))
+        }
+        ParamType::Bool => match word(data, 0)? {
+            x if x[..31].iter().any(|x| *x != 0) => Err(AbiError::InvalidData),
+            x if x[31] > 1 => Err(AbiError::InvalidData),
+            x => Ok(AbiValue::Bool(x[31] == 1)),
+        },
+        ParamType::FixedBytes(size) => {
+            let word = word(data, 0)?;
+            if word[*size..].iter().any(|x| *x != 0) {
+                return Err(AbiError::InvalidData);
+            }
+            Ok(AbiValue::FixedBytes(word[..*size].to_vec()))
+        }
+        ParamType::Bytes | ParamType::String => {
+            let len = read_usize(data, 0)?;
+            let bytes = data
+                .get(32..32 + len)
+                .ok_or(AbiError::InvalidData)?
+                .to_vec();
+            match kind {
+                ParamType::String => Ok(AbiValue::String(
+                    String::from_utf8(bytes).map_err(|_| AbiError::InvalidData)?,
+                )),
+                _ => Ok(AbiValue::Bytes(bytes)),
+            }
+        }
+        ParamType::Array(item) => {
+            let len = read_usize(data, 0)?;
+            // Every element takes at least a word, protects from huge allocations
+            if len * 32 > data.len() - 32 {
+                return Err(AbiError::InvalidData);
+            }
+            let types = vec![item.as_ref().clone(); len];
+            Ok(AbiValue::Array(decode(&types, &data[32..])?))
+        }
+        ParamType::FixedArray(item, size) => {
+            if size * 32 > data.len() {
+                return Err(AbiError::InvalidData);
+            }
+            let types = vec![item.as_ref().clone(); *size];
+            Ok(AbiValue::FixedArray(decode(&types, data)?))
+        }
+        ParamType::Tuple(types) => Ok(AbiValue::Tuple(decode(types, data)?)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::error::Error;
+
+    use super::{decode, encode, selector, AbiValue, ErrorDefinition, ParamType};
+    use crate::types::{address::Address, num::U256};
+
+    #[test]
+    fn parse_type() -> Result<(), Box<dyn Error>> {
+        let kind = ParamType::parse(
+            "tuple[2][]",
+            Some(vec![ParamType::Uint(8), ParamType::Bytes]),
+        )?;
+
+        assert_eq!(kind.signature(), "(uint8,bytes)[2][]");
+        assert!(kind.is_dynamic());
+        assert_eq!(ParamType::parse("int", None)?, ParamType::Int(256));
+        assert_eq!(
+            ParamType::parse("bytes32", None)?,
+            ParamType::FixedBytes(32)
+        );
+        assert!(ParamType::parse("uint7", None).is_err());
+        assert!(ParamType::parse("bytes33", None).is_err());
+        assert!(ParamType::parse("tuple", None).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn selectors() {
+        assert_eq!(selector("Error(string)"), [0x08, 0xc3, 0x79, 0xa0]);
+        assert_eq!(
+            selector("transfer(address,uint256)"),
+            [0xa9, 0x05, 0x9c, 0xbb]
+        );
+    }
+
+    #[test]
+    fn parse_errors() -> Result<(), Box<dyn Error>> {
+        let abi = r#"[
+            {"type": "function", "name": "transfer", "inputs": []},
+            {"type": "error", "name": "Unauthorized", "inputs": []},
+            {"type": "error", "name": "Failed", "inputs": [
+                {"name": "order", "type": "tuple", "components": [
+                    {"name": "id", "type": "uint256"},
+                    {"name": "tags", "type": "string[]"}
+                ]}
+            ]}
+        ]"#;
+
+        let errors = ErrorDefinition::parse_all(abi)?;
+
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].signature(), "Unauthorized()");
+        assert_eq!(errors[1].signature(), "Failed((uint256,string[]))");
+        assert_eq!(errors[1].inputs[0].name, "order");
+        assert!(ErrorDefinition::parse_all("{}").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn decode_values() -> Result<(), Box<dyn Error>> {
+        // (address, int8, string, uint16[]) = (0x11.., -1, "hi", [1, 2])
+        let data = hex::decode(
+            "0000000000000000000000001111111111111111111111111111111111111111\
+             ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff\
+             0000000000000000000000000000000000000000000000000000000000000080\
+             00000000000000000000000000000000000000000000000000000000000000c0\
+             0000000000000000000000000000000000000000000000000000000000000002\
+             6869000000000000000000000000000000000000000000000000000000000000\
+             0000000000000000000000000000000000000000000000000000000000000002\
+             0000000000000000000000000000000000000000000000000000000000000001\
+             0000000000000000000000000000000000000000000000000000000000000002",
+        )?;
+        let types = [
+            ParamType::Address,
+            ParamType::Int(8),
+            ParamType::String,
+            ParamType::Array(Box::new(ParamType::Uint(16))),
+        ];
+
+        let values = decode(&types, &data)?;
+
+        assert_eq!(values[0], AbiValue::Address(Address([0x11; 20])));
+        assert_eq!(values[1], AbiValue::Int(U256([0xff; 32])));
+        assert_eq!(values[2], AbiValue::String("hi".to_string()));
+        assert_eq!(
+            values[3],
+            AbiValue::Array(vec![
+                AbiValue::Uint(U256::from(1)),
+                AbiValue::Uint(U256::from(2))
+            ])
+        );
+
+        assert_eq!(encode(&values), data);
+        assert!(decode(&types, &data[..data.len() - 1]).is_err());
+        assert!(decode(&[ParamType::Uint(8)], &data[32..64]).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn encode_nested() -> Result<(), Box<dyn Error>> {
+        // ((uint8,bytes)[2]) with dynamic tuples inside of a fixed array
+        let kind = ParamType::parse("tuple[2]", Some(vec![ParamType::Uint(8), ParamType::Bytes]))?;
+        let item = |x: u64, data: &[u8]| {
+            AbiValue::Tuple(vec![
+                AbiValue::Uint(U256::from(x)),
+                AbiValue::Bytes(data.to_vec()),
+            ])
+        };
+        let values = vec![AbiValue::FixedArray(vec![item(1, &[0xab]), item(2, &[])])];
+
+        let data = encode(&values);
+
+        assert_eq!(data.len(), 32 * 10);
+        assert_eq!(decode(&[kind], &data)?, values);
+
+        Ok(())
+    }
+}
@@ -1,7 +1,11 @@
 use ic_cdk::export::candid::{CandidType, Deserialize};
 use rlp::{Decodable, Encodable, RlpStream};
 
-use super::{address::Address, num::H256};
+use super::{
+    address::Address,
+    gas::{TX_ACCESS_LIST_ADDRESS_GAS, TX_ACCESS_LIST_STORAGE_KEY_GAS},
+    num::H256,
+};
 
 #[derive(CandidType, Deserialize, Clone, PartialEq, Eq)]
 pub struct AccessList {
@@ -9,6 +13,38 @@ pub struct AccessList {
     pub storage_keys: Vec<H256>,
 }
 
+impl AccessList {
+    /// Merges entries of the same address, removes duplicated storage keys and sorts
+    /// entries by address and keys
+    pub fn normalize(list: Vec<AccessList>) -> Vec<AccessList> {
+        let mut result: Vec<AccessList> = vec![];
+        for item in list {
+            match result.iter_mut().find(|x| x.address == item.address) {
+                Some(x) => x.storage_keys.extend(item.storage_keys),
+                None => result.push(item),
+            }
+        }
+
+        for item in result.iter_mut() {
+            item.storage_keys.sort();
+            item.storage_keys.dedup();
+        }
+        result.sort_by(|a, b| a.address.cmp(&b.address));
+
+        result
+    }
+
+    /// Intrinsic gas paid for access list (EIP-2930)
+    pub fn gas(list: &[AccessList]) -> u64 {
+        list.iter()
+            .map(|x| {
+                TX_ACCESS_LIST_ADDRESS_GAS
+                    + TX_ACCESS_LIST_STORAGE_KEY_GAS * x.storage_keys.len() as u64
+            })
+            .sum()
+    }
+}
+
 impl Decodable for AccessList {
     fn decode(rlp: &rlp::Rlp) -> Result<Self, rlp::DecoderError> {
         let item_count = rlp.item_count()?;
@@ -34,3 +70,37 @@ impl Encodable for AccessList {
         rlp.append_list(&self.storage_keys);
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::AccessList;
+    use crate::types::{address::Address, num::H256};
+
+    #[test]
+    fn normalize() {
+        let list = vec![
+            AccessList {
+                address: Address([2u8; 20]),
+                storage_keys: vec![H256([3u8; 32]), H256([1u8; 32])],
+            },
+            AccessList {
+                address: Address([1u8; 20]),
+                storage_keys: vec![],
+            },
+            AccessList {
+                address: Address([2u8; 20]),
+                storage_keys: vec![H256([1u8; 32]), H256([2u8; 32])],
+            },
+        ];
+
+        let list = AccessList::normalize(list);
+
+        assert_eq!(list.len(), 2);
+        assert!(list[0].address == Address([1u8; 20]));
+        assert_eq!(
+            list[1].storage_keys,
+            vec![H256([1u8; 32]), H256([2u8; 32]), H256([3u8; 32])]
+        );
+        assert_eq!(AccessList::gas(&list), 2 * 2_400 + 3 * 1_900);
+    }
+}
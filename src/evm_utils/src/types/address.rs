@@ -1,4 +1,5 @@
 use std::fmt::Display;
+use std::str::FromStr;
 
 use ic_cdk::export::candid::{CandidType, Deserialize};
 use rlp::{Decodable, DecoderError, Encodable, RlpStream};
@@ -6,9 +7,74 @@ use secp256k1::PublicKey;
 
 use crate::utils::keccak256;
 
-#[derive(CandidType, Deserialize, Clone, PartialEq, Eq)]
+use super::num::{H256, U256};
+
+#[derive(CandidType, Deserialize, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Address(pub [u8; 20]);
 
+impl Address {
+    /// Address of a contract deployed with CREATE, keccak256(rlp([sender, nonce]))[12..]
+    pub fn from_create(sender: &Address, nonce: &U256) -> Self {
+        let mut rlp = RlpStream::new_list(2);
+        rlp.append(sender);
+        rlp.append(nonce);
+
+        Self::from_hash(&keccak256(&[&rlp.out()]))
+    }
+
+    /// Address of a contract deployed with CREATE2, keccak256(0xff ++ deployer ++ salt ++ keccak256(init_code))[12..]
+    pub fn from_create2(deployer: &Address, salt: &H256, init_code_hash: &U256) -> Self {
+        let hash = keccak256(&[&[0xff], &deployer.0, &salt.0, &init_code_hash.0]);
+
+        Self::from_hash(&hash)
+    }
+
+    /// Decodes recipient of a transaction, empty value means contract creation
+    pub fn decode_optional(rlp: &rlp::Rlp) -> Result<Option<Self>, DecoderError> {
+        if rlp.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(rlp.as_val()?))
+        }
+    }
+
+    /// Appends recipient of a transaction, empty value is used for contract creation
+    pub fn append_optional(address: &Option<Self>, rlp: &mut RlpStream) {
+        match address {
+            Some(address) => rlp.append(address),
+            None => rlp.append_empty_data(),
+        };
+    }
+
+    /// Returns address with EIP-55 mixed case checksum
+    pub fn to_checksum(&self) -> String {
+        let hex = hex::encode(self.0);
+        let hash = keccak256(&[hex.as_bytes()]);
+
+        let mut result = String::from("0x");
+        for (i, c) in hex.chars().enumerate() {
+            let nibble = match i % 2 {
+                0 => hash.0[i / 2] >> 4,
+                _ => hash.0[i / 2] & 0x0f,
+            };
+            result.push(if nibble >= 8 {
+                c.to_ascii_uppercase()
+            } else {
+                c
+            });
+        }
+
+        result
+    }
+
+    fn from_hash(hash: &U256) -> Self {
+        let mut buf = [0u8; 20];
+        buf.copy_from_slice(&hash.0[12..]);
+
+        Self(buf)
+    }
+}
+
 impl Decodable for Address {
     fn decode(rlp: &rlp::Rlp) -> Result<Self, rlp::DecoderError> {
         let data = rlp.data()?;
@@ -38,13 +104,26 @@ impl Display for Address {
     }
 }
 
-impl From<PublicKey> for Address {
-    fn from(from: PublicKey) -> Self {
-        let addr = &keccak256(&[&from.serialize_uncompressed()[1..]]).0[12..];
+impl std::fmt::Debug for Address {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self}")
+    }
+}
+
+impl FromStr for Address {
+    type Err = hex::FromHexError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut buf = [0u8; 20];
-        buf.copy_from_slice(&addr[..20]);
+        hex::decode_to_slice(s.trim_start_matches("0x"), &mut buf)?;
 
-        Self(buf)
+        Ok(Self(buf))
+    }
+}
+
+impl From<PublicKey> for Address {
+    fn from(from: PublicKey) -> Self {
+        Self::from_hash(&keccak256(&[&from.serialize_uncompressed()[1..]]))
     }
 }
 
@@ -56,6 +135,21 @@ mod test {
     use secp256k1::PublicKey;
 
     use super::Address;
+    use crate::types::num::{H256, U256};
+    use crate::utils::keccak256;
+
+    #[test]
+    fn checksum() -> Result<(), Box<dyn Error>> {
+        for address in [
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+            "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+        ] {
+            assert_eq!(Address::from_str(address)?.to_checksum(), address);
+        }
+
+        Ok(())
+    }
 
     #[test]
     fn test_public_key_to_address() -> Result<(), Box<dyn Error>> {
@@ -68,4 +162,80 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn test_create_address() -> Result<(), Box<dyn Error>> {
+        let sender = Address::from_str("0x6ac7ea33f8831ea9dcc53393aaa88b25a785dbf0")?;
+        let expected = [
+            "0xcd234a471b72ba2f1ccf0a70fcaba648a5eecd8d",
+            "0x343c43a37d37dff08ae8c4a11544c718abb4fcf8",
+            "0xf778b86fa74e846c4f0a1fbd1335fe81c00a0c91",
+            "0xfffd933a0bc612844eaf0c6fe3e5b8e9b6c1d19c",
+        ];
+
+        for (nonce, addr) in expected.iter().enumerate() {
+            let created = Address::from_create(&sender, &U256::from(nonce as u64));
+            assert_eq!(&format!("{created}"), addr);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_create2_address() -> Result<(), Box<dyn Error>> {
+        // Examples from EIP-1014
+        let vectors = [
+            (
+                "0x0000000000000000000000000000000000000000",
+                "0000000000000000000000000000000000000000000000000000000000000000",
+                "00",
+                "0x4d1a2e2bb4f88f0250f26ffff098b0b30b26bf38",
+            ),
+            (
+                "0xdeadbeef00000000000000000000000000000000",
+                "0000000000000000000000000000000000000000000000000000000000000000",
+                "00",
+                "0xb928f69bb1d91cd65274e3c79d8986362984fda3",
+            ),
+            (
+                "0xdeadbeef00000000000000000000000000000000",
+                "000000000000000000000000feed000000000000000000000000000000000000",
+                "00",
+                "0xd04116cdd17bebe565eb2422f2497e06cc1c9833",
+            ),
+            (
+                "0x0000000000000000000000000000000000000000",
+                "0000000000000000000000000000000000000000000000000000000000000000",
+                "deadbeef",
+                "0x70f2b2914a2a4b783faefb75f459a580616fcb5e",
+            ),
+            (
+                "0x00000000000000000000000000000000deadbeef",
+                "00000000000000000000000000000000000000000000000000000000cafebabe",
+                "deadbeef",
+                "0x60f3f640a8508fc6a86d45df051962668e1e8ac7",
+            ),
+            (
+                "0x0000000000000000000000000000000000000000",
+                "0000000000000000000000000000000000000000000000000000000000000000",
+                "",
+                "0xe33c0c7f7df4809055c3eba6c09cfe4baf1bd9e0",
+            ),
+        ];
+
+        for (deployer, salt, init_code, addr) in vectors {
+            let deployer = Address::from_str(deployer)?;
+
+            let mut buf = [0u8; 32];
+            hex::decode_to_slice(salt, &mut buf)?;
+            let salt = H256(buf);
+
+            let init_code = hex::decode(init_code)?;
+            let created = Address::from_create2(&deployer, &salt, &keccak256(&[&init_code]));
+
+            assert_eq!(&format!("{created}"), addr);
+        }
+
+        Ok(())
+    }
 }
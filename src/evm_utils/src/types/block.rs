@@ -0,0 +1,217 @@
+use std::error::Error;
+
+use ic_cdk::export::candid::{CandidType, Deserialize};
+
+use super::{
+    errors::BlockError,
+    header::Header,
+    num::U256,
+    transaction::{ParsedTransaction, Transaction},
+    withdrawal::Withdrawal,
+};
+use crate::{tree::ordered_trie_root, utils::keccak256};
+
+/// Transaction included in a block, types not supported by `Transaction` are returned only with their hash
+#[derive(CandidType, Deserialize, Clone, PartialEq, Eq)]
+pub enum BlockTransaction {
+    Parsed(Box<ParsedTransaction>),
+    Unsupported { tx_type: u8, hash: U256 },
+}
+
+#[derive(CandidType, Deserialize, Clone, PartialEq, Eq)]
+pub struct Block {
+    pub hash: U256,
+    pub header: Header,
+    pub transactions: Vec<BlockTransaction>,
+    pub ommers: Vec<Header>,
+    /// Present since Shanghai
+    pub withdrawals: Option<Vec<Withdrawal>>,
+}
+
+impl Block {
+    /// Decodes rlp encoded block, verifies transactions root, ommers hash and withdrawals root
+    pub fn decode(raw: &[u8]) -> Result<Block, Box<dyn Error>> {
+        let rlp = rlp::Rlp::new(raw);
+        let item_count = rlp.item_count()?;
+        if item_count != 3 && item_count != 4 {
+            return Err(Box::new(BlockError::InvalidBody));
+        }
+
+        let header_rlp = rlp.at(0)?;
+        let header: Header = header_rlp.as_val()?;
+
+        // legacy transactions are embedded as lists, typed ones as byte strings
+        let mut raw_transactions = vec![];
+        for item in rlp.at(1)?.iter() {
+            if item.is_list() {
+                raw_transactions.push(item.as_raw().to_vec());
+            } else {
+                raw_transactions.push(item.data()?.to_vec());
+            }
+        }
+
+        let transactions_root = ordered_trie_root(&raw_transactions)?;
+        if transactions_root.0 != header.transactions_root.0 {
+            return Err(Box::new(BlockError::TransactionsRootMismatch));
+        }
+
+        let ommers_rlp = rlp.at(2)?;
+        if keccak256(&[ommers_rlp.as_raw()]).0 != header.ommers_hash.0 {
+            return Err(Box::new(BlockError::OmmersHashMismatch));
+        }
+
+        let withdrawals: Option<Vec<Withdrawal>> = if item_count == 4 {
+            Some(rlp.list_at(3)?)
+        } else {
+            None
+        };
+
+        let withdrawals_root = match &withdrawals {
+            Some(items) => {
+                let values: Vec<Vec<u8>> = items.iter().map(|x| rlp::encode(x).to_vec()).collect();
+                Some(ordered_trie_root(&values)?.0)
+            }
+            None => None,
+        };
+        if withdrawals_root != header.withdrawals_root.as_ref().map(|x| x.0) {
+            return Err(Box::new(BlockError::WithdrawalsRootMismatch));
+        }
+
+        let mut transactions = vec![];
+        for item in raw_transactions {
+            let tx = match Transaction::envelope_type(&item) {
+                Ok(_) => BlockTransaction::Parsed(Box::new(ParsedTransaction::decode(&item)?)),
+                Err(_) => BlockTransaction::Unsupported {
+                    tx_type: item.first().cloned().unwrap_or_default(),
+                    hash: keccak256(&[&item]),
+                },
+            };
+            transactions.push(tx);
+        }
+
+        Ok(Self {
+            hash: keccak256(&[header_rlp.as_raw()]),
+            header,
+            transactions,
+            ommers: ommers_rlp.as_list()?,
+            withdrawals,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::error::Error;
+
+    use rlp::RlpStream;
+
+    use super::{Block, BlockTransaction};
+    use crate::{
+        tree::ordered_trie_root,
+        types::{
+            address::Address,
+            header::{test::genesis, Header},
+            num::{H256, U256},
+            withdrawal::Withdrawal,
+        },
+        utils::keccak256,
+    };
+
+    const LEGACY_TX: &str = "f86e8302511e85036e1d083a826b6c948f2d10257ebf6386426456de1b1792b507426548875319b3e6ceb7bf8025a06716fc3c5bebebe88e61bc25714647b262904f7c99bd69c25541c7a796a9727fa071908b9fc3ce08f164cf1844ce43864a9347b7820a8921eef7aa67c55399e0be";
+    const EIP1559_TX: &str = "02f8b10108840f7f4900850519f0118083055845943fe65692bfcd0e6cf84cb1e7d24108e434a7587e80b8447050ccd90000000000000000000000005b1578681d43931030fffe066a072133842dde430000000000000000000000000000000000000000000000000000000000000001c001a0cac74d8e73874331e4ba78809a7b30574c76d35c43ea00983c76752b65a0632aa04b6caeac4c693f082b8bd4d2ad349848bc71b9d32a419e33a5a303a3b3f4e8c8";
+    const BLOB_TX: &str = "03c0";
+
+    fn encode_block(
+        header: &Header,
+        transactions: &[Vec<u8>],
+        withdrawals: &[Withdrawal],
+    ) -> Vec<u8> {
+        let mut rlp = RlpStream::new_list(4);
+        rlp.append(header);
+
+        rlp.begin_list(transactions.len());
+        for tx in transactions {
+            if tx[0] > 0x7f {
+                rlp.append_raw(tx, 1);
+            } else {
+                rlp.append(tx);
+            }
+        }
+
+        rlp.begin_list(0);
+        rlp.append_list(withdrawals);
+
+        rlp.out().to_vec()
+    }
+
+    fn block() -> (Header, Vec<Vec<u8>>, Vec<Withdrawal>) {
+        let transactions = vec![
+            hex::decode(LEGACY_TX).unwrap(),
+            hex::decode(EIP1559_TX).unwrap(),
+            hex::decode(BLOB_TX).unwrap(),
+        ];
+        let withdrawals = vec![Withdrawal {
+            index: 1,
+            validator_index: 2,
+            address: Address([3u8; 20]),
+            amount: U256::from(4u64),
+        }];
+
+        let mut header = genesis();
+        header.transactions_root = H256(ordered_trie_root(&transactions).unwrap().0);
+        header.base_fee_per_gas = Some(U256::from(7u64));
+        header.withdrawals_root = Some(H256(
+            ordered_trie_root(&[rlp::encode(&withdrawals[0]).to_vec()])
+                .unwrap()
+                .0,
+        ));
+
+        (header, transactions, withdrawals)
+    }
+
+    #[test]
+    fn decode_block() -> Result<(), Box<dyn Error>> {
+        let (header, transactions, withdrawals) = block();
+        let raw = encode_block(&header, &transactions, &withdrawals);
+
+        let block = Block::decode(&raw)?;
+
+        assert_eq!(block.hash, keccak256(&[&rlp::encode(&header)]));
+        assert_eq!(block.transactions.len(), 3);
+        assert_eq!(block.withdrawals.map(|x| x.len()), Some(1));
+
+        match &block.transactions[0] {
+            BlockTransaction::Parsed(x) => assert_eq!(
+                format!("{}", x.from.clone().unwrap()),
+                "0x690b9a9e9aa1c9db991c7721a92d351db4fac990"
+            ),
+            _ => panic!("Transaction should be parsed"),
+        }
+        match &block.transactions[1] {
+            BlockTransaction::Parsed(x) => assert_eq!(
+                format!("{}", x.from.clone().unwrap()),
+                "0x5b1578681d43931030fffe066a072133842dde43"
+            ),
+            _ => panic!("Transaction should be parsed"),
+        }
+        match &block.transactions[2] {
+            BlockTransaction::Unsupported { tx_type, .. } => assert_eq!(*tx_type, 3),
+            _ => panic!("Blob transaction is not supported"),
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn decode_block_invalid_roots() -> Result<(), Box<dyn Error>> {
+        let (header, transactions, withdrawals) = block();
+
+        let raw = encode_block(&header, &transactions[..2], &withdrawals);
+        assert!(Block::decode(&raw).is_err());
+
+        let raw = encode_block(&header, &transactions, &[]);
+        assert!(Block::decode(&raw).is_err());
+
+        Ok(())
+    }
+}
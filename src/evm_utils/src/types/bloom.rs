@@ -0,0 +1,126 @@
+use std::error::Error;
+
+use rlp::DecoderError;
+
+use super::log::Log;
+use crate::utils::keccak256;
+
+pub const BLOOM_SIZE: usize = 256;
+
+/// 2048 bit bloom filter of log addresses and topics, as used in receipts and block headers
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Bloom(pub [u8; BLOOM_SIZE]);
+
+impl Default for Bloom {
+    fn default() -> Self {
+        Self([0u8; BLOOM_SIZE])
+    }
+}
+
+impl Bloom {
+    pub fn from_slice(data: &[u8]) -> Result<Self, Box<dyn Error>> {
+        if data.len() != BLOOM_SIZE {
+            return Err(Box::new(DecoderError::Custom(
+                "Invalid number of bytes for bloom",
+            )));
+        }
+
+        let mut buf = [0u8; BLOOM_SIZE];
+        buf.copy_from_slice(data);
+        Ok(Self(buf))
+    }
+
+    pub fn from_logs(logs: &[Log]) -> Self {
+        let mut bloom = Self::default();
+        for log in logs {
+            bloom.accrue_log(log);
+        }
+
+        bloom
+    }
+
+    /// Returns byte index and mask of the three bits set by given value
+    fn bits(data: &[u8]) -> [(usize, u8); 3] {
+        let hash = keccak256(&[data]).0;
+
+        let mut bits = [(0usize, 0u8); 3];
+        for (i, bit) in bits.iter_mut().enumerate() {
+            let index = ((hash[2 * i] as usize) << 8 | hash[2 * i + 1] as usize) & 2047;
+            *bit = (BLOOM_SIZE - 1 - index / 8, 1u8 << (index % 8));
+        }
+
+        bits
+    }
+
+    pub fn accrue(&mut self, data: &[u8]) {
+        for (byte, mask) in Self::bits(data) {
+            self.0[byte] |= mask;
+        }
+    }
+
+    pub fn accrue_log(&mut self, log: &Log) {
+        self.accrue(&log.address.0);
+        for topic in &log.topics {
+            self.accrue(&topic.0);
+        }
+    }
+
+    pub fn accrue_bloom(&mut self, other: &Bloom) {
+        for (a, b) in self.0.iter_mut().zip(other.0.iter()) {
+            *a |= b;
+        }
+    }
+
+    /// Checks if value may be in the bloom, false positives are possible
+    pub fn contains(&self, data: &[u8]) -> bool {
+        Self::bits(data)
+            .iter()
+            .all(|(byte, mask)| self.0[*byte] & mask == *mask)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Bloom;
+    use crate::utils::keccak256;
+
+    #[test]
+    fn bloom_contains() {
+        let mut bloom = Bloom::default();
+        for data in ["testtest", "test", "hallo", "other"] {
+            bloom.accrue(data.as_bytes());
+        }
+
+        for data in ["testtest", "test", "hallo", "other"] {
+            assert!(bloom.contains(data.as_bytes()));
+        }
+        assert!(!bloom.contains("tester".as_bytes()));
+    }
+
+    #[test]
+    fn bloom_extensively() {
+        // Vector from go-ethereum bloom tests
+        let mut bloom = Bloom::default();
+        for i in 0..100 {
+            bloom.accrue(format!("xxxxxxxxxx data {i} yyyyyyyyyyyyyy").as_bytes());
+        }
+
+        assert_eq!(
+            format!("{}", keccak256(&[&bloom.0])),
+            "0xc8d3ca65cdb4874300a9e39475508f23ed6da09fdbc487f89a2dcf50b09eb263"
+        );
+    }
+
+    #[test]
+    fn bloom_merge() {
+        let mut a = Bloom::default();
+        a.accrue("first".as_bytes());
+
+        let mut b = Bloom::default();
+        b.accrue("second".as_bytes());
+
+        a.accrue_bloom(&b);
+        assert!(a.contains("first".as_bytes()));
+        assert!(a.contains("second".as_bytes()));
+    }
+}
@@ -0,0 +1,313 @@
+use std::error::Error;
+
+use ic_cdk::export::candid::{CandidType, Deserialize};
+
+use super::{
+    access_list::AccessList,
+    address::Address,
+    errors::TransactionError,
+    fee::BlobSchedule,
+    num::U256,
+    transaction::Transaction,
+    transaction_1559::Transaction1559,
+    transaction_2930::Transaction2930,
+    transaction_legacy::TransactionLegacy,
+    validation::{self, ValidationRules, Violation},
+};
+
+/// EVM compatible network and the transaction types it accepts
+#[derive(CandidType, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct Chain {
+    pub chain_id: u64,
+    pub name: String,
+    pub native_currency_decimals: u8,
+    /// Supported transaction types, 0 for legacy transactions
+    pub tx_types: Vec<u8>,
+    /// Chain rejects legacy transactions that are not replay protected
+    pub eip155_required: bool,
+    /// Blob parameters of the current fork, has to be updated when a fork changes them
+    pub blob_schedule: Option<BlobSchedule>,
+}
+
+/// Transaction fields that do not depend on the network, fee fields decide which
+/// transaction type is built
+#[derive(CandidType, Deserialize, Clone, PartialEq, Eq)]
+pub struct TransactionRequest {
+    pub nonce: U256,
+    pub to: Option<Address>,
+    pub value: U256,
+    pub data: Vec<u8>,
+    pub gas_limit: U256,
+    pub gas_price: Option<U256>,
+    pub max_fee_per_gas: Option<U256>,
+    pub max_priority_fee_per_gas: Option<U256>,
+    pub access_list: Vec<AccessList>,
+}
+
+impl Chain {
+    pub fn new(chain_id: u64, name: &str, tx_types: &[u8]) -> Self {
+        Self {
+            chain_id,
+            name: name.to_string(),
+            native_currency_decimals: 18,
+            tx_types: tx_types.to_vec(),
+            eip155_required: true,
+            blob_schedule: None,
+        }
+    }
+
+    pub fn supports(&self, tx_type: u8) -> bool {
+        self.tx_types.contains(&tx_type)
+    }
+
+    pub fn validation_rules(&self) -> ValidationRules {
+        ValidationRules {
+            chain_id: Some(self.chain_id),
+            max_init_code_size: None,
+            eip155_required: self.eip155_required,
+        }
+    }
+
+    /// Checks transaction against rules of this chain, including its type
+    pub fn validate(&self, tx: &Transaction) -> Vec<Violation> {
+        let mut violations = vec![];
+        if !self.supports(tx.tx_type()) {
+            violations.push(Violation::UnsupportedType {
+                tx_type: tx.tx_type(),
+            });
+        }
+
+        violations.extend(validation::validate(tx, &self.validation_rules()));
+        violations
+    }
+
+    /// Builds transaction for this chain. EIP1559 transaction is built when max fee is provided,
+    /// otherwise EIP2930 when access list is not empty, or Legacy. When the chain does not
+    /// support that type, another supported type is built: gas price of Legacy and EIP2930
+    /// transactions defaults to max fee, both fees of EIP1559 transaction default to gas price.
+    /// Fails when priority fee exceeds max fee
+    pub fn build_transaction(
+        &self,
+        req: TransactionRequest,
+    ) -> Result<Transaction, Box<dyn Error>> {
+        let preferred: &[u8] = match (&req.max_fee_per_gas, req.access_list.is_empty()) {
+            (Some(_), false) => &[2, 1, 0],
+            (Some(_), true) => &[2, 0, 1],
+            (None, false) => &[1, 2, 0],
+            (None, true) => &[0, 1, 2],
+        };
+
+        let tx_type = preferred
+            .iter()
+            .find(|x| self.supports(**x))
+            .ok_or(TransactionError::UnsupportedType)?;
+
+        let gas_price = || {
+            req.gas_price
+                .clone()
+                .or_else(|| req.max_fee_per_gas.clone())
+                .ok_or(TransactionError::MissingFee)
+        };
+
+        let tx = match tx_type {
+            2 => {
+                let max_fee_per_gas = req
+                    .max_fee_per_gas
+                    .clone()
+                    .or_else(|| req.gas_price.clone())
+                    .ok_or(TransactionError::MissingFee)?;
+                let max_priority_fee_per_gas = req
+                    .max_priority_fee_per_gas
+                    .clone()
+                    .or_else(|| req.gas_price.clone())
+                    .ok_or(TransactionError::MissingFee)?;
+                if max_priority_fee_per_gas > max_fee_per_gas {
+                    return Err(Box::new(TransactionError::PriorityFeeAboveMaxFee));
+                }
+
+                Transaction::EIP1559(Transaction1559 {
+                    chain_id: self.chain_id,
+                    nonce: req.nonce,
+                    max_priority_fee_per_gas,
+                    max_fee_per_gas,
+                    gas_limit: req.gas_limit,
+                    to: req.to,
+                    value: req.value,
+                    data: req.data,
+                    access_list: req.access_list,
+                    sign: None,
+                })
+            }
+            1 => Transaction::EIP2930(Transaction2930 {
+                chain_id: self.chain_id,
+                nonce: req.nonce,
+                gas_price: gas_price()?,
+                gas_limit: req.gas_limit,
+                to: req.to,
+                value: req.value,
+                data: req.data,
+                access_list: req.access_list,
+                sign: None,
+            }),
+            _ => Transaction::Legacy(TransactionLegacy {
+                chain_id: self.chain_id,
+                nonce: req.nonce,
+                gas_price: gas_price()?,
+                gas_limit: req.gas_limit,
+                to: req.to,
+                value: req.value,
+                data: req.data,
+                sign: None,
+            }),
+        };
+
+        Ok(tx)
+    }
+}
+
+/// Chains known to the canister without any configuration
+pub fn builtin_chains() -> Vec<Chain> {
+    vec![
+        Chain {
+            blob_schedule: Some(BlobSchedule::BPO2),
+            ..Chain::new(1, "Ethereum Mainnet", &[0, 1, 2])
+        },
+        Chain {
+            blob_schedule: Some(BlobSchedule::BPO2),
+            ..Chain::new(11155111, "Sepolia", &[0, 1, 2])
+        },
+        Chain {
+            blob_schedule: Some(BlobSchedule::BPO2),
+            ..Chain::new(17000, "Holesky", &[0, 1, 2])
+        },
+        Chain::new(10, "OP Mainnet", &[0, 1, 2]),
+        Chain::new(56, "BNB Smart Chain", &[0, 1, 2]),
+        Chain::new(137, "Polygon PoS", &[0, 1, 2]),
+        Chain::new(8453, "Base", &[0, 1, 2]),
+        Chain::new(42161, "Arbitrum One", &[0, 1, 2]),
+        Chain::new(43114, "Avalanche C-Chain", &[0, 1, 2]),
+    ]
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Chain, TransactionRequest};
+    use crate::types::{
+        access_list::AccessList, address::Address, num::U256, transaction::Transaction,
+        validation::Violation,
+    };
+
+    fn request() -> TransactionRequest {
+        TransactionRequest {
+            nonce: U256::from(1u64),
+            to: Some(Address([1u8; 20])),
+            value: U256::zero(),
+            data: vec![],
+            gas_limit: U256::from(21_000u64),
+            gas_price: None,
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+            access_list: vec![],
+        }
+    }
+
+    #[test]
+    fn build_transaction_picks_type() {
+        let chain = Chain::new(1, "Ethereum Mainnet", &[0, 1, 2]);
+
+        let mut req = request();
+        req.max_fee_per_gas = Some(U256::from(10u64));
+        req.max_priority_fee_per_gas = Some(U256::from(1u64));
+        let tx = chain.build_transaction(req).unwrap();
+        assert_eq!(tx.tx_type(), 2);
+        assert_eq!(tx.chain_id(), 1);
+
+        let mut req = request();
+        req.gas_price = Some(U256::from(10u64));
+        req.access_list = vec![AccessList {
+            address: Address([2u8; 20]),
+            storage_keys: vec![],
+        }];
+        assert_eq!(chain.build_transaction(req).unwrap().tx_type(), 1);
+
+        let mut req = request();
+        req.gas_price = Some(U256::from(10u64));
+        assert!(matches!(
+            chain.build_transaction(req),
+            Ok(Transaction::Legacy(_))
+        ));
+
+        assert!(chain.build_transaction(request()).is_err());
+    }
+
+    #[test]
+    fn build_transaction_unsupported_type() {
+        let chain = Chain::new(1337, "Legacy only", &[0]);
+
+        let mut req = request();
+        req.max_fee_per_gas = Some(U256::from(10u64));
+        req.max_priority_fee_per_gas = Some(U256::from(1u64));
+
+        // falls back to legacy transaction paying max fee
+        match chain.build_transaction(req.clone()) {
+            Ok(Transaction::Legacy(x)) => assert_eq!(x.gas_price, U256::from(10u64)),
+            _ => panic!("Expected legacy transaction"),
+        }
+
+        let chain = Chain::new(1338, "EIP1559 only", &[2]);
+        let mut legacy = request();
+        legacy.gas_price = Some(U256::from(7u64));
+        match chain.build_transaction(legacy) {
+            Ok(Transaction::EIP1559(x)) => {
+                assert_eq!(x.max_fee_per_gas, U256::from(7u64));
+                assert_eq!(x.max_priority_fee_per_gas, U256::from(7u64));
+            }
+            _ => panic!("Expected EIP1559 transaction"),
+        }
+
+        let chain = Chain::new(1339, "No transactions", &[]);
+        assert!(chain.build_transaction(req).is_err());
+    }
+
+    #[test]
+    fn build_transaction_fee_precedence() {
+        let chain = Chain::new(1, "Ethereum Mainnet", &[0, 1, 2]);
+
+        let mut req = request();
+        req.gas_price = Some(U256::from(5u64));
+        req.max_fee_per_gas = Some(U256::from(10u64));
+        req.max_priority_fee_per_gas = Some(U256::from(1u64));
+
+        // max fee is kept for EIP1559 transaction, gas price for Legacy one
+        match chain.build_transaction(req.clone()) {
+            Ok(Transaction::EIP1559(x)) => assert_eq!(x.max_fee_per_gas, U256::from(10u64)),
+            _ => panic!("Expected EIP1559 transaction"),
+        }
+        match Chain::new(1337, "Legacy only", &[0]).build_transaction(req.clone()) {
+            Ok(Transaction::Legacy(x)) => assert_eq!(x.gas_price, U256::from(5u64)),
+            _ => panic!("Expected legacy transaction"),
+        }
+
+        // priority fee falls back to gas price above max fee
+        req.max_priority_fee_per_gas = None;
+        req.gas_price = Some(U256::from(11u64));
+        assert!(chain.build_transaction(req).is_err());
+    }
+
+    #[test]
+    fn validate_unsupported_type() {
+        let chain = Chain::new(1, "Legacy only", &[0]);
+
+        let mut req = request();
+        req.max_fee_per_gas = Some(U256::from(10u64));
+        req.max_priority_fee_per_gas = Some(U256::from(1u64));
+        let tx = Chain::new(1, "Ethereum Mainnet", &[0, 1, 2])
+            .build_transaction(req)
+            .unwrap();
+
+        assert_eq!(
+            chain.validate(&tx),
+            vec![Violation::UnsupportedType { tx_type: 2 }]
+        );
+    }
+}
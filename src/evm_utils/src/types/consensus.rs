@@ -0,0 +1,346 @@
+use std::{error::Error, future::Future, pin::Pin};
+
+use ic_cdk::api::management_canister::http_request::{
+    CanisterHttpRequestArgument, HttpHeader, HttpMethod, HttpResponse, TransformContext,
+};
+use ic_cdk::export::candid::{CandidType, Deserialize};
+use serde_json::Value;
+
+use super::{
+    errors::RpcError,
+    rpc::{RpcRequest, RpcResponse},
+};
+
+/// Size of a response if provider does not set it, same as the IC default
+const DEFAULT_MAX_RESPONSE_BYTES: u64 = 2 * 1024 * 1024;
+
+/// JSON-RPC endpoint, url may contain an API key
+#[derive(CandidType, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct RpcProvider {
+    pub name: String,
+    pub url: String,
+}
+
+/// Providers of a chain and rules for reaching consensus between them
+#[derive(CandidType, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct RpcConfig {
+    pub chain_id: u64,
+    pub providers: Vec<RpcProvider>,
+    /// Number of providers that must return the same response
+    pub quorum: u32,
+    /// Limit of a single response size, outcalls are charged for it even if response is smaller
+    pub max_response_bytes: Option<u64>,
+    /// Maximal amount of cycles spent on outcalls of a single call
+    pub max_cycles: u128,
+}
+
+/// Layer performing HTTPS outcalls, allows replacing management canister in tests
+pub trait Outcall {
+    fn http_request(
+        &self,
+        arg: CanisterHttpRequestArgument,
+        cycles: u128,
+    ) -> Pin<Box<dyn Future<Output = Result<HttpResponse, String>> + '_>>;
+}
+
+/// Returns cycles required by the management canister for an outcall
+pub fn outcall_cycles(arg: &CanisterHttpRequestArgument) -> u128 {
+    let max_response_bytes = arg.max_response_bytes.unwrap_or(DEFAULT_MAX_RESPONSE_BYTES);
+    let arg_raw = candid::utils::encode_args((arg,)).map_or(0, |x| x.len());
+
+    // 12 is the length of "http_request" method name
+    400_000_000u128 + 100_000u128 * (arg_raw as u128 + 12 + max_response_bytes as u128)
+}
+
+impl RpcConfig {
+    /// Checks that quorum can be reached with configured providers
+    pub fn validate(&self) -> Result<(), RpcError> {
+        if self.quorum == 0 {
+            return Err(RpcError::InvalidConfig("Quorum must be greater than zero"));
+        }
+        if self.quorum as usize > self.providers.len() {
+            return Err(RpcError::InvalidConfig(
+                "Quorum is greater than number of providers",
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Builds outcall arguments for each provider, fails if they exceed cycles budget
+    pub fn outcalls(
+        &self,
+        request: &RpcRequest,
+        transform: Option<TransformContext>,
+    ) -> Result<Vec<(CanisterHttpRequestArgument, u128)>, RpcError> {
+        self.validate()?;
+
+        let body = request.to_json(1);
+        let args: Vec<(CanisterHttpRequestArgument, u128)> = self
+            .providers
+            .iter()
+            .map(|provider| {
+                let arg = CanisterHttpRequestArgument {
+                    url: provider.url.clone(),
+                    max_response_bytes: self.max_response_bytes,
+                    method: HttpMethod::POST,
+                    headers: vec![HttpHeader {
+                        name: "Content-Type".to_string(),
+                        value: "application/json".to_string(),
+                    }],
+                    body: Some(body.clone()),
+                    transform: transform.clone(),
+                };
+                let cycles = outcall_cycles(&arg);
+
+                (arg, cycles)
+            })
+            .collect();
+
+        let required = args.iter().map(|(_, cycles)| cycles).sum();
+        if required > self.max_cycles {
+            return Err(RpcError::CyclesBudgetExceeded {
+                required,
+                budget: self.max_cycles,
+            });
+        }
+
+        Ok(args)
+    }
+
+    /// Sends request to providers one by one until quorum of them returns the same response
+    pub async fn call(
+        &self,
+        request: &RpcRequest,
+        transform: Option<TransformContext>,
+        outcall: &impl Outcall,
+    ) -> Result<RpcResponse, Box<dyn Error>> {
+        let mut responses = vec![];
+        for (arg, cycles) in self.outcalls(request, transform)? {
+            let response = outcall
+                .http_request(arg, cycles)
+                .await
+                .ok()
+                .filter(|x| x.status == 200);
+            responses.push(response.map(|x| x.body));
+
+            // remaining providers are not called once quorum is reached
+            if consensus(&responses, self.quorum).is_ok() {
+                break;
+            }
+        }
+
+        let body = consensus(&responses, self.quorum)?;
+        request.parse_response(&body)
+    }
+}
+
+/// Removes fields that differ between providers, like request id, and formatting
+fn normalize(body: &[u8]) -> Option<String> {
+    let mut body: Value = serde_json::from_slice(body).ok()?;
+    let body = body.as_object_mut()?;
+    body.remove("id");
+    body.remove("jsonrpc");
+
+    Some(Value::Object(body.clone()).to_string())
+}
+
+/// Returns body of a response returned by at least `quorum` providers, failed outcalls are ignored.
+/// Errors returned by nodes take part in voting, so agreed error is passed to the caller
+pub fn consensus(responses: &[Option<Vec<u8>>], quorum: u32) -> Result<Vec<u8>, RpcError> {
+    let mut groups: Vec<(String, &Vec<u8>, u32)> = vec![];
+    for body in responses.iter().flatten() {
+        let key = match normalize(body) {
+            Some(x) => x,
+            None => continue,
+        };
+
+        match groups.iter_mut().find(|(k, _, _)| *k == key) {
+            Some((_, _, count)) => *count += 1,
+            None => groups.push((key, body, 1)),
+        }
+    }
+
+    match groups.into_iter().max_by_key(|(_, _, count)| *count) {
+        Some((_, body, count)) if count >= quorum => Ok(body.clone()),
+        best => Err(RpcError::NoConsensus {
+            agreed: best.map_or(0, |(_, _, count)| count),
+            quorum,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        cell::RefCell,
+        error::Error,
+        future::{self, Future},
+        pin::{pin, Pin},
+        task::{Context, Poll, Waker},
+    };
+
+    use candid::Nat;
+    use ic_cdk::api::management_canister::http_request::{
+        CanisterHttpRequestArgument, HttpResponse,
+    };
+
+    use super::{consensus, Outcall, RpcConfig, RpcProvider};
+    use crate::types::{
+        address::Address,
+        errors::RpcError,
+        num::U256,
+        rpc::{BlockTag, RpcRequest, RpcResponse},
+    };
+
+    /// Returns responses by provider url and remembers which providers were called
+    struct MockOutcall {
+        responses: Vec<(&'static str, Result<&'static str, String>)>,
+        called: RefCell<Vec<String>>,
+    }
+
+    impl Outcall for MockOutcall {
+        fn http_request(
+            &self,
+            arg: CanisterHttpRequestArgument,
+            _cycles: u128,
+        ) -> Pin<Box<dyn Future<Output = Result<HttpResponse, String>> + '_>> {
+            self.called.borrow_mut().push(arg.url.clone());
+
+            let (_, response) = self
+                .responses
+                .iter()
+                .find(|(url, _)| *url == arg.url)
+                .unwrap();
+            let response = response.clone().map(|body| HttpResponse {
+                status: Nat::from(200),
+                headers: vec![],
+                body: body.as_bytes().to_vec(),
+            });
+
+            Box::pin(future::ready(response))
+        }
+    }
+
+    fn block_on<F: Future>(f: F) -> F::Output {
+        let mut f = pin!(f);
+        let mut cx = Context::from_waker(Waker::noop());
+        loop {
+            if let Poll::Ready(x) = f.as_mut().poll(&mut cx) {
+                return x;
+            }
+        }
+    }
+
+    fn config(urls: &[&str], quorum: u32) -> RpcConfig {
+        RpcConfig {
+            chain_id: 1,
+            providers: urls
+                .iter()
+                .map(|url| RpcProvider {
+                    name: url.to_string(),
+                    url: url.to_string(),
+                })
+                .collect(),
+            quorum,
+            max_response_bytes: Some(2048),
+            max_cycles: 10_000_000_000,
+        }
+    }
+
+    fn balance_request() -> RpcRequest {
+        RpcRequest::GetBalance {
+            address: Address([1u8; 20]),
+            block: BlockTag::Latest,
+        }
+    }
+
+    #[test]
+    fn consensus_ignores_id_and_formatting() {
+        let responses = vec![
+            Some(br#"{"jsonrpc":"2.0","id":1,"result":"0x10"}"#.to_vec()),
+            None,
+            Some(br#"{ "result": "0x10", "id": 7 }"#.to_vec()),
+            Some(br#"{"jsonrpc":"2.0","id":1,"result":"0x11"}"#.to_vec()),
+        ];
+
+        assert!(consensus(&responses, 2).is_ok());
+        match consensus(&responses, 3) {
+            Err(RpcError::NoConsensus { agreed, quorum }) => assert_eq!((agreed, quorum), (2, 3)),
+            _ => panic!("Consensus should not be reached"),
+        }
+    }
+
+    #[test]
+    fn call_reaches_quorum() -> Result<(), Box<dyn Error>> {
+        let outcall = MockOutcall {
+            responses: vec![
+                ("https://a", Err("Timeout".to_string())),
+                (
+                    "https://b",
+                    Ok(r#"{"jsonrpc":"2.0","id":1,"result":"0x10"}"#),
+                ),
+                (
+                    "https://c",
+                    Ok(r#"{"id":1,"result":"0x10","jsonrpc":"2.0"}"#),
+                ),
+                (
+                    "https://d",
+                    Ok(r#"{"jsonrpc":"2.0","id":1,"result":"0x11"}"#),
+                ),
+            ],
+            called: RefCell::default(),
+        };
+
+        let config = config(&["https://a", "https://b", "https://c", "https://d"], 2);
+        match block_on(config.call(&balance_request(), None, &outcall))? {
+            RpcResponse::Balance(x) => assert_eq!(x, U256::from(16u64)),
+            _ => panic!("Wrong response type"),
+        }
+
+        // last provider is not needed once quorum is reached
+        assert_eq!(outcall.called.borrow().len(), 3);
+
+        Ok(())
+    }
+
+    #[test]
+    fn call_without_quorum_fails() {
+        let outcall = MockOutcall {
+            responses: vec![
+                (
+                    "https://a",
+                    Ok(r#"{"jsonrpc":"2.0","id":1,"result":"0x10"}"#),
+                ),
+                (
+                    "https://b",
+                    Ok(r#"{"jsonrpc":"2.0","id":1,"result":"0x11"}"#),
+                ),
+                ("https://c", Err("Timeout".to_string())),
+            ],
+            called: RefCell::default(),
+        };
+
+        let config = config(&["https://a", "https://b", "https://c"], 2);
+        let err = block_on(config.call(&balance_request(), None, &outcall)).err();
+
+        assert!(format!("{}", err.unwrap()).starts_with("NoConsensus"));
+        assert_eq!(outcall.called.borrow().len(), 3);
+    }
+
+    #[test]
+    fn cycles_budget() {
+        let mut config = config(&["https://a", "https://b"], 2);
+        let outcalls = config.outcalls(&balance_request(), None).unwrap();
+        let required: u128 = outcalls.iter().map(|(_, cycles)| cycles).sum();
+
+        config.max_cycles = required - 1;
+        match config.outcalls(&balance_request(), None) {
+            Err(RpcError::CyclesBudgetExceeded { required: x, .. }) => assert_eq!(x, required),
+            _ => panic!("Budget should be exceeded"),
+        }
+
+        config.quorum = 3;
+        assert!(config.validate().is_err());
+    }
+}
@@ -0,0 +1,93 @@
+use ic_cdk::export::candid::{CandidType, Deserialize};
+
+use super::{
+    abi::{self, AbiValue},
+    address::Address,
+    num::{H256, U256},
+};
+use crate::utils::keccak256;
+
+/// EIP-712 domain, only fields that are set are part of the domain type
+#[derive(CandidType, Deserialize, Clone, PartialEq, Eq, Debug, Default)]
+pub struct Domain {
+    pub name: Option<String>,
+    pub version: Option<String>,
+    pub chain_id: Option<u64>,
+    pub verifying_contract: Option<Address>,
+    pub salt: Option<H256>,
+}
+
+impl Domain {
+    pub fn separator(&self) -> H256 {
+        let mut fields = vec![];
+        let mut values = vec![];
+
+        if let Some(name) = &self.name {
+            fields.push("string name");
+            values.push(AbiValue::FixedBytes(hash_str(name).0.to_vec()));
+        }
+        if let Some(version) = &self.version {
+            fields.push("string version");
+            values.push(AbiValue::FixedBytes(hash_str(version).0.to_vec()));
+        }
+        if let Some(chain_id) = self.chain_id {
+            fields.push("uint256 chainId");
+            values.push(AbiValue::Uint(U256::from(chain_id)));
+        }
+        if let Some(contract) = &self.verifying_contract {
+            fields.push("address verifyingContract");
+            values.push(AbiValue::Address(contract.clone()));
+        }
+        if let Some(salt) = &self.salt {
+            fields.push("bytes32 salt");
+            values.push(AbiValue::FixedBytes(salt.0.to_vec()));
+        }
+
+        hash_struct(&format!("EIP712Domain({})", fields.join(",")), &values)
+    }
+}
+
+fn hash_str(value: &str) -> H256 {
+    keccak256(&[value.as_bytes()]).into()
+}
+
+/// Hashes struct of static fields, members of nested structs and arrays have to be
+/// hashed already
+pub fn hash_struct(type_string: &str, values: &[AbiValue]) -> H256 {
+    let type_hash = hash_str(type_string);
+    let encoded = abi::encode(values);
+
+    keccak256(&[&type_hash.0, &encoded]).into()
+}
+
+/// Digest that is signed, keccak256(0x1901 ++ domain separator ++ struct hash)
+pub fn digest(domain: &Domain, struct_hash: &H256) -> H256 {
+    keccak256(&[&[0x19, 0x01], &domain.separator().0, &struct_hash.0]).into()
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use super::Domain;
+    use crate::types::address::Address;
+
+    #[test]
+    fn domain_separator() {
+        // Example from EIP-712
+        let domain = Domain {
+            name: Some("Ether Mail".to_string()),
+            version: Some("1".to_string()),
+            chain_id: Some(1),
+            verifying_contract: Some(
+                Address::from_str("0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC").unwrap(),
+            ),
+            salt: None,
+        };
+
+        assert_eq!(
+            hex::encode(domain.separator().0),
+            "f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f"
+        );
+    }
+}
@@ -0,0 +1,212 @@
+use ic_cdk::export::candid::{CandidType, Deserialize};
+
+use super::{
+    abi::{self, AbiValue, ParamType},
+    address::Address,
+    errors::AbiError,
+    num::H256,
+};
+use crate::utils::recover_address;
+
+pub const IS_VALID_SIGNATURE: &str = "isValidSignature(bytes32,bytes)";
+
+/// Returned by `isValidSignature` for valid signatures, equal to selector of the method
+pub const MAGIC_VALUE: [u8; 4] = [0x16, 0x26, 0xba, 0x7e];
+
+/// Suffix of ERC-6492 signatures of contracts that are not deployed yet
+pub const ERC6492_SUFFIX: [u8; 32] = [
+    0x64, 0x92, 0x64, 0x92, 0x64, 0x92, 0x64, 0x92, 0x64, 0x92, 0x64, 0x92, 0x64, 0x92, 0x64, 0x92,
+    0x64, 0x92, 0x64, 0x92, 0x64, 0x92, 0x64, 0x92, 0x64, 0x92, 0x64, 0x92, 0x64, 0x92, 0x64, 0x92,
+];
+
+/// Call of a factory deploying the signer contract
+#[derive(CandidType, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct FactoryCall {
+    pub factory: Address,
+    pub data: Vec<u8>,
+}
+
+/// ERC-6492 signature, abi.encode(factory, factoryCalldata, signature) ++ suffix
+#[derive(CandidType, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct Erc6492Signature {
+    pub deploy: FactoryCall,
+    pub signature: Vec<u8>,
+}
+
+impl Erc6492Signature {
+    /// Returns None when signature does not end with ERC-6492 suffix
+    pub fn unwrap(signature: &[u8]) -> Result<Option<Self>, AbiError> {
+        let data = match signature.strip_suffix(&ERC6492_SUFFIX) {
+            Some(data) => data,
+            None => return Ok(None),
+        };
+
+        let values = abi::decode(
+            &[ParamType::Address, ParamType::Bytes, ParamType::Bytes],
+            data,
+        )?;
+
+        match values.as_slice() {
+            [AbiValue::Address(factory), AbiValue::Bytes(data), AbiValue::Bytes(signature)] => {
+                Ok(Some(Self {
+                    deploy: FactoryCall {
+                        factory: factory.clone(),
+                        data: data.clone(),
+                    },
+                    signature: signature.clone(),
+                }))
+            }
+            _ => Err(AbiError::InvalidData),
+        }
+    }
+
+    pub fn wrap(&self) -> Vec<u8> {
+        let mut data = abi::encode(&[
+            AbiValue::Address(self.deploy.factory.clone()),
+            AbiValue::Bytes(self.deploy.data.clone()),
+            AbiValue::Bytes(self.signature.clone()),
+        ]);
+        data.extend(ERC6492_SUFFIX);
+        data
+    }
+}
+
+/// `eth_call` to be made to the signer contract, its output is checked with `is_valid_result`
+#[derive(CandidType, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct Erc1271Call {
+    pub to: Address,
+    pub data: Vec<u8>,
+    /// Has to be executed before the call when signer is not deployed yet (ERC-6492)
+    pub deploy: Option<FactoryCall>,
+}
+
+impl Erc1271Call {
+    pub fn new(signer: &Address, hash: &H256, signature: &[u8]) -> Self {
+        let mut data = abi::selector(IS_VALID_SIGNATURE).to_vec();
+        data.extend(abi::encode(&[
+            AbiValue::FixedBytes(hash.0.to_vec()),
+            AbiValue::Bytes(signature.to_vec()),
+        ]));
+
+        Self {
+            to: signer.clone(),
+            data,
+            deploy: None,
+        }
+    }
+}
+
+/// Result of verification of a signature
+#[derive(CandidType, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub enum SignatureCheck {
+    /// ECDSA signature was made by the signer
+    Ecdsa,
+    /// Signer may be a contract, verification has to be completed with `eth_call`
+    Contract(Erc1271Call),
+}
+
+impl SignatureCheck {
+    /// Verifies ECDSA signature of the hash, when it does not match the signer or signature is
+    /// wrapped with ERC-6492, returns `isValidSignature` call to be made to the signer
+    pub fn new(signer: &Address, hash: &H256, signature: &[u8]) -> Result<Self, AbiError> {
+        if let Some(wrapped) = Erc6492Signature::unwrap(signature)? {
+            let mut call = Erc1271Call::new(signer, hash, &wrapped.signature);
+            call.deploy = Some(wrapped.deploy);
+
+            return Ok(Self::Contract(call));
+        }
+
+        match recover_address(signature, &hash.0) {
+            Ok(address) if address == *signer => Ok(Self::Ecdsa),
+            _ => Ok(Self::Contract(Erc1271Call::new(signer, hash, signature))),
+        }
+    }
+}
+
+/// Checks that output of `isValidSignature` is ABI encoded magic value
+pub fn is_valid_result(output: &[u8]) -> bool {
+    output.len() == 32 && output[..4] == MAGIC_VALUE && output[4..].iter().all(|x| *x == 0)
+}
+
+#[cfg(test)]
+mod test {
+    use std::error::Error;
+
+    use secp256k1::{Message, SecretKey, SECP256K1};
+
+    use super::{
+        is_valid_result, Erc1271Call, Erc6492Signature, FactoryCall, SignatureCheck,
+        IS_VALID_SIGNATURE, MAGIC_VALUE,
+    };
+    use crate::types::{abi, address::Address, num::H256};
+
+    #[test]
+    fn magic_value() {
+        assert_eq!(abi::selector(IS_VALID_SIGNATURE), MAGIC_VALUE);
+
+        let mut output = [0u8; 32];
+        output[..4].copy_from_slice(&MAGIC_VALUE);
+        assert!(is_valid_result(&output));
+        assert!(!is_valid_result(&output[..4]));
+        assert!(!is_valid_result(&[0xff; 32]));
+    }
+
+    #[test]
+    fn call_data() {
+        let call = Erc1271Call::new(&Address([1; 20]), &H256([2; 32]), &[3; 65]);
+
+        assert_eq!(call.data[..4], MAGIC_VALUE);
+        assert_eq!(call.data[4..36], [2; 32]);
+        // offset of bytes, length and 65 bytes padded to 96
+        assert_eq!(call.data[67], 0x40);
+        assert_eq!(call.data[99], 65);
+        assert_eq!(call.data.len(), 4 + 32 * 3 + 96);
+    }
+
+    #[test]
+    fn erc6492() -> Result<(), Box<dyn Error>> {
+        let wrapped = Erc6492Signature {
+            deploy: FactoryCall {
+                factory: Address([5; 20]),
+                data: vec![1, 2, 3, 4, 5],
+            },
+            signature: vec![6; 65],
+        };
+        let data = wrapped.wrap();
+
+        assert_eq!(Erc6492Signature::unwrap(&data)?, Some(wrapped.clone()));
+        assert_eq!(Erc6492Signature::unwrap(&[6; 65])?, None);
+        assert!(Erc6492Signature::unwrap(&data[data.len() - 40..]).is_err());
+
+        let check = SignatureCheck::new(&Address([7; 20]), &H256([8; 32]), &data)?;
+        let mut call = Erc1271Call::new(&Address([7; 20]), &H256([8; 32]), &[6; 65]);
+        call.deploy = Some(wrapped.deploy);
+        assert_eq!(check, SignatureCheck::Contract(call));
+
+        Ok(())
+    }
+
+    #[test]
+    fn ecdsa() -> Result<(), Box<dyn Error>> {
+        let key = SecretKey::from_slice(&[0x46; 32])?;
+        let signer = Address::from(key.public_key(SECP256K1));
+        let hash = H256([9; 32]);
+
+        let (id, data) = SECP256K1
+            .sign_ecdsa_recoverable(&Message::from_slice(&hash.0)?, &key)
+            .serialize_compact();
+        let mut signature = data.to_vec();
+        signature.push(27 + id.to_i32() as u8);
+
+        assert_eq!(
+            SignatureCheck::new(&signer, &hash, &signature)?,
+            SignatureCheck::Ecdsa
+        );
+        assert!(matches!(
+            SignatureCheck::new(&Address([1; 20]), &hash, &signature)?,
+            SignatureCheck::Contract(_)
+        ));
+
+        Ok(())
+    }
+}
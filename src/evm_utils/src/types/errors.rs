@@ -1,8 +1,15 @@
 use std::fmt::{self, Display};
 
+use super::{address::Address, num::H256};
+
 #[derive(Debug)]
 pub enum TransactionError {
     InvalidType,
+    ValueOverflow,
+    UnsupportedType,
+    MissingFee,
+    PriorityFeeAboveMaxFee,
+    InvalidSignature,
 }
 
 impl std::error::Error for TransactionError {}
@@ -13,3 +20,222 @@ impl Display for TransactionError {
         Ok(())
     }
 }
+
+#[derive(Debug)]
+pub enum BlockError {
+    InvalidBody,
+    TransactionsRootMismatch,
+    OmmersHashMismatch,
+    WithdrawalsRootMismatch,
+}
+
+impl std::error::Error for BlockError {}
+
+impl Display for BlockError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&format!("{self:?}"))?;
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub enum RpcError {
+    Response { code: i64, message: String },
+    InvalidResponse(&'static str),
+    InvalidConfig(&'static str),
+    CyclesBudgetExceeded { required: u128, budget: u128 },
+    NoConsensus { agreed: u32, quorum: u32 },
+}
+
+impl std::error::Error for RpcError {}
+
+impl Display for RpcError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RpcError::Response { code, message } => write!(f, "Response {code}: {message}"),
+            RpcError::InvalidResponse(x) => write!(f, "InvalidResponse {x}"),
+            RpcError::InvalidConfig(x) => write!(f, "InvalidConfig {x}"),
+            RpcError::CyclesBudgetExceeded { required, budget } => {
+                write!(
+                    f,
+                    "CyclesBudgetExceeded required {required}, budget {budget}"
+                )
+            }
+            RpcError::NoConsensus { agreed, quorum } => {
+                write!(
+                    f,
+                    "NoConsensus {agreed} providers agreed, quorum is {quorum}"
+                )
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum NonceError {
+    NotSynced,
+    NotAllocated,
+    DuplicateTransaction,
+    UnknownTransaction,
+}
+
+impl std::error::Error for NonceError {}
+
+impl Display for NonceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&format!("{self:?}"))?;
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub enum FeeError {
+    ValueOverflow,
+    EmptyHistory,
+    UnknownPercentile,
+    UnknownBlobSchedule,
+}
+
+impl std::error::Error for FeeError {}
+
+impl Display for FeeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&format!("{self:?}"))?;
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub enum StorageError {
+    KeyTooLong,
+    InvalidSize,
+    SlotOverflow,
+}
+
+impl std::error::Error for StorageError {}
+
+impl Display for StorageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&format!("{self:?}"))?;
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub enum ProofError {
+    InvalidProof,
+    AccountMismatch,
+    StorageMismatch(H256),
+    CodeHashMismatch,
+}
+
+impl std::error::Error for ProofError {}
+
+impl Display for ProofError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProofError::StorageMismatch(key) => {
+                write!(f, "StorageMismatch 0x{}", hex::encode(key.0))
+            }
+            x => write!(f, "{x:?}"),
+        }
+    }
+}
+
+/// State required by execution was not provided, result would not be reliable
+#[derive(Debug)]
+pub enum ExecutionError {
+    MissingAccount(Address),
+    MissingStorage(Address, H256),
+    MissingBlockHash(u64),
+    UnsupportedPrecompile(Address),
+    IntrinsicGas,
+    InsufficientBalance,
+}
+
+impl std::error::Error for ExecutionError {}
+
+impl Display for ExecutionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExecutionError::MissingAccount(x) => write!(f, "MissingAccount {x}"),
+            ExecutionError::MissingStorage(address, slot) => {
+                write!(f, "MissingStorage {address} 0x{}", hex::encode(slot.0))
+            }
+            ExecutionError::MissingBlockHash(x) => write!(f, "MissingBlockHash {x}"),
+            ExecutionError::UnsupportedPrecompile(x) => write!(f, "UnsupportedPrecompile {x}"),
+            x => write!(f, "{x:?}"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum AbiError {
+    InvalidAbi,
+    UnknownType,
+    InvalidData,
+}
+
+impl std::error::Error for AbiError {}
+
+impl Display for AbiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&format!("{self:?}"))?;
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub enum SiweError {
+    InvalidFormat,
+    InvalidAddress,
+    InvalidTimestamp,
+    InvalidNonce,
+    InvalidSignature,
+    UnsupportedVersion,
+    DomainMismatch,
+    ConfigMismatch,
+    Expired,
+    NotYetValid,
+    AnonymousPrincipal,
+    LoginTtlTooLong,
+}
+
+impl std::error::Error for SiweError {}
+
+impl Display for SiweError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&format!("{self:?}"))?;
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub enum SafeError {
+    InvalidSignature,
+    DuplicateOwner,
+}
+
+impl std::error::Error for SafeError {}
+
+impl Display for SafeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&format!("{self:?}"))?;
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub enum ReceiptError {
+    UnsupportedType,
+    InvalidBloomSize,
+}
+
+impl std::error::Error for ReceiptError {}
+
+impl Display for ReceiptError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&format!("{self:?}"))?;
+        Ok(())
+    }
+}
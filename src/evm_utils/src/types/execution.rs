@@ -0,0 +1,40 @@
+use ic_cdk::export::candid::{CandidType, Deserialize};
+
+use super::{address::Address, log::Log, num::U256, proof::AccountProof};
+
+/// Call to simulate, like parameters of eth_call. Gas fees are not charged
+#[derive(CandidType, Deserialize, Clone, PartialEq, Eq)]
+pub struct CallRequest {
+    /// Sender, zero address if not set
+    pub from: Option<Address>,
+    /// Contract creation if not set
+    pub to: Option<Address>,
+    pub value: U256,
+    pub data: Vec<u8>,
+    /// Gas limit of the block if not set
+    pub gas_limit: Option<u64>,
+    pub gas_price: Option<U256>,
+}
+
+/// Account state returned by eth_getProof, with the code of the account
+#[derive(CandidType, Deserialize, Clone, PartialEq, Eq)]
+pub struct ProvenAccount {
+    pub proof: AccountProof,
+    pub code: Vec<u8>,
+}
+
+#[derive(CandidType, Deserialize, Clone, PartialEq, Eq)]
+pub struct ExecutionResult {
+    pub success: bool,
+    /// Returned or reverted data, code of the contract for contract creation
+    pub output: Vec<u8>,
+    /// Logs emitted by successful execution
+    pub logs: Vec<Log>,
+    /// Gas used including intrinsic gas, after refunds
+    pub gas_used: u64,
+    /// Message of `Error(string)` revert
+    pub revert_reason: Option<String>,
+    /// Reason of exceptional halt, like OutOfGas or InvalidJump
+    pub halt_reason: Option<String>,
+    pub created_address: Option<Address>,
+}
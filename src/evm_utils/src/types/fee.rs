@@ -0,0 +1,312 @@
+use ic_cdk::export::candid::{CandidType, Deserialize};
+use primitive_types::U256 as Uint;
+
+use super::{errors::FeeError, num::U256, rpc::FeeHistory};
+
+pub const BASE_FEE_MAX_CHANGE_DENOMINATOR: u64 = 8;
+pub const ELASTICITY_MULTIPLIER: u64 = 2;
+
+pub const MIN_BLOB_BASE_FEE: u64 = 1;
+pub const GAS_PER_BLOB: u64 = 131_072;
+
+/// Blob gas parameters of a fork (EIP-7840), they change with every fork that changes
+/// blob count, so they are configured per chain instead of being derived from a header
+#[derive(CandidType, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct BlobSchedule {
+    pub target_blob_gas_per_block: u64,
+    pub max_blob_gas_per_block: u64,
+    pub base_fee_update_fraction: u64,
+    /// Execution cost of a blob bounding blob base fee from below (EIP-7918), since Osaka
+    pub blob_base_cost: Option<u64>,
+}
+
+impl BlobSchedule {
+    pub const CANCUN: Self = Self {
+        target_blob_gas_per_block: 3 * GAS_PER_BLOB,
+        max_blob_gas_per_block: 6 * GAS_PER_BLOB,
+        base_fee_update_fraction: 3_338_477,
+        blob_base_cost: None,
+    };
+    /// EIP-7691
+    pub const PRAGUE: Self = Self {
+        target_blob_gas_per_block: 6 * GAS_PER_BLOB,
+        max_blob_gas_per_block: 9 * GAS_PER_BLOB,
+        base_fee_update_fraction: 5_007_716,
+        blob_base_cost: None,
+    };
+    pub const OSAKA: Self = Self {
+        blob_base_cost: Some(8_192),
+        ..Self::PRAGUE
+    };
+    /// Blob parameter only forks of mainnet (EIP-7892)
+    pub const BPO1: Self = Self {
+        target_blob_gas_per_block: 10 * GAS_PER_BLOB,
+        max_blob_gas_per_block: 15 * GAS_PER_BLOB,
+        base_fee_update_fraction: 8_346_193,
+        blob_base_cost: Some(8_192),
+    };
+    pub const BPO2: Self = Self {
+        target_blob_gas_per_block: 14 * GAS_PER_BLOB,
+        max_blob_gas_per_block: 21 * GAS_PER_BLOB,
+        base_fee_update_fraction: 11_684_671,
+        blob_base_cost: Some(8_192),
+    };
+
+    /// Finds schedule of a mainnet fork by its lowercase name
+    pub fn from_fork(fork: &str) -> Option<Self> {
+        match fork {
+            "cancun" => Some(Self::CANCUN),
+            "prague" => Some(Self::PRAGUE),
+            "osaka" => Some(Self::OSAKA),
+            "bpo1" => Some(Self::BPO1),
+            "bpo2" => Some(Self::BPO2),
+            _ => None,
+        }
+    }
+}
+
+/// EIP1559 fee fields suggested for next block
+#[derive(CandidType, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct FeeSuggestion {
+    /// Base fee of the next block
+    pub base_fee_per_gas: U256,
+    pub max_priority_fee_per_gas: U256,
+    /// Covers base fee doubling, which takes at least 6 full blocks
+    pub max_fee_per_gas: U256,
+}
+
+/// Calculates base fee of a block from its parent (EIP-1559)
+pub fn next_base_fee(gas_used: u64, gas_limit: u64, base_fee: &U256) -> Result<U256, FeeError> {
+    let base_fee = Uint::from(base_fee);
+    let target = gas_limit / ELASTICITY_MULTIPLIER;
+
+    if gas_used == target || target == 0 {
+        return Ok(base_fee.into());
+    }
+
+    let change = |diff: u64| {
+        base_fee
+            .checked_mul(Uint::from(diff))
+            .map(|x| x / target / BASE_FEE_MAX_CHANGE_DENOMINATOR)
+            .ok_or(FeeError::ValueOverflow)
+    };
+
+    let next = if gas_used > target {
+        let delta = change(gas_used - target)?.max(Uint::one());
+        base_fee.checked_add(delta).ok_or(FeeError::ValueOverflow)?
+    } else {
+        base_fee - change(target - gas_used)?
+    };
+
+    Ok(next.into())
+}
+
+/// Calculates excess blob gas of a block from its parent (EIP-4844). Since Osaka excess grows
+/// faster while execution base fee dominates blob fees (EIP-7918)
+pub fn next_excess_blob_gas(
+    excess_blob_gas: u64,
+    blob_gas_used: u64,
+    base_fee: &U256,
+    schedule: &BlobSchedule,
+) -> Result<u64, FeeError> {
+    let total = excess_blob_gas.saturating_add(blob_gas_used);
+    if total < schedule.target_blob_gas_per_block {
+        return Ok(0);
+    }
+
+    if let Some(blob_base_cost) = schedule.blob_base_cost {
+        let reserve_price = Uint::from(blob_base_cost)
+            .checked_mul(Uint::from(base_fee))
+            .ok_or(FeeError::ValueOverflow)?;
+        let blob_price = Uint::from(&blob_base_fee(excess_blob_gas, schedule)?)
+            .checked_mul(Uint::from(GAS_PER_BLOB))
+            .ok_or(FeeError::ValueOverflow)?;
+
+        if reserve_price > blob_price && schedule.max_blob_gas_per_block > 0 {
+            let increase = u128::from(blob_gas_used)
+                * u128::from(
+                    schedule
+                        .max_blob_gas_per_block
+                        .saturating_sub(schedule.target_blob_gas_per_block),
+                )
+                / u128::from(schedule.max_blob_gas_per_block);
+
+            return Ok(excess_blob_gas.saturating_add(increase as u64));
+        }
+    }
+
+    Ok(total - schedule.target_blob_gas_per_block)
+}
+
+/// Approximates factor * e ** (numerator / denominator) using Taylor expansion
+fn fake_exponential(factor: u64, numerator: u64, denominator: u64) -> Result<Uint, FeeError> {
+    let numerator = Uint::from(numerator);
+    let denominator = Uint::from(denominator);
+
+    let mut output = Uint::zero();
+    let mut accum = Uint::from(factor) * denominator;
+    let mut i = 1u64;
+    while !accum.is_zero() {
+        output = output.checked_add(accum).ok_or(FeeError::ValueOverflow)?;
+        accum = accum
+            .checked_mul(numerator)
+            .ok_or(FeeError::ValueOverflow)?
+            / (denominator * i);
+        i += 1;
+    }
+
+    Ok(output / denominator)
+}
+
+/// Calculates blob base fee of a block from its excess blob gas (EIP-4844)
+pub fn blob_base_fee(excess_blob_gas: u64, schedule: &BlobSchedule) -> Result<U256, FeeError> {
+    Ok(fake_exponential(
+        MIN_BLOB_BASE_FEE,
+        excess_blob_gas,
+        schedule.base_fee_update_fraction,
+    )?
+    .into())
+}
+
+/// Suggests fees from eth_feeHistory, `percentile` has to be one of `reward_percentiles`
+/// used in the request. Tip is a median of rewards at the percentile, blocks without
+/// transactions are skipped
+pub fn suggest_fees(
+    history: &FeeHistory,
+    reward_percentiles: &[f64],
+    percentile: f64,
+) -> Result<FeeSuggestion, FeeError> {
+    let index = reward_percentiles
+        .iter()
+        .position(|x| *x == percentile)
+        .ok_or(FeeError::UnknownPercentile)?;
+
+    // base fee of the block after the newest one is returned as the last item
+    let base_fee = history
+        .base_fee_per_gas
+        .last()
+        .ok_or(FeeError::EmptyHistory)?;
+
+    let mut rewards: Vec<&U256> = history
+        .reward
+        .iter()
+        .zip(history.gas_used_ratio.iter())
+        .filter(|(_, ratio)| **ratio > 0.0)
+        .filter_map(|(reward, _)| reward.get(index))
+        .collect();
+    rewards.sort();
+
+    let tip = match rewards.get(rewards.len() / 2) {
+        Some(x) => (*x).clone(),
+        None => U256::zero(),
+    };
+
+    let max_fee = Uint::from(base_fee)
+        .checked_mul(Uint::from(2))
+        .and_then(|x| x.checked_add(Uint::from(&tip)))
+        .ok_or(FeeError::ValueOverflow)?;
+
+    Ok(FeeSuggestion {
+        base_fee_per_gas: base_fee.clone(),
+        max_priority_fee_per_gas: tip,
+        max_fee_per_gas: max_fee.into(),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use std::error::Error;
+
+    use super::{blob_base_fee, next_base_fee, next_excess_blob_gas, suggest_fees, BlobSchedule};
+    use crate::types::{num::U256, rpc::FeeHistory};
+
+    #[test]
+    fn base_fee() -> Result<(), Box<dyn Error>> {
+        let base_fee = U256::from(1_000_000_000u64);
+
+        assert_eq!(next_base_fee(15_000_000, 30_000_000, &base_fee)?, base_fee);
+        assert_eq!(
+            next_base_fee(30_000_000, 30_000_000, &base_fee)?,
+            U256::from(1_125_000_000u64)
+        );
+        assert_eq!(
+            next_base_fee(0, 30_000_000, &base_fee)?,
+            U256::from(875_000_000u64)
+        );
+        assert_eq!(
+            next_base_fee(20_000_000, 30_000_000, &base_fee)?,
+            U256::from(1_041_666_666u64)
+        );
+
+        // base fee increases at least by one
+        assert_eq!(
+            next_base_fee(15_000_001, 30_000_000, &U256::from(7u64))?,
+            U256::from(8u64)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn blob_fee() -> Result<(), Box<dyn Error>> {
+        let base_fee = U256::from(1_000_000_000u64);
+        let excess = |excess, used, schedule| {
+            next_excess_blob_gas(excess, used, &base_fee, schedule).unwrap()
+        };
+
+        let cancun = BlobSchedule::CANCUN;
+        assert_eq!(excess(0, 786_432, &cancun), 393_216);
+        assert_eq!(excess(100_000, 131_072, &cancun), 0);
+        assert_eq!(excess(u64::MAX, 786_432, &cancun), u64::MAX - 393_216);
+
+        assert_eq!(blob_base_fee(0, &cancun)?, U256::from(1u64));
+        assert_eq!(blob_base_fee(10_000_000, &cancun)?, U256::from(19u64));
+        assert_eq!(blob_base_fee(393_216 * 40, &cancun)?, U256::from(111u64));
+
+        let prague = BlobSchedule::PRAGUE;
+        assert_eq!(excess(0, 786_432, &prague), 0);
+        assert_eq!(excess(0, 1_179_648, &prague), 393_216);
+
+        assert_eq!(blob_base_fee(0, &prague)?, U256::from(1u64));
+        assert_eq!(blob_base_fee(10_000_000, &prague)?, U256::from(7u64));
+
+        // blob fee is below reserve price, excess grows by used gas scaled by (max - target) / max
+        let bpo2 = BlobSchedule::BPO2;
+        assert_eq!(excess(0, 1_835_008, &bpo2), 611_669);
+        assert_eq!(excess(0, 1_966_080, &bpo2), 655_360);
+        assert_eq!(excess(0, 1_703_936, &bpo2), 0);
+        let cheap = next_excess_blob_gas(0, 1_966_080, &U256::zero(), &bpo2)?;
+        assert_eq!(cheap, 131_072);
+
+        Ok(())
+    }
+
+    #[test]
+    fn fees_from_history() -> Result<(), Box<dyn Error>> {
+        let history = FeeHistory {
+            oldest_block: 100,
+            base_fee_per_gas: vec![
+                U256::from(10u64),
+                U256::from(11u64),
+                U256::from(12u64),
+                U256::from(13u64),
+            ],
+            gas_used_ratio: vec![0.9, 0.0, 0.5],
+            reward: vec![
+                vec![U256::from(1u64), U256::from(5u64)],
+                vec![U256::zero(), U256::zero()],
+                vec![U256::from(2u64), U256::from(3u64)],
+            ],
+        };
+
+        let fees = suggest_fees(&history, &[10.0, 50.0], 50.0)?;
+
+        assert_eq!(fees.base_fee_per_gas, U256::from(13u64));
+        assert_eq!(fees.max_priority_fee_per_gas, U256::from(5u64));
+        assert_eq!(fees.max_fee_per_gas, U256::from(31u64));
+
+        assert!(suggest_fees(&history, &[10.0, 50.0], 90.0).is_err());
+
+        Ok(())
+    }
+}
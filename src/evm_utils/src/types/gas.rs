@@ -0,0 +1,193 @@
+use std::error::Error;
+
+use ic_cdk::export::candid::{CandidType, Deserialize};
+use primitive_types::U256 as Uint;
+
+use super::{
+    access_list::AccessList, errors::TransactionError, num::U256, transaction::Transaction,
+};
+
+pub const TX_GAS: u64 = 21_000;
+pub const TX_CREATE_GAS: u64 = 53_000;
+pub const TX_DATA_ZERO_GAS: u64 = 4;
+pub const TX_DATA_NON_ZERO_GAS: u64 = 16;
+pub const TX_ACCESS_LIST_ADDRESS_GAS: u64 = 2_400;
+pub const TX_ACCESS_LIST_STORAGE_KEY_GAS: u64 = 1_900;
+pub const INIT_CODE_WORD_GAS: u64 = 2;
+
+/// Gas and fee requirements of a transaction
+#[derive(CandidType, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct GasCost {
+    /// Gas charged before any execution takes place
+    pub intrinsic_gas: u64,
+    /// Price per gas paid by the sender, None for EIP1559 transactions when base fee
+    /// is not known or exceeds max fee per gas
+    pub effective_gas_price: Option<U256>,
+    /// Balance required upfront, gas_limit * max fee per gas + value
+    pub max_cost: U256,
+    /// Transaction would be rejected as its gas limit does not cover intrinsic gas
+    pub below_intrinsic_gas: bool,
+}
+
+impl GasCost {
+    pub fn calculate(tx: &Transaction, base_fee: Option<&U256>) -> Result<Self, Box<dyn Error>> {
+        let intrinsic_gas = intrinsic_gas(tx);
+
+        let gas_limit = Uint::from(tx.gas_limit());
+        let max_fee = Uint::from(tx.max_fee_per_gas());
+
+        let max_cost = gas_limit
+            .checked_mul(max_fee)
+            .and_then(|x| x.checked_add(Uint::from(tx.value())))
+            .ok_or(TransactionError::ValueOverflow)?;
+
+        Ok(Self {
+            intrinsic_gas,
+            effective_gas_price: effective_gas_price(tx, base_fee),
+            max_cost: max_cost.into(),
+            below_intrinsic_gas: gas_limit < Uint::from(intrinsic_gas),
+        })
+    }
+}
+
+/// Calculates gas used by a transaction before execution, includes calldata,
+/// access list and EIP-3860 init code costs
+pub fn intrinsic_gas(tx: &Transaction) -> u64 {
+    let is_create = tx.to().is_none();
+    let data = tx.data();
+
+    let mut gas = if is_create { TX_CREATE_GAS } else { TX_GAS };
+
+    for byte in data {
+        gas += match byte {
+            0 => TX_DATA_ZERO_GAS,
+            _ => TX_DATA_NON_ZERO_GAS,
+        };
+    }
+
+    gas += AccessList::gas(tx.access_list());
+
+    if is_create {
+        let words = (data.len() as u64).div_ceil(32);
+        gas += INIT_CODE_WORD_GAS * words;
+    }
+
+    gas
+}
+
+/// Price per gas paid by the sender, for EIP1559 min(max_fee_per_gas, base_fee + max_priority_fee_per_gas)
+pub fn effective_gas_price(tx: &Transaction, base_fee: Option<&U256>) -> Option<U256> {
+    match tx {
+        Transaction::EIP1559(x) => {
+            let base_fee = Uint::from(base_fee?);
+            let max_fee = Uint::from(&x.max_fee_per_gas);
+
+            if base_fee > max_fee {
+                return None;
+            }
+
+            let tip = Uint::from(&x.max_priority_fee_per_gas).min(max_fee - base_fee);
+            Some((base_fee + tip).into())
+        }
+        _ => Some(tx.max_fee_per_gas().clone()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::error::Error;
+
+    use super::{intrinsic_gas, GasCost};
+    use crate::types::{
+        access_list::AccessList, address::Address, num::H256, num::U256, transaction::Transaction,
+        transaction_1559::Transaction1559,
+    };
+
+    fn transaction(to: Option<Address>, data: Vec<u8>) -> Transaction1559 {
+        Transaction1559 {
+            chain_id: 1,
+            nonce: U256::zero(),
+            max_priority_fee_per_gas: U256::from(2_000_000_000u64),
+            max_fee_per_gas: U256::from(100_000_000_000u64),
+            gas_limit: U256::from(21_000u64),
+            to,
+            value: U256::from(1_000u64),
+            data,
+            access_list: vec![],
+            sign: None,
+        }
+    }
+
+    #[test]
+    fn intrinsic_gas_transfer() {
+        let tx = Transaction::EIP1559(transaction(Some(Address([1u8; 20])), vec![]));
+
+        assert_eq!(intrinsic_gas(&tx), 21_000);
+    }
+
+    #[test]
+    fn intrinsic_gas_calldata_and_access_list() {
+        let mut tx = transaction(Some(Address([1u8; 20])), vec![0, 0, 1, 2]);
+        tx.access_list = vec![
+            AccessList {
+                address: Address([2u8; 20]),
+                storage_keys: vec![H256::zero(), H256::zero()],
+            },
+            AccessList {
+                address: Address([3u8; 20]),
+                storage_keys: vec![],
+            },
+        ];
+
+        let gas = intrinsic_gas(&Transaction::EIP1559(tx));
+
+        assert_eq!(gas, 21_000 + 2 * 4 + 2 * 16 + 2 * 2_400 + 2 * 1_900);
+    }
+
+    #[test]
+    fn intrinsic_gas_create() {
+        let tx = Transaction::EIP1559(transaction(None, vec![1u8; 33]));
+
+        assert_eq!(intrinsic_gas(&tx), 53_000 + 33 * 16 + 2 * 2);
+    }
+
+    #[test]
+    fn gas_cost() -> Result<(), Box<dyn Error>> {
+        let tx = Transaction::EIP1559(transaction(Some(Address([1u8; 20])), vec![1]));
+
+        let cost = GasCost::calculate(&tx, Some(&U256::from(30_000_000_000u64)))?;
+
+        assert!(cost.below_intrinsic_gas);
+        assert_eq!(cost.intrinsic_gas, 21_016);
+        assert_eq!(
+            cost.effective_gas_price,
+            Some(U256::from(32_000_000_000u64))
+        );
+        assert_eq!(
+            cost.max_cost,
+            U256::from(21_000 * 100_000_000_000u64 + 1_000)
+        );
+
+        let cost = GasCost::calculate(&tx, Some(&U256::from(99_000_000_000u64)))?;
+        assert_eq!(
+            cost.effective_gas_price,
+            Some(U256::from(100_000_000_000u64))
+        );
+
+        let cost = GasCost::calculate(&tx, Some(&U256::from(101_000_000_000u64)))?;
+        assert_eq!(cost.effective_gas_price, None);
+
+        let cost = GasCost::calculate(&tx, None)?;
+        assert_eq!(cost.effective_gas_price, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn gas_cost_overflow() {
+        let mut tx = transaction(Some(Address([1u8; 20])), vec![]);
+        tx.gas_limit = U256([0xffu8; 32]);
+
+        assert!(GasCost::calculate(&Transaction::EIP1559(tx), None).is_err());
+    }
+}
@@ -0,0 +1,207 @@
+use ic_cdk::export::candid::{CandidType, Deserialize};
+use rlp::{Decodable, Encodable, RlpStream};
+
+use super::{
+    address::Address,
+    num::{H256, U256},
+};
+
+/// Execution block header, fields added by later forks are optional
+#[derive(CandidType, Deserialize, Clone, PartialEq, Eq)]
+pub struct Header {
+    pub parent_hash: H256,
+    pub ommers_hash: H256,
+    pub beneficiary: Address,
+    pub state_root: H256,
+    pub transactions_root: H256,
+    pub receipts_root: H256,
+    pub logs_bloom: Vec<u8>,
+    pub difficulty: U256,
+    pub number: u64,
+    pub gas_limit: u64,
+    pub gas_used: u64,
+    pub timestamp: u64,
+    pub extra_data: Vec<u8>,
+    pub mix_hash: H256,
+    pub nonce: Vec<u8>,
+    /// London
+    pub base_fee_per_gas: Option<U256>,
+    /// Shanghai
+    pub withdrawals_root: Option<H256>,
+    /// Cancun
+    pub blob_gas_used: Option<u64>,
+    pub excess_blob_gas: Option<u64>,
+    pub parent_beacon_block_root: Option<H256>,
+    /// Prague
+    pub requests_hash: Option<H256>,
+}
+
+impl Decodable for Header {
+    fn decode(rlp: &rlp::Rlp) -> Result<Self, rlp::DecoderError> {
+        let item_count = rlp.item_count()?;
+        if !(15..=21).contains(&item_count) {
+            return Err(rlp::DecoderError::Custom("Invalid parameters for header"));
+        }
+
+        let optional = |i: usize| -> Result<Option<rlp::Rlp>, rlp::DecoderError> {
+            if i < item_count {
+                Ok(Some(rlp.at(i)?))
+            } else {
+                Ok(None)
+            }
+        };
+
+        Ok(Self {
+            parent_hash: rlp.val_at(0)?,
+            ommers_hash: rlp.val_at(1)?,
+            beneficiary: rlp.val_at(2)?,
+            state_root: rlp.val_at(3)?,
+            transactions_root: rlp.val_at(4)?,
+            receipts_root: rlp.val_at(5)?,
+            logs_bloom: rlp.val_at(6)?,
+            difficulty: rlp.val_at(7)?,
+            number: rlp.val_at(8)?,
+            gas_limit: rlp.val_at(9)?,
+            gas_used: rlp.val_at(10)?,
+            timestamp: rlp.val_at(11)?,
+            extra_data: rlp.val_at(12)?,
+            mix_hash: rlp.val_at(13)?,
+            nonce: rlp.val_at(14)?,
+            base_fee_per_gas: optional(15)?.map(|x| x.as_val()).transpose()?,
+            withdrawals_root: optional(16)?.map(|x| x.as_val()).transpose()?,
+            blob_gas_used: optional(17)?.map(|x| x.as_val()).transpose()?,
+            excess_blob_gas: optional(18)?.map(|x| x.as_val()).transpose()?,
+            parent_beacon_block_root: optional(19)?.map(|x| x.as_val()).transpose()?,
+            requests_hash: optional(20)?.map(|x| x.as_val()).transpose()?,
+        })
+    }
+}
+
+impl Encodable for Header {
+    fn rlp_append(&self, rlp: &mut RlpStream) {
+        rlp.begin_unbounded_list();
+
+        rlp.append(&self.parent_hash);
+        rlp.append(&self.ommers_hash);
+        rlp.append(&self.beneficiary);
+        rlp.append(&self.state_root);
+        rlp.append(&self.transactions_root);
+        rlp.append(&self.receipts_root);
+        rlp.append(&self.logs_bloom);
+        rlp.append(&self.difficulty);
+        rlp.append(&self.number);
+        rlp.append(&self.gas_limit);
+        rlp.append(&self.gas_used);
+        rlp.append(&self.timestamp);
+        rlp.append(&self.extra_data);
+        rlp.append(&self.mix_hash);
+        rlp.append(&self.nonce);
+
+        // fields of later forks are present only when all previous ones are
+        if let Some(x) = &self.base_fee_per_gas {
+            rlp.append(x);
+            if let Some(x) = &self.withdrawals_root {
+                rlp.append(x);
+                if let (Some(a), Some(b), Some(c)) = (
+                    &self.blob_gas_used,
+                    &self.excess_blob_gas,
+                    &self.parent_beacon_block_root,
+                ) {
+                    rlp.append(a);
+                    rlp.append(b);
+                    rlp.append(c);
+                    if let Some(x) = &self.requests_hash {
+                        rlp.append(x);
+                    }
+                }
+            }
+        }
+
+        rlp.finalize_unbounded_list();
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use std::error::Error;
+
+    use super::Header;
+    use crate::{
+        types::{
+            address::Address,
+            num::{H256, U256},
+        },
+        utils::keccak256,
+    };
+
+    fn hash(data: &str) -> H256 {
+        let mut buf = [0u8; 32];
+        hex::decode_to_slice(data, &mut buf).unwrap();
+        H256(buf)
+    }
+
+    /// Header of Ethereum mainnet genesis block
+    pub fn genesis() -> Header {
+        Header {
+            parent_hash: H256::zero(),
+            ommers_hash: hash("1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347"),
+            beneficiary: Address([0u8; 20]),
+            state_root: hash("d7f8974fb5ac78d9ac099b9ad5018bedc2ce0a72dad1827a1709da30580f0544"),
+            transactions_root: hash(
+                "56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421",
+            ),
+            receipts_root: hash("56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421"),
+            logs_bloom: vec![0u8; 256],
+            difficulty: U256::from(0x400000000u64),
+            number: 0,
+            gas_limit: 5000,
+            gas_used: 0,
+            timestamp: 0,
+            extra_data: hex::decode(
+                "11bbe8db4e347b4e8c937c1c8370e4b5ed33adb3db69cbdb7a38e1e50b1b82fa",
+            )
+            .unwrap(),
+            mix_hash: H256::zero(),
+            nonce: hex::decode("0000000000000042").unwrap(),
+            base_fee_per_gas: None,
+            withdrawals_root: None,
+            blob_gas_used: None,
+            excess_blob_gas: None,
+            parent_beacon_block_root: None,
+            requests_hash: None,
+        }
+    }
+
+    #[test]
+    fn genesis_header_hash() -> Result<(), Box<dyn Error>> {
+        let encoded = rlp::encode(&genesis());
+
+        assert_eq!(
+            format!("{}", keccak256(&[&encoded])),
+            "0xd4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3"
+        );
+
+        let decoded: Header = rlp::decode(&encoded)?;
+        assert!(decoded == genesis());
+
+        Ok(())
+    }
+
+    #[test]
+    fn encode_decode_cancun_header() -> Result<(), Box<dyn Error>> {
+        let mut header = genesis();
+        header.base_fee_per_gas = Some(U256::from(7u64));
+        header.withdrawals_root = Some(H256([1u8; 32]));
+        header.blob_gas_used = Some(131072);
+        header.excess_blob_gas = Some(0);
+        header.parent_beacon_block_root = Some(H256([2u8; 32]));
+
+        let encoded = rlp::encode(&header);
+        assert_eq!(rlp::Rlp::new(&encoded).item_count()?, 20);
+
+        let decoded: Header = rlp::decode(&encoded)?;
+        assert!(decoded == header);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,36 @@
+use ic_cdk::export::candid::{CandidType, Deserialize};
+use rlp::{Decodable, Encodable, RlpStream};
+
+use super::{address::Address, num::H256};
+
+/// Log emitted by a contract during transaction execution
+#[derive(CandidType, Deserialize, Clone, PartialEq, Eq)]
+pub struct Log {
+    pub address: Address,
+    pub topics: Vec<H256>,
+    pub data: Vec<u8>,
+}
+
+impl Decodable for Log {
+    fn decode(rlp: &rlp::Rlp) -> Result<Self, rlp::DecoderError> {
+        let item_count = rlp.item_count()?;
+        if item_count != 3 {
+            return Err(rlp::DecoderError::Custom("Invalid parameters for Log"));
+        }
+
+        Ok(Self {
+            address: rlp.val_at(0)?,
+            topics: rlp.list_at(1)?,
+            data: rlp.val_at(2)?,
+        })
+    }
+}
+
+impl Encodable for Log {
+    fn rlp_append(&self, rlp: &mut RlpStream) {
+        rlp.begin_list(3);
+        rlp.append(&self.address);
+        rlp.append_list(&self.topics);
+        rlp.append(&self.data);
+    }
+}
@@ -1,10 +1,37 @@
+pub mod abi;
 pub mod access_list;
 pub mod address;
+pub mod block;
+pub mod bloom;
+pub mod chain;
+pub mod consensus;
+pub mod eip712;
+pub mod erc1271;
 pub mod errors;
+pub mod execution;
+pub mod fee;
+pub mod gas;
+pub mod header;
+pub mod log;
+pub mod nonce;
 pub mod num;
+pub mod permit;
+pub mod proof;
+pub mod receipt;
+pub mod replacement;
+pub mod requests;
+pub mod revert;
 pub mod rlp;
+pub mod rpc;
+pub mod safe;
 pub mod signature;
+pub mod siwe;
+pub mod storage;
+pub mod token;
 pub mod transaction;
 pub mod transaction_1559;
 pub mod transaction_2930;
 pub mod transaction_legacy;
+pub mod user_operation;
+pub mod validation;
+pub mod withdrawal;
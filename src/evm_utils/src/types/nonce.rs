@@ -0,0 +1,269 @@
+use ic_cdk::export::candid::{CandidType, Deserialize};
+
+use super::{address::Address, errors::NonceError, num::U256};
+
+#[derive(CandidType, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TransactionStatus {
+    Pending,
+    Confirmed,
+    /// Transaction was replaced or evicted from mempool, its nonce can be reused
+    Dropped,
+}
+
+#[derive(CandidType, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct TrackedTransaction {
+    pub hash: U256,
+    pub nonce: u64,
+    pub status: TransactionStatus,
+}
+
+/// Nonces of an address on a chain, allocated nonces are not handed out again
+/// until transaction using them is dropped
+#[derive(CandidType, Deserialize, Clone, PartialEq, Eq)]
+pub struct NonceAccount {
+    pub chain_id: u64,
+    pub address: Address,
+    /// Nonce allocated next, unless there are free nonces
+    pub next_nonce: u64,
+    /// Nonces below next_nonce that are not used by any pending transaction, sorted
+    pub free_nonces: Vec<u64>,
+    pub transactions: Vec<TrackedTransaction>,
+}
+
+impl NonceAccount {
+    pub fn new(chain_id: u64, address: Address, next_nonce: u64) -> Self {
+        Self {
+            chain_id,
+            address,
+            next_nonce,
+            free_nonces: vec![],
+            transactions: vec![],
+        }
+    }
+
+    /// Returns lowest free nonce, to fill gaps left by dropped transactions first
+    pub fn allocate(&mut self) -> u64 {
+        if self.free_nonces.is_empty() {
+            self.next_nonce += 1;
+            self.next_nonce - 1
+        } else {
+            self.free_nonces.remove(0)
+        }
+    }
+
+    pub fn transaction(&self, hash: &U256) -> Option<&TrackedTransaction> {
+        self.transactions.iter().find(|x| x.hash == *hash)
+    }
+
+    /// Starts tracking transaction sent with allocated nonce, replacements may share the nonce
+    pub fn track(&mut self, hash: U256, nonce: u64) -> Result<(), NonceError> {
+        if nonce >= self.next_nonce {
+            return Err(NonceError::NotAllocated);
+        }
+        if self.transaction(&hash).is_some() {
+            return Err(NonceError::DuplicateTransaction);
+        }
+
+        self.free_nonces.retain(|x| *x != nonce);
+        self.transactions.push(TrackedTransaction {
+            hash,
+            nonce,
+            status: TransactionStatus::Pending,
+        });
+
+        Ok(())
+    }
+
+    /// Updates status of a transaction, other pending transactions with the same nonce are dropped
+    /// when it is confirmed, nonce is freed when last transaction using it is dropped and taken
+    /// back when any of them is pending or confirmed again
+    pub fn set_status(&mut self, hash: &U256, status: TransactionStatus) -> Result<(), NonceError> {
+        let nonce = self
+            .transaction(hash)
+            .ok_or(NonceError::UnknownTransaction)?
+            .nonce;
+
+        for tx in self.transactions.iter_mut() {
+            if tx.hash == *hash {
+                tx.status = status;
+            } else if tx.nonce == nonce
+                && tx.status == TransactionStatus::Pending
+                && status == TransactionStatus::Confirmed
+            {
+                tx.status = TransactionStatus::Dropped;
+            }
+        }
+
+        // dropped transaction may still be mined, its nonce is not free anymore
+        if status != TransactionStatus::Dropped {
+            self.free_nonces.retain(|x| *x != nonce);
+        }
+
+        let used = self
+            .transactions
+            .iter()
+            .any(|x| x.nonce == nonce && x.status != TransactionStatus::Dropped);
+        if !used && !self.free_nonces.contains(&nonce) {
+            self.free_nonces.push(nonce);
+            self.free_nonces.sort_unstable();
+        }
+
+        Ok(())
+    }
+
+    /// Synchronizes with `eth_getTransactionCount` of the address. Nonces below the count are
+    /// used on chain, so transactions with them are finished and forgotten, including pending
+    /// replacements. Next nonce never moves back, so nonces allocated but not yet tracked are
+    /// not handed out again, free nonces below the count are discarded
+    pub fn resync(&mut self, count: u64) {
+        self.transactions.retain(|x| x.nonce >= count);
+
+        self.next_nonce = self.next_nonce.max(count);
+        self.free_nonces.retain(|x| *x >= count);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{NonceAccount, TransactionStatus};
+    use crate::types::{address::Address, errors::NonceError, num::U256};
+
+    fn hash(x: u64) -> U256 {
+        U256::from(x)
+    }
+
+    #[test]
+    fn allocate_and_track() {
+        let mut account = NonceAccount::new(1, Address([1u8; 20]), 5);
+
+        assert_eq!(account.allocate(), 5);
+        assert_eq!(account.allocate(), 6);
+        assert!(matches!(
+            account.track(hash(1), 7),
+            Err(NonceError::NotAllocated)
+        ));
+
+        account.track(hash(1), 5).unwrap();
+        account.track(hash(2), 6).unwrap();
+        assert!(matches!(
+            account.track(hash(1), 5),
+            Err(NonceError::DuplicateTransaction)
+        ));
+
+        // dropped nonce is reused before new one
+        account
+            .set_status(&hash(1), TransactionStatus::Dropped)
+            .unwrap();
+        assert_eq!(account.free_nonces, vec![5]);
+        assert_eq!(account.allocate(), 5);
+        assert_eq!(account.allocate(), 7);
+    }
+
+    #[test]
+    fn confirm_drops_replaced() {
+        let mut account = NonceAccount::new(1, Address([1u8; 20]), 0);
+
+        let nonce = account.allocate();
+        account.track(hash(1), nonce).unwrap();
+        account.track(hash(2), nonce).unwrap();
+
+        account
+            .set_status(&hash(2), TransactionStatus::Confirmed)
+            .unwrap();
+
+        assert_eq!(
+            account.transaction(&hash(1)).unwrap().status,
+            TransactionStatus::Dropped
+        );
+        assert!(account.free_nonces.is_empty());
+        assert!(account
+            .set_status(&hash(3), TransactionStatus::Confirmed)
+            .is_err());
+    }
+
+    #[test]
+    fn resync() {
+        let mut account = NonceAccount::new(1, Address([1u8; 20]), 0);
+        for i in 0..4 {
+            let nonce = account.allocate();
+            account.track(hash(i), nonce).unwrap();
+        }
+        account
+            .set_status(&hash(2), TransactionStatus::Dropped)
+            .unwrap();
+
+        account.resync(2);
+
+        assert_eq!(account.transactions.len(), 2);
+        assert_eq!(account.next_nonce, 4);
+        assert_eq!(account.free_nonces, vec![2]);
+
+        // transactions were sent outside of the manager
+        account.resync(10);
+
+        assert!(account.transactions.is_empty());
+        assert_eq!(account.allocate(), 10);
+    }
+
+    #[test]
+    fn dropped_transaction_mined() {
+        let mut account = NonceAccount::new(1, Address([1u8; 20]), 0);
+
+        let nonce = account.allocate();
+        account.track(hash(1), nonce).unwrap();
+        account
+            .set_status(&hash(1), TransactionStatus::Dropped)
+            .unwrap();
+        assert_eq!(account.free_nonces, vec![0]);
+
+        // rebroadcast transaction was mined after all
+        account
+            .set_status(&hash(1), TransactionStatus::Confirmed)
+            .unwrap();
+        assert!(account.free_nonces.is_empty());
+        assert_eq!(account.allocate(), 1);
+    }
+
+    #[test]
+    fn resync_forgets_replacements() {
+        let mut account = NonceAccount::new(1, Address([1u8; 20]), 0);
+        let nonce = account.allocate();
+        account.track(hash(1), nonce).unwrap();
+        account.track(hash(2), nonce).unwrap();
+        let nonce = account.allocate();
+        account.track(hash(3), nonce).unwrap();
+
+        account.resync(1);
+
+        assert!(account.transaction(&hash(1)).is_none());
+        assert!(account.transaction(&hash(2)).is_none());
+        assert_eq!(
+            account.transaction(&hash(3)).unwrap().status,
+            TransactionStatus::Pending
+        );
+    }
+
+    #[test]
+    fn resync_keeps_allocated() {
+        let mut account = NonceAccount::new(1, Address([1u8; 20]), 0);
+        for i in 0..3 {
+            let nonce = account.allocate();
+            account.track(hash(i), nonce).unwrap();
+        }
+        account
+            .set_status(&hash(1), TransactionStatus::Dropped)
+            .unwrap();
+
+        // allocated, but transactions are not sent yet
+        assert_eq!(account.allocate(), 1);
+        assert_eq!(account.allocate(), 3);
+        assert_eq!(account.allocate(), 4);
+
+        account.resync(1);
+
+        assert_eq!(account.next_nonce, 5);
+        assert!(account.free_nonces.is_empty());
+        assert_eq!(account.allocate(), 5);
+        account.track(hash(3), 4).unwrap();
+    }
+}
@@ -1,17 +1,33 @@
 use std::fmt::Display;
 
 use ic_cdk::export::candid::{CandidType, Deserialize};
+use primitive_types::U256 as Uint;
 use rlp::{Decodable, DecoderError, Encodable, Rlp, RlpStream};
 
-#[derive(CandidType, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[derive(CandidType, Deserialize, Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
 pub struct U256(pub [u8; 32]);
 impl U256 {
     pub fn zero() -> Self {
         Self([0u8; 32])
     }
+
+    pub fn is_zero(&self) -> bool {
+        self.0.iter().all(|x| *x == 0)
+    }
+
+    /// Returns value as u64, None if it does not fit
+    pub fn as_u64(&self) -> Option<u64> {
+        if self.0[..24].iter().any(|x| *x != 0) {
+            return None;
+        }
+
+        let mut buf = [0u8; 8];
+        buf.copy_from_slice(&self.0[24..]);
+        Some(u64::from_be_bytes(buf))
+    }
 }
 
-#[derive(CandidType, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[derive(CandidType, Deserialize, Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
 pub struct H256(pub [u8; 32]);
 impl H256 {
     pub fn zero() -> Self {
@@ -42,6 +58,29 @@ impl From<u64> for U256 {
     }
 }
 
+impl From<&U256> for Uint {
+    #[inline]
+    fn from(num: &U256) -> Self {
+        Uint::from_big_endian(&num.0)
+    }
+}
+
+impl From<Uint> for U256 {
+    #[inline]
+    fn from(num: Uint) -> Self {
+        let mut buf = [0u8; 32];
+        num.to_big_endian(&mut buf);
+        U256(buf)
+    }
+}
+
+impl From<U256> for H256 {
+    #[inline]
+    fn from(num: U256) -> Self {
+        H256(num.0)
+    }
+}
+
 impl Display for U256 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "0x")?;
@@ -133,7 +172,7 @@ mod test {
 
     #[test]
     fn rlp_encode_decode() -> Result<(), Box<dyn Error>> {
-        let num = U256::from(1000_000u64);
+        let num = U256::from(1_000_000u64);
         let encoded = rlp::encode(&num);
         let decoded: U256 = rlp::decode(&encoded)?;
 
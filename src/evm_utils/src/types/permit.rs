@@ -0,0 +1,338 @@
+use std::{error::Error, str::FromStr};
+
+use ic_cdk::export::candid::{CandidType, Deserialize};
+
+use super::{
+    abi::AbiValue,
+    address::Address,
+    eip712::{self, Domain},
+    num::{H256, U256},
+    signature::Signature,
+    validation,
+};
+use crate::utils::{keccak256, recover_address};
+
+/// Address of Uniswap Permit2 contract, the same on all chains
+pub const PERMIT2_ADDRESS: &str = "0x000000000022D473030F116dDEE9F6B43aC78BA3";
+
+const PERMIT_TYPE: &str =
+    "Permit(address owner,address spender,uint256 value,uint256 nonce,uint256 deadline)";
+const PERMIT_DETAILS_TYPE: &str =
+    "PermitDetails(address token,uint160 amount,uint48 expiration,uint48 nonce)";
+const PERMIT_SINGLE_TYPE: &str =
+    "PermitSingle(PermitDetails details,address spender,uint256 sigDeadline)";
+const PERMIT_BATCH_TYPE: &str =
+    "PermitBatch(PermitDetails[] details,address spender,uint256 sigDeadline)";
+const TOKEN_PERMISSIONS_TYPE: &str = "TokenPermissions(address token,uint256 amount)";
+const PERMIT_TRANSFER_FROM_TYPE: &str =
+    "PermitTransferFrom(TokenPermissions permitted,address spender,uint256 nonce,uint256 deadline)";
+
+/// EIP-2612 permit, signed by the owner of ERC-20 tokens
+#[derive(CandidType, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct Erc2612Permit {
+    pub owner: Address,
+    pub spender: Address,
+    pub value: U256,
+    /// Current value of `nonces(owner)` of the token
+    pub nonce: U256,
+    pub deadline: U256,
+}
+
+impl Erc2612Permit {
+    /// Digest to sign, domain is the one of the token (`DOMAIN_SEPARATOR`)
+    pub fn digest(&self, domain: &Domain) -> H256 {
+        let hash = eip712::hash_struct(
+            PERMIT_TYPE,
+            &[
+                AbiValue::Address(self.owner.clone()),
+                AbiValue::Address(self.spender.clone()),
+                AbiValue::Uint(self.value.clone()),
+                AbiValue::Uint(self.nonce.clone()),
+                AbiValue::Uint(self.deadline.clone()),
+            ],
+        );
+
+        eip712::digest(domain, &hash)
+    }
+}
+
+/// Allowance given to a spender with Permit2 AllowanceTransfer
+#[derive(CandidType, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct PermitDetails {
+    pub token: Address,
+    /// uint160 amount
+    pub amount: U256,
+    /// uint48 timestamp at which allowance expires
+    pub expiration: u64,
+    /// uint48 nonce of owner, token and spender
+    pub nonce: u64,
+}
+
+impl PermitDetails {
+    fn hash(&self) -> H256 {
+        eip712::hash_struct(
+            PERMIT_DETAILS_TYPE,
+            &[
+                AbiValue::Address(self.token.clone()),
+                AbiValue::Uint(self.amount.clone()),
+                AbiValue::Uint(U256::from(self.expiration)),
+                AbiValue::Uint(U256::from(self.nonce)),
+            ],
+        )
+    }
+}
+
+/// Token and amount that can be transferred with Permit2 SignatureTransfer
+#[derive(CandidType, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct TokenPermissions {
+    pub token: Address,
+    pub amount: U256,
+}
+
+/// Permit2 messages
+#[derive(CandidType, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub enum Permit2 {
+    Single {
+        details: PermitDetails,
+        spender: Address,
+        sig_deadline: U256,
+    },
+    Batch {
+        details: Vec<PermitDetails>,
+        spender: Address,
+        sig_deadline: U256,
+    },
+    /// Spender is the contract calling `permitTransferFrom`
+    TransferFrom {
+        permitted: TokenPermissions,
+        spender: Address,
+        /// Unordered nonce, bit in owner's nonce bitmap
+        nonce: U256,
+        deadline: U256,
+    },
+}
+
+impl Permit2 {
+    pub fn domain(chain_id: u64) -> Domain {
+        Domain {
+            name: Some("Permit2".to_string()),
+            chain_id: Some(chain_id),
+            verifying_contract: Address::from_str(PERMIT2_ADDRESS).ok(),
+            ..Default::default()
+        }
+    }
+
+    pub fn struct_hash(&self) -> H256 {
+        let hash = |x: H256| AbiValue::FixedBytes(x.0.to_vec());
+
+        match self {
+            Permit2::Single {
+                details,
+                spender,
+                sig_deadline,
+            } => eip712::hash_struct(
+                &format!("{PERMIT_SINGLE_TYPE}{PERMIT_DETAILS_TYPE}"),
+                &[
+                    hash(details.hash()),
+                    AbiValue::Address(spender.clone()),
+                    AbiValue::Uint(sig_deadline.clone()),
+                ],
+            ),
+            Permit2::Batch {
+                details,
+                spender,
+                sig_deadline,
+            } => {
+                let hashes: Vec<[u8; 32]> = details.iter().map(|x| x.hash().0).collect();
+                eip712::hash_struct(
+                    &format!("{PERMIT_BATCH_TYPE}{PERMIT_DETAILS_TYPE}"),
+                    &[
+                        hash(keccak256(&[&hashes.concat()]).into()),
+                        AbiValue::Address(spender.clone()),
+                        AbiValue::Uint(sig_deadline.clone()),
+                    ],
+                )
+            }
+            Permit2::TransferFrom {
+                permitted,
+                spender,
+                nonce,
+                deadline,
+            } => {
+                let permitted = eip712::hash_struct(
+                    TOKEN_PERMISSIONS_TYPE,
+                    &[
+                        AbiValue::Address(permitted.token.clone()),
+                        AbiValue::Uint(permitted.amount.clone()),
+                    ],
+                );
+                eip712::hash_struct(
+                    &format!("{PERMIT_TRANSFER_FROM_TYPE}{TOKEN_PERMISSIONS_TYPE}"),
+                    &[
+                        hash(permitted),
+                        AbiValue::Address(spender.clone()),
+                        AbiValue::Uint(nonce.clone()),
+                        AbiValue::Uint(deadline.clone()),
+                    ],
+                )
+            }
+        }
+    }
+
+    pub fn digest(&self, chain_id: u64) -> H256 {
+        eip712::digest(&Self::domain(chain_id), &self.struct_hash())
+    }
+}
+
+/// Checks that 65 bytes signature of a permit digest was made by the owner. Signatures with
+/// high s are rejected, like by OpenZeppelin ECDSA, as permits using them revert on chain
+pub fn verify_signature(
+    digest: &H256,
+    signature: &[u8],
+    owner: &Address,
+) -> Result<bool, Box<dyn Error>> {
+    let signer = recover_address(signature, &digest.0)?;
+    let sign = Signature {
+        v: signature[64] as u64,
+        r: signature[..32].to_vec(),
+        s: signature[32..64].to_vec(),
+        from: Some(signer.clone()),
+        hash: U256(digest.0),
+    };
+
+    Ok(signer == *owner && validation::validate_signature(&sign).is_empty())
+}
+
+#[cfg(test)]
+mod test {
+    use std::{error::Error, str::FromStr};
+
+    use secp256k1::{Message, SecretKey, SECP256K1};
+
+    use primitive_types::U256 as Uint;
+
+    use super::{verify_signature, Erc2612Permit, Permit2, PermitDetails, TokenPermissions};
+    use crate::{
+        types::{address::Address, eip712::Domain, num::U256},
+        utils::recover_address,
+    };
+
+    const USDC: &str = "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48";
+
+    fn details(token: Address, amount: u64, expiration: u64, nonce: u64) -> PermitDetails {
+        PermitDetails {
+            token,
+            amount: U256::from(amount),
+            expiration,
+            nonce,
+        }
+    }
+
+    #[test]
+    fn erc2612_digest() -> Result<(), Box<dyn Error>> {
+        let domain = Domain {
+            name: Some("USD Coin".to_string()),
+            version: Some("2".to_string()),
+            chain_id: Some(1),
+            verifying_contract: Some(Address::from_str(USDC)?),
+            salt: None,
+        };
+        let permit = Erc2612Permit {
+            owner: Address([0x11; 20]),
+            spender: Address([0x22; 20]),
+            value: U256::from(1_000_000),
+            nonce: U256::zero(),
+            deadline: U256::from(1_700_000_000),
+        };
+
+        assert_eq!(
+            hex::encode(permit.digest(&domain).0),
+            "f7624397695573d9efdb675e137da096dd011a9917968a3ae9a4d17d07946700"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn permit2_digests() -> Result<(), Box<dyn Error>> {
+        let usdc = details(Address::from_str(USDC)?, 1_000_000, 1_700_000_000, 0);
+
+        let single = Permit2::Single {
+            details: usdc.clone(),
+            spender: Address([0x22; 20]),
+            sig_deadline: U256::from(1_700_000_000),
+        };
+        assert_eq!(
+            hex::encode(single.digest(1).0),
+            "92e167c8e8f785836d3692e152ee3bcb68bce014a5c908a3d1d824f88003bf3b"
+        );
+
+        let batch = Permit2::Batch {
+            details: vec![usdc, details(Address([0x33; 20]), 5, 1_700_000_001, 1)],
+            spender: Address([0x22; 20]),
+            sig_deadline: U256::from(1_700_000_000),
+        };
+        assert_eq!(
+            hex::encode(batch.digest(1).0),
+            "2066d5f7253f50ced385578c842094f41c51e17f7e6ab9a5a3d374f97e64cd8d"
+        );
+
+        let transfer = Permit2::TransferFrom {
+            permitted: TokenPermissions {
+                token: Address::from_str(USDC)?,
+                amount: U256::from(1_000_000),
+            },
+            spender: Address([0x22; 20]),
+            nonce: U256::from(5),
+            deadline: U256::from(1_700_000_000),
+        };
+        assert_eq!(
+            hex::encode(transfer.digest(1).0),
+            "da6f656632438123b8fd864f39206959c3c22a746c15c14e05655597dabc5264"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn recover_owner() -> Result<(), Box<dyn Error>> {
+        let key = SecretKey::from_slice(&[0x46; 32])?;
+        let owner = Address::from(key.public_key(SECP256K1));
+        let permit = Erc2612Permit {
+            owner: owner.clone(),
+            spender: Address([0x22; 20]),
+            value: U256::from(1),
+            nonce: U256::zero(),
+            deadline: U256::from(1_700_000_000),
+        };
+        let digest = permit.digest(&Domain::default());
+
+        let (id, data) = SECP256K1
+            .sign_ecdsa_recoverable(&Message::from_slice(&digest.0)?, &key)
+            .serialize_compact();
+        let mut signature = data.to_vec();
+        signature.push(27 + id.to_i32() as u8);
+
+        assert_eq!(recover_address(&signature, &digest.0)?, owner);
+        assert!(recover_address(&signature[..64], &digest.0).is_err());
+        assert!(verify_signature(&digest, &signature, &owner)?);
+        assert!(!verify_signature(
+            &digest,
+            &signature,
+            &Address([0x22; 20])
+        )?);
+
+        // the same signature with s replaced by n - s, recovers owner but reverts on chain
+        let n = Uint::from_str_radix(
+            "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141",
+            16,
+        )?;
+        let mut high_s = signature.clone();
+        (n - Uint::from_big_endian(&signature[32..64])).to_big_endian(&mut high_s[32..64]);
+        high_s[64] = 55 - high_s[64];
+        assert_eq!(recover_address(&high_s, &digest.0)?, owner);
+        assert!(!verify_signature(&digest, &high_s, &owner)?);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,151 @@
+use hasher::HasherKeccak;
+use ic_cdk::export::candid::{CandidType, Deserialize};
+use rlp::RlpStream;
+
+use super::{
+    address::Address,
+    errors::ProofError,
+    num::{H256, U256},
+};
+use crate::utils::keccak256;
+
+/// Root of a trie without any nodes, keccak256(rlp(""))
+pub const EMPTY_TRIE_ROOT: [u8; 32] = [
+    0x56, 0xe8, 0x1f, 0x17, 0x1b, 0xcc, 0x55, 0xa6, 0xff, 0x83, 0x45, 0xe6, 0x92, 0xc0, 0xf8, 0x6e,
+    0x5b, 0x48, 0xe0, 0x1b, 0x99, 0x6c, 0xad, 0xc0, 0x01, 0x62, 0x2f, 0xb5, 0xe3, 0x63, 0xb4, 0x21,
+];
+
+/// Hash of empty code, keccak256("")
+pub const EMPTY_CODE_HASH: [u8; 32] = [
+    0xc5, 0xd2, 0x46, 0x01, 0x86, 0xf7, 0x23, 0x3c, 0x92, 0x7e, 0x7d, 0xb2, 0xdc, 0xc7, 0x03, 0xc0,
+    0xe5, 0x00, 0xb6, 0x53, 0xca, 0x82, 0x27, 0x3b, 0x7b, 0xfa, 0xd8, 0x04, 0x5d, 0x85, 0xa4, 0x70,
+];
+
+fn verify(root: &H256, key: &[u8], proof: &[Vec<u8>]) -> Result<Option<Vec<u8>>, ProofError> {
+    cita_trie::verify_proof(
+        &root.0,
+        &keccak256(&[key]).0,
+        proof.to_vec(),
+        HasherKeccak::new(),
+    )
+    .map_err(|_| ProofError::InvalidProof)
+}
+
+/// Merkle proof of a storage slot, as returned by eth_getProof
+#[derive(CandidType, Deserialize, Clone, PartialEq, Eq)]
+pub struct StorageProof {
+    pub key: H256,
+    pub value: U256,
+    pub proof: Vec<Vec<u8>>,
+}
+
+/// Merkle proof of an account and its storage, as returned by eth_getProof
+#[derive(CandidType, Deserialize, Clone, PartialEq, Eq)]
+pub struct AccountProof {
+    pub address: Address,
+    pub balance: U256,
+    pub code_hash: H256,
+    pub nonce: U256,
+    pub storage_hash: H256,
+    pub account_proof: Vec<Vec<u8>>,
+    pub storage_proof: Vec<StorageProof>,
+}
+
+impl AccountProof {
+    /// Verifies account and storage values against state root of a block, account that
+    /// does not exist has to be returned with empty values
+    pub fn verify(&self, state_root: &H256) -> Result<(), ProofError> {
+        let account = verify(state_root, &self.address.0, &self.account_proof)?;
+
+        let expected = match account {
+            Some(x) => x,
+            None if self.nonce.is_zero()
+                && self.balance.is_zero()
+                && self.code_hash.0 == EMPTY_CODE_HASH
+                && self.storage_hash.0 == EMPTY_TRIE_ROOT =>
+            {
+                vec![]
+            }
+            None => return Err(ProofError::AccountMismatch),
+        };
+
+        if !expected.is_empty() {
+            let mut rlp = RlpStream::new_list(4);
+            rlp.append(&self.nonce);
+            rlp.append(&self.balance);
+            rlp.append(&self.storage_hash);
+            rlp.append(&self.code_hash);
+
+            if rlp.out().to_vec() != expected {
+                return Err(ProofError::AccountMismatch);
+            }
+        }
+
+        for item in &self.storage_proof {
+            let value = verify(&self.storage_hash, &item.key.0, &item.proof)?;
+            let value: U256 = match value {
+                Some(x) => rlp::decode(&x).map_err(|_| ProofError::InvalidProof)?,
+                None => U256::zero(),
+            };
+
+            if value != item.value {
+                return Err(ProofError::StorageMismatch(item.key.clone()));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks that code belongs to the account
+    pub fn verify_code(&self, code: &[u8]) -> Result<(), ProofError> {
+        if keccak256(&[code]).0 != self.code_hash.0 {
+            return Err(ProofError::CodeHashMismatch);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::error::Error;
+
+    use super::EMPTY_CODE_HASH;
+    use crate::{
+        types::{
+            num::{H256, U256},
+            rpc::{BlockTag, RpcRequest, RpcResponse},
+        },
+        utils::keccak256,
+    };
+
+    #[test]
+    fn verify_account_proof() -> Result<(), Box<dyn Error>> {
+        let req = RpcRequest::GetProof {
+            address: "0xcca577ee56d30a444c73f8fc8d5ce34ed1c7da8b".parse()?,
+            storage_keys: vec![H256::zero()],
+            block: BlockTag::Latest,
+        };
+        let mut proof =
+            match req.parse_response(include_bytes!("../../../../__tests__/proof.json"))? {
+                RpcResponse::Proof(x) => x,
+                _ => panic!("Wrong response type"),
+            };
+
+        // root node of the proof is hashed in to the state root
+        let state_root: H256 = keccak256(&[&proof.account_proof[0]]).into();
+        proof.verify(&state_root)?;
+
+        assert!(proof.verify_code(&[]).is_err());
+        assert_ne!(proof.code_hash.0, EMPTY_CODE_HASH);
+
+        proof.storage_proof[0].value = U256::from(1u64);
+        assert!(proof.verify(&state_root).is_err());
+
+        proof.storage_proof[0].value = U256::zero();
+        proof.balance = U256::from(1u64);
+        assert!(proof.verify(&state_root).is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,203 @@
+use std::error::Error;
+
+use bytes::{BufMut, BytesMut};
+use ic_cdk::export::candid::{CandidType, Deserialize};
+use rlp::{Decodable, DecoderError, Encodable, RlpStream};
+
+use super::{
+    bloom::BLOOM_SIZE,
+    errors::{ReceiptError, TransactionError},
+    log::Log,
+    num::{H256, U256},
+};
+
+/// Result of transaction execution, status since Byzantium, root of state trie before it
+#[derive(CandidType, Deserialize, Clone, PartialEq, Eq)]
+pub enum ReceiptOutcome {
+    Status(bool),
+    PostState(H256),
+}
+
+#[derive(CandidType, Deserialize, Clone, PartialEq, Eq)]
+pub struct Receipt {
+    /// Type of transaction envelope, 0 for legacy receipts
+    pub tx_type: u8,
+    pub outcome: ReceiptOutcome,
+    pub cumulative_gas_used: U256,
+    pub logs_bloom: Vec<u8>,
+    pub logs: Vec<Log>,
+}
+
+impl Receipt {
+    /// Decodes receipt, supports Legacy, EIP2930, EIP1559, EIP4844 and EIP7702 envelopes
+    pub fn decode(raw: &[u8]) -> Result<Receipt, Box<dyn Error>> {
+        match raw.first() {
+            Some(x) if *x > 0x7f => Ok(rlp::decode(raw)?),
+            Some(x) if (0x01..=0x04).contains(x) => {
+                let mut item: Receipt = rlp::decode(&raw[1..])?;
+                item.tx_type = *x;
+                Ok(item)
+            }
+            _ => Err(Box::new(TransactionError::InvalidType)),
+        }
+    }
+
+    /// Encodes receipt, fails for envelopes and blooms that `decode` would reject
+    pub fn encode(&self) -> Result<BytesMut, ReceiptError> {
+        if self.tx_type > 0x04 {
+            return Err(ReceiptError::UnsupportedType);
+        }
+        if self.logs_bloom.len() != BLOOM_SIZE {
+            return Err(ReceiptError::InvalidBloomSize);
+        }
+
+        let mut buf = BytesMut::new();
+        if self.tx_type != 0 {
+            buf.put_u8(self.tx_type);
+        }
+        buf.extend_from_slice(&rlp::encode(self));
+
+        Ok(buf)
+    }
+}
+
+impl Decodable for Receipt {
+    fn decode(rlp: &rlp::Rlp) -> Result<Self, DecoderError> {
+        let item_count = rlp.item_count()?;
+        if item_count != 4 {
+            return Err(DecoderError::Custom("Invalid parameters for receipt"));
+        }
+
+        let outcome: Vec<u8> = rlp.val_at(0)?;
+        let outcome = match outcome.len() {
+            0 => ReceiptOutcome::Status(false),
+            1 if outcome[0] == 1 => ReceiptOutcome::Status(true),
+            32 => ReceiptOutcome::PostState(rlp.val_at(0)?),
+            _ => return Err(DecoderError::Custom("Invalid receipt status")),
+        };
+
+        let logs_bloom: Vec<u8> = rlp.val_at(2)?;
+        if logs_bloom.len() != BLOOM_SIZE {
+            return Err(DecoderError::Custom("Invalid number of bytes for bloom"));
+        }
+
+        Ok(Self {
+            tx_type: 0,
+            outcome,
+            cumulative_gas_used: rlp.val_at(1)?,
+            logs_bloom,
+            logs: rlp.list_at(3)?,
+        })
+    }
+}
+
+impl Encodable for Receipt {
+    fn rlp_append(&self, rlp: &mut RlpStream) {
+        rlp.begin_list(4);
+
+        match &self.outcome {
+            ReceiptOutcome::Status(true) => rlp.append(&1u8),
+            ReceiptOutcome::Status(false) => rlp.append_empty_data(),
+            ReceiptOutcome::PostState(root) => rlp.append(root),
+        };
+
+        rlp.append(&self.cumulative_gas_used);
+        rlp.append(&self.logs_bloom);
+        rlp.append_list(&self.logs);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::error::Error;
+
+    use super::{Receipt, ReceiptOutcome};
+    use crate::types::{
+        address::Address,
+        bloom::Bloom,
+        log::Log,
+        num::{H256, U256},
+    };
+
+    fn receipt(tx_type: u8, outcome: ReceiptOutcome) -> Receipt {
+        let logs = vec![Log {
+            address: Address([0x11u8; 20]),
+            topics: vec![H256([0x22u8; 32]), H256([0x33u8; 32])],
+            data: vec![1, 2, 3],
+        }];
+
+        Receipt {
+            tx_type,
+            outcome,
+            cumulative_gas_used: U256::from(21_000u64),
+            logs_bloom: Bloom::from_logs(&logs).0.to_vec(),
+            logs,
+        }
+    }
+
+    #[test]
+    fn encode_decode_receipt() -> Result<(), Box<dyn Error>> {
+        for (tx_type, outcome) in [
+            (0, ReceiptOutcome::Status(true)),
+            (0, ReceiptOutcome::PostState(H256([0x44u8; 32]))),
+            (1, ReceiptOutcome::Status(false)),
+            (2, ReceiptOutcome::Status(true)),
+            (3, ReceiptOutcome::Status(true)),
+            (4, ReceiptOutcome::Status(false)),
+        ] {
+            let item = receipt(tx_type, outcome);
+            let encoded = item.encode()?;
+
+            if tx_type == 0 {
+                assert!(encoded[0] > 0x7f);
+            } else {
+                assert_eq!(encoded[0], tx_type);
+            }
+
+            let decoded = Receipt::decode(&encoded)?;
+            assert!(decoded == item);
+            assert_eq!(decoded.encode()?, encoded);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn encode_invalid_receipt() {
+        let mut item = receipt(0x80, ReceiptOutcome::Status(true));
+        assert!(item.encode().is_err());
+
+        item.tx_type = 0x05;
+        assert!(item.encode().is_err());
+
+        item.tx_type = 0x02;
+        item.logs_bloom.pop();
+        assert!(item.encode().is_err());
+    }
+
+    #[test]
+    fn encode_status_receipt() -> Result<(), Box<dyn Error>> {
+        let item = Receipt {
+            tx_type: 2,
+            outcome: ReceiptOutcome::Status(true),
+            cumulative_gas_used: U256::from(0x5208u64),
+            logs_bloom: vec![0u8; 256],
+            logs: vec![],
+        };
+
+        let mut expected = String::from("02f9010801825208b90100");
+        expected.push_str(&"00".repeat(256));
+        expected.push_str("c0");
+
+        assert_eq!(hex::encode(item.encode()?), expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn decode_invalid_receipt() {
+        assert!(Receipt::decode(&[]).is_err());
+        assert!(Receipt::decode(&[0x05, 0xc0]).is_err());
+        assert!(Receipt::decode(&[0xc0]).is_err());
+    }
+}
@@ -0,0 +1,206 @@
+use ic_cdk::export::candid::{CandidType, Deserialize};
+use primitive_types::U256 as Uint;
+
+use super::{
+    address::Address, errors::TransactionError, gas::TX_GAS, num::U256, transaction::Transaction,
+};
+use crate::utils::keccak256;
+
+/// Minimal fee increase accepted by nodes for a transaction with the same nonce
+pub const MIN_FEE_BUMP_PERCENT: u64 = 10;
+
+/// Fees replacement should pay at least, like current network fees. Fees lower than
+/// minimal bump over the original transaction are raised to it
+#[derive(CandidType, Deserialize, Clone, PartialEq, Eq, Default)]
+pub struct ReplacementFees {
+    pub gas_price: Option<U256>,
+    pub max_fee_per_gas: Option<U256>,
+    pub max_priority_fee_per_gas: Option<U256>,
+}
+
+/// Unsigned transaction replacing a pending one, with its hash to be signed
+#[derive(CandidType, Deserialize, Clone, PartialEq, Eq)]
+pub struct Replacement {
+    pub transaction: Transaction,
+    pub signing_hash: U256,
+}
+
+/// Increases fee by given percent, rounding up
+pub fn bump_fee(fee: &U256, percent: u64) -> Result<U256, TransactionError> {
+    let bumped = Uint::from(fee)
+        .checked_mul(Uint::from(100 + percent))
+        .and_then(|x| x.checked_add(Uint::from(99)))
+        .ok_or(TransactionError::ValueOverflow)?
+        / 100;
+
+    Ok(bumped.into())
+}
+
+fn replacement_fee(fee: &U256, requested: &Option<U256>) -> Result<U256, TransactionError> {
+    let bumped = bump_fee(fee, MIN_FEE_BUMP_PERCENT)?;
+
+    Ok(match requested {
+        Some(x) if *x > bumped => x.clone(),
+        _ => bumped,
+    })
+}
+
+impl Replacement {
+    fn new(transaction: Transaction) -> Self {
+        let signing_hash = keccak256(&[&transaction.encode(true)]);
+
+        Self {
+            transaction,
+            signing_hash,
+        }
+    }
+
+    /// Builds the same transaction with bumped fees, signature is removed
+    pub fn speed_up(tx: &Transaction, fees: &ReplacementFees) -> Result<Self, TransactionError> {
+        let mut tx = tx.clone();
+        match &mut tx {
+            Transaction::Legacy(a) => {
+                a.gas_price = replacement_fee(&a.gas_price, &fees.gas_price)?;
+                a.sign = None;
+            }
+            Transaction::EIP2930(a) => {
+                a.gas_price = replacement_fee(&a.gas_price, &fees.gas_price)?;
+                a.sign = None;
+            }
+            Transaction::EIP1559(a) => {
+                a.max_priority_fee_per_gas =
+                    replacement_fee(&a.max_priority_fee_per_gas, &fees.max_priority_fee_per_gas)?;
+                // max fee can not be lower than the tip
+                a.max_fee_per_gas = replacement_fee(&a.max_fee_per_gas, &fees.max_fee_per_gas)?
+                    .max(a.max_priority_fee_per_gas.clone());
+                a.sign = None;
+            }
+        }
+
+        Ok(Self::new(tx))
+    }
+
+    /// Builds transfer of zero value from the sender to itself, with the same nonce and bumped fees
+    pub fn cancel(
+        tx: &Transaction,
+        from: &Address,
+        fees: &ReplacementFees,
+    ) -> Result<Self, TransactionError> {
+        let mut tx = Self::speed_up(tx, fees)?.transaction;
+        match &mut tx {
+            Transaction::Legacy(a) => {
+                a.to = Some(from.clone());
+                a.value = U256::zero();
+                a.data = vec![];
+                a.gas_limit = U256::from(TX_GAS);
+            }
+            Transaction::EIP2930(a) => {
+                a.to = Some(from.clone());
+                a.value = U256::zero();
+                a.data = vec![];
+                a.gas_limit = U256::from(TX_GAS);
+                a.access_list = vec![];
+            }
+            Transaction::EIP1559(a) => {
+                a.to = Some(from.clone());
+                a.value = U256::zero();
+                a.data = vec![];
+                a.gas_limit = U256::from(TX_GAS);
+                a.access_list = vec![];
+            }
+        }
+
+        Ok(Self::new(tx))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::error::Error;
+
+    use super::{bump_fee, Replacement, ReplacementFees};
+    use crate::{
+        types::{
+            address::Address, num::U256, transaction::Transaction,
+            transaction_1559::Transaction1559,
+        },
+        utils::keccak256,
+    };
+
+    fn transaction() -> Transaction1559 {
+        Transaction1559 {
+            chain_id: 1,
+            nonce: U256::from(7u64),
+            max_priority_fee_per_gas: U256::from(2_000_000_000u64),
+            max_fee_per_gas: U256::from(30_000_000_000u64),
+            gas_limit: U256::from(100_000u64),
+            to: Some(Address([1u8; 20])),
+            value: U256::from(1_000u64),
+            data: vec![1, 2, 3],
+            access_list: vec![],
+            sign: None,
+        }
+    }
+
+    #[test]
+    fn bump_rounds_up() -> Result<(), Box<dyn Error>> {
+        assert_eq!(bump_fee(&U256::from(15u64), 10)?, U256::from(17u64));
+        assert_eq!(bump_fee(&U256::from(100u64), 10)?, U256::from(110u64));
+        assert!(bump_fee(&U256([0xffu8; 32]), 10).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn speed_up_1559() -> Result<(), Box<dyn Error>> {
+        let tx = Transaction::EIP1559(transaction());
+
+        let fees = ReplacementFees {
+            max_fee_per_gas: Some(U256::from(50_000_000_000u64)),
+            max_priority_fee_per_gas: Some(U256::from(1_000_000_000u64)),
+            ..Default::default()
+        };
+        let replacement = Replacement::speed_up(&tx, &fees)?;
+
+        match &replacement.transaction {
+            Transaction::EIP1559(a) => {
+                assert_eq!(a.nonce, U256::from(7u64));
+                assert_eq!(a.max_priority_fee_per_gas, U256::from(2_200_000_000u64));
+                assert_eq!(a.max_fee_per_gas, U256::from(50_000_000_000u64));
+                assert_eq!(a.data, vec![1, 2, 3]);
+            }
+            _ => panic!("Transaction type changed"),
+        }
+        assert_eq!(
+            replacement.signing_hash,
+            keccak256(&[&replacement.transaction.encode(true)])
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn cancel_signed_legacy() -> Result<(), Box<dyn Error>> {
+        let raw = hex::decode("f86e8302511e85036e1d083a826b6c948f2d10257ebf6386426456de1b1792b507426548875319b3e6ceb7bf8025a06716fc3c5bebebe88e61bc25714647b262904f7c99bd69c25541c7a796a9727fa071908b9fc3ce08f164cf1844ce43864a9347b7820a8921eef7aa67c55399e0be")?;
+        let tx = Transaction::decode(&raw)?;
+        let from = tx.signature().unwrap().from.clone().unwrap();
+
+        let replacement = Replacement::cancel(&tx, &from, &ReplacementFees::default())?;
+
+        match &replacement.transaction {
+            Transaction::Legacy(a) => {
+                assert_eq!(a.chain_id, 1);
+                assert_eq!(a.nonce, U256::from(0x2511eu64));
+                assert_eq!(a.gas_price, U256::from(16_205_528_128u64));
+                assert_eq!(a.gas_limit, U256::from(21_000u64));
+                assert_eq!(format!("{}", a.to.as_ref().unwrap()), format!("{from}"));
+                assert!(a.value.is_zero());
+                assert!(a.data.is_empty());
+                assert!(a.sign.is_none());
+            }
+            _ => panic!("Transaction type changed"),
+        }
+
+        Ok(())
+    }
+}
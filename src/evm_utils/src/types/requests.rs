@@ -0,0 +1,206 @@
+use std::error::Error;
+
+use ic_cdk::export::candid::{CandidType, Deserialize};
+use rlp::DecoderError;
+use sha2::{Digest, Sha256};
+
+use super::{address::Address, num::H256, num::U256};
+
+pub const DEPOSIT_REQUEST_TYPE: u8 = 0x00;
+pub const WITHDRAWAL_REQUEST_TYPE: u8 = 0x01;
+pub const CONSOLIDATION_REQUEST_TYPE: u8 = 0x02;
+
+const PUBKEY_SIZE: usize = 48;
+const SIGNATURE_SIZE: usize = 96;
+
+/// Validator deposit processed by the execution layer (EIP-6110)
+#[derive(CandidType, Deserialize, Clone, PartialEq, Eq)]
+pub struct DepositRequest {
+    pub pubkey: Vec<u8>,
+    pub withdrawal_credentials: H256,
+    /// Amount in gwei
+    pub amount: u64,
+    pub signature: Vec<u8>,
+    pub index: u64,
+}
+
+/// Withdrawal triggered from the execution layer (EIP-7002)
+#[derive(CandidType, Deserialize, Clone, PartialEq, Eq)]
+pub struct WithdrawalRequest {
+    pub source_address: Address,
+    pub validator_pubkey: Vec<u8>,
+    /// Amount in gwei
+    pub amount: u64,
+}
+
+/// Consolidation of two validators triggered from the execution layer (EIP-7251)
+#[derive(CandidType, Deserialize, Clone, PartialEq, Eq)]
+pub struct ConsolidationRequest {
+    pub source_address: Address,
+    pub source_pubkey: Vec<u8>,
+    pub target_pubkey: Vec<u8>,
+}
+
+/// Execution layer requests included in a block (EIP-7685)
+#[derive(CandidType, Deserialize, Clone, PartialEq, Eq, Default)]
+pub struct ExecutionRequests {
+    pub deposits: Vec<DepositRequest>,
+    pub withdrawals: Vec<WithdrawalRequest>,
+    pub consolidations: Vec<ConsolidationRequest>,
+}
+
+fn check_size(data: &[u8], size: usize) -> Result<(), Box<dyn Error>> {
+    if data.len() != size {
+        return Err(Box::new(DecoderError::Custom(
+            "Invalid number of bytes for request field",
+        )));
+    }
+
+    Ok(())
+}
+
+impl DepositRequest {
+    /// Encodes request data, integers are little endian as in the deposit contract
+    pub fn encode(&self, buf: &mut Vec<u8>) -> Result<(), Box<dyn Error>> {
+        check_size(&self.pubkey, PUBKEY_SIZE)?;
+        check_size(&self.signature, SIGNATURE_SIZE)?;
+
+        buf.extend_from_slice(&self.pubkey);
+        buf.extend_from_slice(&self.withdrawal_credentials.0);
+        buf.extend_from_slice(&self.amount.to_le_bytes());
+        buf.extend_from_slice(&self.signature);
+        buf.extend_from_slice(&self.index.to_le_bytes());
+
+        Ok(())
+    }
+}
+
+impl WithdrawalRequest {
+    pub fn encode(&self, buf: &mut Vec<u8>) -> Result<(), Box<dyn Error>> {
+        check_size(&self.validator_pubkey, PUBKEY_SIZE)?;
+
+        buf.extend_from_slice(&self.source_address.0);
+        buf.extend_from_slice(&self.validator_pubkey);
+        buf.extend_from_slice(&self.amount.to_le_bytes());
+
+        Ok(())
+    }
+}
+
+impl ConsolidationRequest {
+    pub fn encode(&self, buf: &mut Vec<u8>) -> Result<(), Box<dyn Error>> {
+        check_size(&self.source_pubkey, PUBKEY_SIZE)?;
+        check_size(&self.target_pubkey, PUBKEY_SIZE)?;
+
+        buf.extend_from_slice(&self.source_address.0);
+        buf.extend_from_slice(&self.source_pubkey);
+        buf.extend_from_slice(&self.target_pubkey);
+
+        Ok(())
+    }
+}
+
+impl ExecutionRequests {
+    /// Returns requests as type prefixed byte lists, types without requests are skipped
+    pub fn encode(&self) -> Result<Vec<Vec<u8>>, Box<dyn Error>> {
+        let mut deposits = vec![DEPOSIT_REQUEST_TYPE];
+        for item in &self.deposits {
+            item.encode(&mut deposits)?;
+        }
+
+        let mut withdrawals = vec![WITHDRAWAL_REQUEST_TYPE];
+        for item in &self.withdrawals {
+            item.encode(&mut withdrawals)?;
+        }
+
+        let mut consolidations = vec![CONSOLIDATION_REQUEST_TYPE];
+        for item in &self.consolidations {
+            item.encode(&mut consolidations)?;
+        }
+
+        Ok([deposits, withdrawals, consolidations]
+            .into_iter()
+            .filter(|x| x.len() > 1)
+            .collect())
+    }
+
+    /// Calculates requestsHash committed to in block header, sha256(sha256(type ++ data) ++ ...)
+    pub fn hash(&self) -> Result<U256, Box<dyn Error>> {
+        let mut hasher = Sha256::new();
+        for item in self.encode()? {
+            hasher.update(Sha256::digest(&item));
+        }
+
+        Ok(U256::from(hasher.finalize().as_ref()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::error::Error;
+
+    use sha2::{Digest, Sha256};
+
+    use super::{ConsolidationRequest, DepositRequest, ExecutionRequests};
+    use crate::types::{address::Address, num::H256};
+
+    #[test]
+    fn empty_requests_hash() -> Result<(), Box<dyn Error>> {
+        // requestsHash of Prague blocks without requests, sha256 of empty input
+        let hash = ExecutionRequests::default().hash()?;
+
+        assert_eq!(
+            format!("{hash}"),
+            "0xe3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn requests_hash() -> Result<(), Box<dyn Error>> {
+        let requests = ExecutionRequests {
+            deposits: vec![DepositRequest {
+                pubkey: vec![1u8; 48],
+                withdrawal_credentials: H256([2u8; 32]),
+                amount: 32_000_000_000,
+                signature: vec![3u8; 96],
+                index: 7,
+            }],
+            withdrawals: vec![],
+            consolidations: vec![ConsolidationRequest {
+                source_address: Address([4u8; 20]),
+                source_pubkey: vec![5u8; 48],
+                target_pubkey: vec![6u8; 48],
+            }],
+        };
+
+        // expected values are computed from EIP-6110 and EIP-7685 definitions, not by this code
+        let encoded = requests.encode()?;
+        assert_eq!(encoded.len(), 2);
+        assert_eq!(
+            hex::encode(Sha256::digest(&encoded[0])),
+            "86cc73fe77c2f18014653b35497f67084e10beaaebc04562f55b361f24f15c68"
+        );
+        assert_eq!(
+            format!("{}", requests.hash()?),
+            "0x4cd303aa35cc6b802abb86ebc89161432f8b4cfa0d1b02b7d6e333659f98c10b"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_pubkey() {
+        let requests = ExecutionRequests {
+            consolidations: vec![ConsolidationRequest {
+                source_address: Address([4u8; 20]),
+                source_pubkey: vec![5u8; 47],
+                target_pubkey: vec![6u8; 48],
+            }],
+            ..Default::default()
+        };
+
+        assert!(requests.hash().is_err());
+    }
+}
@@ -0,0 +1,194 @@
+use ic_cdk::export::candid::{CandidType, Deserialize};
+
+use super::{
+    abi::{self, AbiValue, ErrorDefinition, ParamType},
+    errors::AbiError,
+    num::U256,
+};
+
+/// Selector of `Error(string)`, used by `require` and `revert` with a message
+pub const ERROR_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+/// Selector of `Panic(uint256)`, used by failed assertions and checked arithmetic
+pub const PANIC_SELECTOR: [u8; 4] = [0x4e, 0x48, 0x7b, 0x71];
+
+/// Parameter of a custom error
+#[derive(CandidType, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct DecodedParam {
+    pub name: String,
+    /// Solidity type, like `uint256` or `(address,bytes)[]`
+    pub kind: String,
+    pub value: AbiValue,
+}
+
+/// Decoded data returned by a failed call
+#[derive(CandidType, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub enum Revert {
+    /// Revert without data, like `revert()` or `require` without a message
+    Empty,
+    Error(String),
+    Panic {
+        code: U256,
+        description: String,
+    },
+    Custom {
+        name: String,
+        signature: String,
+        params: Vec<DecodedParam>,
+    },
+    /// Selector does not match any of known errors
+    Unknown {
+        selector: Vec<u8>,
+        data: Vec<u8>,
+    },
+}
+
+impl Revert {
+    /// Decodes revert data, custom errors are matched against given definitions
+    pub fn decode(data: &[u8], errors: &[ErrorDefinition]) -> Result<Self, AbiError> {
+        if data.is_empty() {
+            return Ok(Revert::Empty);
+        }
+
+        let (selector, params) = data.split_at(data.len().min(4));
+        if selector == ERROR_SELECTOR {
+            return match abi::decode(&[ParamType::String], params)?.pop() {
+                Some(AbiValue::String(x)) => Ok(Revert::Error(x)),
+                _ => Err(AbiError::InvalidData),
+            };
+        }
+
+        if selector == PANIC_SELECTOR {
+            return match abi::decode(&[ParamType::Uint(256)], params)?.pop() {
+                Some(AbiValue::Uint(code)) => Ok(Revert::Panic {
+                    description: panic_description(&code).to_string(),
+                    code,
+                }),
+                _ => Err(AbiError::InvalidData),
+            };
+        }
+
+        let error = match errors.iter().find(|x| x.selector() == selector) {
+            Some(x) => x,
+            None => {
+                return Ok(Revert::Unknown {
+                    selector: selector.to_vec(),
+                    data: params.to_vec(),
+                })
+            }
+        };
+
+        let types: Vec<ParamType> = error.inputs.iter().map(|x| x.kind.clone()).collect();
+        let values = abi::decode(&types, params)?;
+
+        Ok(Revert::Custom {
+            name: error.name.clone(),
+            signature: error.signature(),
+            params: error
+                .inputs
+                .iter()
+                .zip(values)
+                .map(|(param, value)| DecodedParam {
+                    name: param.name.clone(),
+                    kind: param.kind.signature(),
+                    value,
+                })
+                .collect(),
+        })
+    }
+}
+
+/// Describes panic code emitted by Solidity compiler
+pub fn panic_description(code: &U256) -> &'static str {
+    match code.as_u64() {
+        Some(0x00) => "Generic compiler inserted panic",
+        Some(0x01) => "Assertion failed",
+        Some(0x11) => "Arithmetic overflow or underflow",
+        Some(0x12) => "Division or modulo by zero",
+        Some(0x21) => "Conversion of invalid value to enum",
+        Some(0x22) => "Access to incorrectly encoded storage byte array",
+        Some(0x31) => "Pop from empty array",
+        Some(0x32) => "Array index out of bounds",
+        Some(0x41) => "Too much memory allocated",
+        Some(0x51) => "Call to zero initialized function",
+        _ => "Unknown panic code",
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::error::Error;
+
+    use super::{DecodedParam, Revert};
+    use crate::types::{abi::AbiValue, abi::ErrorDefinition, num::U256};
+
+    #[test]
+    fn error_string() -> Result<(), Box<dyn Error>> {
+        let data = hex::decode("08c379a0000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000000124e6f7420656e6f7567682062616c616e63650000000000000000000000000000")?;
+
+        assert_eq!(
+            Revert::decode(&data, &[])?,
+            Revert::Error("Not enough balance".to_string())
+        );
+        assert!(Revert::decode(&data[..40], &[]).is_err());
+        assert_eq!(Revert::decode(&[], &[])?, Revert::Empty);
+
+        Ok(())
+    }
+
+    #[test]
+    fn panic() -> Result<(), Box<dyn Error>> {
+        let data = hex::decode(
+            "4e487b710000000000000000000000000000000000000000000000000000000000000011",
+        )?;
+
+        assert_eq!(
+            Revert::decode(&data, &[])?,
+            Revert::Panic {
+                code: U256::from(0x11),
+                description: "Arithmetic overflow or underflow".to_string()
+            }
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn custom_error() -> Result<(), Box<dyn Error>> {
+        let abi = r#"[{"type": "error", "name": "InsufficientBalance", "inputs": [
+            {"name": "available", "type": "uint256"},
+            {"name": "required", "type": "uint256"}
+        ]}]"#;
+        let errors = ErrorDefinition::parse_all(abi)?;
+        let data = hex::decode("cf47918100000000000000000000000000000000000000000000000000000000000000070000000000000000000000000000000000000000000000000000000000000009")?;
+
+        assert_eq!(
+            Revert::decode(&data, &errors)?,
+            Revert::Custom {
+                name: "InsufficientBalance".to_string(),
+                signature: "InsufficientBalance(uint256,uint256)".to_string(),
+                params: vec![
+                    DecodedParam {
+                        name: "available".to_string(),
+                        kind: "uint256".to_string(),
+                        value: AbiValue::Uint(U256::from(7)),
+                    },
+                    DecodedParam {
+                        name: "required".to_string(),
+                        kind: "uint256".to_string(),
+                        value: AbiValue::Uint(U256::from(9)),
+                    },
+                ],
+            }
+        );
+
+        assert_eq!(
+            Revert::decode(&data, &[])?,
+            Revert::Unknown {
+                selector: data[..4].to_vec(),
+                data: data[4..].to_vec()
+            }
+        );
+
+        Ok(())
+    }
+}
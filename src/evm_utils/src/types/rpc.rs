@@ -0,0 +1,777 @@
+use std::error::Error;
+
+use ic_cdk::export::candid::{CandidType, Deserialize};
+use serde_json::{json, Value};
+
+use super::{
+    access_list::AccessList,
+    address::Address,
+    errors::RpcError,
+    log::Log,
+    num::{H256, U256},
+    proof::{AccountProof, StorageProof},
+    receipt::{Receipt, ReceiptOutcome},
+    signature::Signature,
+    transaction::Transaction,
+    transaction_1559::Transaction1559,
+    transaction_2930::Transaction2930,
+    transaction_legacy::TransactionLegacy,
+};
+
+#[derive(CandidType, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub enum BlockTag {
+    Latest,
+    Safe,
+    Finalized,
+    Pending,
+    Earliest,
+    Number(u64),
+}
+
+/// Supported JSON-RPC methods with their parameters
+#[derive(CandidType, Deserialize, Clone, PartialEq)]
+pub enum RpcRequest {
+    GetBalance {
+        address: Address,
+        block: BlockTag,
+    },
+    GetTransactionCount {
+        address: Address,
+        block: BlockTag,
+    },
+    Call {
+        to: Address,
+        from: Option<Address>,
+        data: Vec<u8>,
+        block: BlockTag,
+    },
+    CreateAccessList {
+        to: Option<Address>,
+        from: Option<Address>,
+        data: Vec<u8>,
+        block: BlockTag,
+    },
+    GetProof {
+        address: Address,
+        storage_keys: Vec<H256>,
+        block: BlockTag,
+    },
+    SendRawTransaction {
+        data: Vec<u8>,
+    },
+    GetTransactionByHash {
+        hash: U256,
+    },
+    GetTransactionReceipt {
+        hash: U256,
+    },
+    FeeHistory {
+        block_count: u64,
+        newest_block: BlockTag,
+        reward_percentiles: Vec<f64>,
+    },
+}
+
+/// Access list accessed by a call and gas used with it applied
+#[derive(CandidType, Deserialize, Clone, PartialEq, Eq)]
+pub struct CreatedAccessList {
+    pub access_list: Vec<AccessList>,
+    pub gas_used: U256,
+}
+
+#[derive(CandidType, Deserialize, Clone, PartialEq)]
+pub struct FeeHistory {
+    pub oldest_block: u64,
+    pub base_fee_per_gas: Vec<U256>,
+    pub gas_used_ratio: Vec<f64>,
+    pub reward: Vec<Vec<U256>>,
+}
+
+/// Result of JSON-RPC call, variant depends on the request
+#[derive(CandidType, Deserialize, Clone, PartialEq)]
+pub enum RpcResponse {
+    Balance(U256),
+    TransactionCount(u64),
+    Call(Vec<u8>),
+    AccessList(CreatedAccessList),
+    Proof(AccountProof),
+    TransactionHash(U256),
+    Transaction(Option<Transaction>),
+    Receipt(Option<Receipt>),
+    FeeHistory(FeeHistory),
+}
+
+impl BlockTag {
+    fn to_json(&self) -> Value {
+        match self {
+            BlockTag::Latest => json!("latest"),
+            BlockTag::Safe => json!("safe"),
+            BlockTag::Finalized => json!("finalized"),
+            BlockTag::Pending => json!("pending"),
+            BlockTag::Earliest => json!("earliest"),
+            BlockTag::Number(x) => json!(format!("{x:#x}")),
+        }
+    }
+}
+
+impl RpcRequest {
+    pub fn method(&self) -> &'static str {
+        match self {
+            RpcRequest::GetBalance { .. } => "eth_getBalance",
+            RpcRequest::GetTransactionCount { .. } => "eth_getTransactionCount",
+            RpcRequest::Call { .. } => "eth_call",
+            RpcRequest::CreateAccessList { .. } => "eth_createAccessList",
+            RpcRequest::GetProof { .. } => "eth_getProof",
+            RpcRequest::SendRawTransaction { .. } => "eth_sendRawTransaction",
+            RpcRequest::GetTransactionByHash { .. } => "eth_getTransactionByHash",
+            RpcRequest::GetTransactionReceipt { .. } => "eth_getTransactionReceipt",
+            RpcRequest::FeeHistory { .. } => "eth_feeHistory",
+        }
+    }
+
+    fn params(&self) -> Value {
+        match self {
+            RpcRequest::GetBalance { address, block }
+            | RpcRequest::GetTransactionCount { address, block } => {
+                json!([address.to_string(), block.to_json()])
+            }
+            RpcRequest::Call {
+                to,
+                from,
+                data,
+                block,
+            } => json!([call_object(Some(to), from, data), block.to_json()]),
+            RpcRequest::CreateAccessList {
+                to,
+                from,
+                data,
+                block,
+            } => json!([call_object(to.as_ref(), from, data), block.to_json()]),
+            RpcRequest::GetProof {
+                address,
+                storage_keys,
+                block,
+            } => {
+                let keys: Vec<String> = storage_keys
+                    .iter()
+                    .map(|x| format!("0x{}", hex::encode(x.0)))
+                    .collect();
+
+                json!([address.to_string(), keys, block.to_json()])
+            }
+            RpcRequest::SendRawTransaction { data } => {
+                json!([format!("0x{}", hex::encode(data))])
+            }
+            RpcRequest::GetTransactionByHash { hash }
+            | RpcRequest::GetTransactionReceipt { hash } => json!([hash.to_string()]),
+            RpcRequest::FeeHistory {
+                block_count,
+                newest_block,
+                reward_percentiles,
+            } => json!([
+                format!("{block_count:#x}"),
+                newest_block.to_json(),
+                reward_percentiles
+            ]),
+        }
+    }
+
+    /// Returns JSON-RPC payload of the request
+    pub fn to_json(&self, id: u64) -> Vec<u8> {
+        let body = json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": self.method(),
+            "params": self.params(),
+        });
+
+        body.to_string().into_bytes()
+    }
+
+    /// Parses JSON-RPC response of the request
+    pub fn parse_response(&self, body: &[u8]) -> Result<RpcResponse, Box<dyn Error>> {
+        let result = result(body)?;
+
+        let response = match self {
+            RpcRequest::GetBalance { .. } => RpcResponse::Balance(to_u256(&result)?),
+            RpcRequest::GetTransactionCount { .. } => {
+                RpcResponse::TransactionCount(to_u64(&result)?)
+            }
+            RpcRequest::Call { .. } => RpcResponse::Call(to_bytes(&result)?),
+            RpcRequest::CreateAccessList { .. } => {
+                RpcResponse::AccessList(to_created_access_list(&result)?)
+            }
+            RpcRequest::GetProof { .. } => RpcResponse::Proof(to_proof(&result)?),
+            RpcRequest::SendRawTransaction { .. } => {
+                RpcResponse::TransactionHash(to_u256(&result)?)
+            }
+            RpcRequest::GetTransactionByHash { .. } => RpcResponse::Transaction(match result {
+                Value::Null => None,
+                x => Some(to_transaction(&x)?),
+            }),
+            RpcRequest::GetTransactionReceipt { .. } => RpcResponse::Receipt(match result {
+                Value::Null => None,
+                x => Some(to_receipt(&x)?),
+            }),
+            RpcRequest::FeeHistory { .. } => RpcResponse::FeeHistory(to_fee_history(&result)?),
+        };
+
+        Ok(response)
+    }
+}
+
+/// Transaction call object of eth_call and eth_createAccessList
+fn call_object(to: Option<&Address>, from: &Option<Address>, data: &[u8]) -> Value {
+    let mut call = json!({ "data": format!("0x{}", hex::encode(data)) });
+    if let Some(to) = to {
+        call["to"] = json!(to.to_string());
+    }
+    if let Some(from) = from {
+        call["from"] = json!(from.to_string());
+    }
+
+    call
+}
+
+/// Returns result of JSON-RPC response, or error returned by the node
+pub fn result(body: &[u8]) -> Result<Value, RpcError> {
+    let mut body: Value = serde_json::from_slice(body)
+        .map_err(|_| RpcError::InvalidResponse("Response is not a valid JSON"))?;
+
+    if let Some(error) = body.get("error") {
+        return Err(RpcError::Response {
+            code: error.get("code").and_then(|x| x.as_i64()).unwrap_or(0),
+            message: error
+                .get("message")
+                .and_then(|x| x.as_str())
+                .unwrap_or_default()
+                .to_string(),
+        });
+    }
+
+    body.get_mut("result")
+        .map(|x| x.take())
+        .ok_or(RpcError::InvalidResponse("Missing result"))
+}
+
+fn field<'a>(value: &'a Value, name: &'static str) -> Result<&'a Value, RpcError> {
+    value.get(name).ok_or(RpcError::InvalidResponse(name))
+}
+
+fn to_bytes(value: &Value) -> Result<Vec<u8>, RpcError> {
+    let data = value
+        .as_str()
+        .ok_or(RpcError::InvalidResponse("Expected hex string"))?
+        .trim_start_matches("0x");
+
+    // quantities are encoded without leading zeros
+    let data = if data.len() % 2 == 1 {
+        format!("0{data}")
+    } else {
+        data.to_string()
+    };
+
+    hex::decode(data).map_err(|_| RpcError::InvalidResponse("Invalid hex string"))
+}
+
+fn to_word(value: &Value) -> Result<[u8; 32], RpcError> {
+    let data = to_bytes(value)?;
+    let data = &data[data.iter().take_while(|x| **x == 0).count()..];
+    if data.len() > 32 {
+        return Err(RpcError::InvalidResponse("Value does not fit in 32 bytes"));
+    }
+
+    let mut buf = [0u8; 32];
+    buf[32 - data.len()..].copy_from_slice(data);
+    Ok(buf)
+}
+
+fn to_u256(value: &Value) -> Result<U256, RpcError> {
+    Ok(U256(to_word(value)?))
+}
+
+fn to_h256(value: &Value) -> Result<H256, RpcError> {
+    Ok(H256(to_word(value)?))
+}
+
+fn to_u64(value: &Value) -> Result<u64, RpcError> {
+    to_u256(value)?
+        .as_u64()
+        .ok_or(RpcError::InvalidResponse("Value does not fit in 64 bits"))
+}
+
+fn to_address(value: &Value) -> Result<Address, RpcError> {
+    let data = to_bytes(value)?;
+    if data.len() != 20 {
+        return Err(RpcError::InvalidResponse("Invalid address"));
+    }
+
+    let mut buf = [0u8; 20];
+    buf.copy_from_slice(&data);
+    Ok(Address(buf))
+}
+
+fn to_list<T>(
+    value: &Value,
+    f: impl Fn(&Value) -> Result<T, RpcError>,
+) -> Result<Vec<T>, RpcError> {
+    value
+        .as_array()
+        .ok_or(RpcError::InvalidResponse("Expected array"))?
+        .iter()
+        .map(f)
+        .collect()
+}
+
+/// Integers like r and s are stored in transactions without leading zeros
+fn to_scalar(value: &Value) -> Result<Vec<u8>, RpcError> {
+    let data = to_bytes(value)?;
+    let zeros = data.iter().take_while(|x| **x == 0).count();
+
+    Ok(data[zeros..].to_vec())
+}
+
+fn to_proof(value: &Value) -> Result<AccountProof, RpcError> {
+    let storage_proof = to_list(field(value, "storageProof")?, |x| {
+        Ok(StorageProof {
+            key: to_h256(field(x, "key")?)?,
+            value: to_u256(field(x, "value")?)?,
+            proof: to_list(field(x, "proof")?, to_bytes)?,
+        })
+    })?;
+
+    Ok(AccountProof {
+        address: to_address(field(value, "address")?)?,
+        balance: to_u256(field(value, "balance")?)?,
+        code_hash: to_h256(field(value, "codeHash")?)?,
+        nonce: to_u256(field(value, "nonce")?)?,
+        storage_hash: to_h256(field(value, "storageHash")?)?,
+        account_proof: to_list(field(value, "accountProof")?, to_bytes)?,
+        storage_proof,
+    })
+}
+
+fn to_access_list(value: &Value) -> Result<Vec<AccessList>, RpcError> {
+    to_list(value, |x| {
+        Ok(AccessList {
+            address: to_address(field(x, "address")?)?,
+            storage_keys: to_list(field(x, "storageKeys")?, to_h256)?,
+        })
+    })
+}
+
+/// Converts eth_createAccessList result, returned access list is normalized
+pub fn to_created_access_list(value: &Value) -> Result<CreatedAccessList, RpcError> {
+    if let Some(error) = value.get("error").and_then(|x| x.as_str()) {
+        return Err(RpcError::Response {
+            code: 0,
+            message: error.to_string(),
+        });
+    }
+
+    Ok(CreatedAccessList {
+        access_list: AccessList::normalize(to_access_list(field(value, "accessList")?)?),
+        gas_used: to_u256(field(value, "gasUsed")?)?,
+    })
+}
+
+fn to_transaction(value: &Value) -> Result<Transaction, RpcError> {
+    let tx_type = match value.get("type") {
+        Some(x) => to_u64(x)?,
+        None => 0,
+    };
+
+    let to = match field(value, "to")? {
+        Value::Null => None,
+        x => Some(to_address(x)?),
+    };
+
+    // sender and hash are derived from the transaction, values claimed by the node are
+    // only compared with them
+    let from = to_address(field(value, "from")?)?;
+    let hash = to_u256(field(value, "hash")?)?;
+    let sign = Some(Signature {
+        v: to_u64(field(value, "v")?)?,
+        r: to_scalar(field(value, "r")?)?,
+        s: to_scalar(field(value, "s")?)?,
+        from: None,
+        hash: U256::zero(),
+    });
+
+    let nonce = to_u256(field(value, "nonce")?)?;
+    let gas_limit = to_u256(field(value, "gas")?)?;
+    let value_ = to_u256(field(value, "value")?)?;
+    let data = to_bytes(field(value, "input")?)?;
+
+    let tx = match tx_type {
+        0 => {
+            let v = to_u64(field(value, "v")?)?;
+            let chain_id = match value.get("chainId") {
+                Some(x) if !x.is_null() => to_u64(x)?,
+                _ if v >= 35 => (v - 35) / 2,
+                _ => 0,
+            };
+
+            Transaction::Legacy(TransactionLegacy {
+                chain_id,
+                nonce,
+                gas_price: to_u256(field(value, "gasPrice")?)?,
+                gas_limit,
+                to,
+                value: value_,
+                data,
+                sign,
+            })
+        }
+        1 => Transaction::EIP2930(Transaction2930 {
+            chain_id: to_u64(field(value, "chainId")?)?,
+            nonce,
+            gas_price: to_u256(field(value, "gasPrice")?)?,
+            gas_limit,
+            to,
+            value: value_,
+            data,
+            access_list: to_access_list(field(value, "accessList")?)?,
+            sign,
+        }),
+        2 => Transaction::EIP1559(Transaction1559 {
+            chain_id: to_u64(field(value, "chainId")?)?,
+            nonce,
+            max_priority_fee_per_gas: to_u256(field(value, "maxPriorityFeePerGas")?)?,
+            max_fee_per_gas: to_u256(field(value, "maxFeePerGas")?)?,
+            gas_limit,
+            to,
+            value: value_,
+            data,
+            access_list: to_access_list(field(value, "accessList")?)?,
+            sign,
+        }),
+        _ => return Err(RpcError::InvalidResponse("Unsupported transaction type")),
+    };
+
+    let tx = Transaction::decode(&tx.encode(false))
+        .map_err(|_| RpcError::InvalidResponse("Invalid transaction signature"))?;
+    let sign = tx
+        .signature()
+        .ok_or(RpcError::InvalidResponse("Missing transaction signature"))?;
+
+    if sign.from.as_ref() != Some(&from) {
+        return Err(RpcError::InvalidResponse("Sender does not match signature"));
+    }
+    if sign.hash != hash {
+        return Err(RpcError::InvalidResponse("Hash does not match transaction"));
+    }
+
+    Ok(tx)
+}
+
+fn to_receipt(value: &Value) -> Result<Receipt, RpcError> {
+    let tx_type = match value.get("type") {
+        Some(x) => u8::try_from(to_u64(x)?)
+            .map_err(|_| RpcError::InvalidResponse("Invalid receipt type"))?,
+        None => 0,
+    };
+
+    let outcome = match value.get("status") {
+        Some(x) if !x.is_null() => ReceiptOutcome::Status(to_u64(x)? == 1),
+        _ => ReceiptOutcome::PostState(to_h256(field(value, "root")?)?),
+    };
+
+    let logs = to_list(field(value, "logs")?, |x| {
+        Ok(Log {
+            address: to_address(field(x, "address")?)?,
+            topics: to_list(field(x, "topics")?, to_h256)?,
+            data: to_bytes(field(x, "data")?)?,
+        })
+    })?;
+
+    Ok(Receipt {
+        tx_type,
+        outcome,
+        cumulative_gas_used: to_u256(field(value, "cumulativeGasUsed")?)?,
+        logs_bloom: to_bytes(field(value, "logsBloom")?)?,
+        logs,
+    })
+}
+
+fn to_fee_history(value: &Value) -> Result<FeeHistory, RpcError> {
+    let gas_used_ratio = to_list(field(value, "gasUsedRatio")?, |x| {
+        x.as_f64()
+            .ok_or(RpcError::InvalidResponse("Expected number"))
+    })?;
+
+    let reward = match value.get("reward") {
+        Some(x) if !x.is_null() => to_list(x, |x| to_list(x, to_u256))?,
+        _ => vec![],
+    };
+
+    Ok(FeeHistory {
+        oldest_block: to_u64(field(value, "oldestBlock")?)?,
+        base_fee_per_gas: to_list(field(value, "baseFeePerGas")?, to_u256)?,
+        gas_used_ratio,
+        reward,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use std::error::Error;
+    use std::str::FromStr;
+
+    use hasher::HasherKeccak;
+    use serde_json::Value;
+
+    use super::{BlockTag, RpcRequest, RpcResponse};
+    use crate::{
+        types::{
+            address::Address,
+            bloom::Bloom,
+            num::{H256, U256},
+            transaction::Transaction,
+        },
+        utils::keccak256,
+    };
+
+    fn hash(data: &str) -> U256 {
+        let mut buf = [0u8; 32];
+        hex::decode_to_slice(data.trim_start_matches("0x"), &mut buf).unwrap();
+        U256(buf)
+    }
+
+    #[test]
+    fn build_request() -> Result<(), Box<dyn Error>> {
+        let req = RpcRequest::GetProof {
+            address: Address::from_str("0xcca577ee56d30a444c73f8fc8d5ce34ed1c7da8b")?,
+            storage_keys: vec![H256::zero()],
+            block: BlockTag::Number(0x10c4d6a),
+        };
+
+        let body: Value = serde_json::from_slice(&req.to_json(7))?;
+
+        assert_eq!(body["id"], 7);
+        assert_eq!(body["method"], "eth_getProof");
+        assert_eq!(
+            body["params"][0],
+            "0xcca577ee56d30a444c73f8fc8d5ce34ed1c7da8b"
+        );
+        assert_eq!(
+            body["params"][1][0],
+            "0x0000000000000000000000000000000000000000000000000000000000000000"
+        );
+        assert_eq!(body["params"][2], "0x10c4d6a");
+
+        let req = RpcRequest::Call {
+            to: Address([1u8; 20]),
+            from: None,
+            data: vec![0x70, 0xa0, 0x82, 0x31],
+            block: BlockTag::Latest,
+        };
+        let body: Value = serde_json::from_slice(&req.to_json(1))?;
+
+        assert_eq!(body["params"][0]["data"], "0x70a08231");
+        assert!(body["params"][0].get("from").is_none());
+        assert_eq!(body["params"][1], "latest");
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_balance_and_call() -> Result<(), Box<dyn Error>> {
+        let req = RpcRequest::GetBalance {
+            address: Address([1u8; 20]),
+            block: BlockTag::Latest,
+        };
+        match req.parse_response(include_bytes!("../../fixtures/rpc/get_balance.json"))? {
+            RpcResponse::Balance(x) => assert_eq!(x, U256::from(0x0234c8a3397aab58u64)),
+            _ => panic!("Wrong response type"),
+        }
+
+        let req = RpcRequest::Call {
+            to: Address([1u8; 20]),
+            from: None,
+            data: vec![],
+            block: BlockTag::Latest,
+        };
+        match req.parse_response(include_bytes!("../../fixtures/rpc/call.json"))? {
+            RpcResponse::Call(x) => assert_eq!(x, U256::from(1_000_000_000_000_000_000u64).0),
+            _ => panic!("Wrong response type"),
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_access_list() -> Result<(), Box<dyn Error>> {
+        let req = RpcRequest::CreateAccessList {
+            to: Some(Address([1u8; 20])),
+            from: None,
+            data: vec![],
+            block: BlockTag::Latest,
+        };
+
+        let list = match req
+            .parse_response(include_bytes!("../../fixtures/rpc/create_access_list.json"))?
+        {
+            RpcResponse::AccessList(x) => x,
+            _ => panic!("Wrong response type"),
+        };
+
+        assert_eq!(list.gas_used, U256::from(0xa3b4u64));
+        assert_eq!(list.access_list.len(), 2);
+        assert_eq!(
+            format!("{}", list.access_list[0].address),
+            "0x5b1578681d43931030fffe066a072133842dde43"
+        );
+        assert_eq!(list.access_list[1].storage_keys.len(), 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_error() {
+        let req = RpcRequest::SendRawTransaction { data: vec![] };
+
+        let err = req
+            .parse_response(include_bytes!("../../fixtures/rpc/error.json"))
+            .err()
+            .unwrap();
+
+        assert!(format!("{err}").contains("nonce too low"));
+    }
+
+    #[test]
+    fn parse_proof() -> Result<(), Box<dyn Error>> {
+        let req = RpcRequest::GetProof {
+            address: Address([1u8; 20]),
+            storage_keys: vec![H256::zero()],
+            block: BlockTag::Latest,
+        };
+
+        let proof = match req.parse_response(include_bytes!("../../../../__tests__/proof.json"))? {
+            RpcResponse::Proof(x) => x,
+            _ => panic!("Wrong response type"),
+        };
+
+        assert_eq!(
+            format!("{}", proof.address),
+            "0xcca577ee56d30a444c73f8fc8d5ce34ed1c7da8b"
+        );
+        assert_eq!(proof.account_proof.len(), 10);
+
+        let storage = &proof.storage_proof[0];
+        let value = cita_trie::verify_proof(
+            &proof.storage_hash.0,
+            &keccak256(&[&storage.key.0]).0,
+            storage.proof.clone(),
+            HasherKeccak::new(),
+        )?
+        .unwrap();
+
+        let value: U256 = rlp::decode(&value)?;
+        assert_eq!(value, storage.value);
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_transactions() -> Result<(), Box<dyn Error>> {
+        let req = RpcRequest::GetTransactionByHash { hash: U256::zero() };
+
+        for (body, raw) in [
+            (
+                &include_bytes!("../../fixtures/rpc/get_transaction_eip1559.json")[..],
+                "02f8b10108840f7f4900850519f0118083055845943fe65692bfcd0e6cf84cb1e7d24108e434a7587e80b8447050ccd90000000000000000000000005b1578681d43931030fffe066a072133842dde430000000000000000000000000000000000000000000000000000000000000001c001a0cac74d8e73874331e4ba78809a7b30574c76d35c43ea00983c76752b65a0632aa04b6caeac4c693f082b8bd4d2ad349848bc71b9d32a419e33a5a303a3b3f4e8c8",
+            ),
+            (
+                &include_bytes!("../../fixtures/rpc/get_transaction_legacy.json")[..],
+                "f86e8302511e85036e1d083a826b6c948f2d10257ebf6386426456de1b1792b507426548875319b3e6ceb7bf8025a06716fc3c5bebebe88e61bc25714647b262904f7c99bd69c25541c7a796a9727fa071908b9fc3ce08f164cf1844ce43864a9347b7820a8921eef7aa67c55399e0be",
+            ),
+        ] {
+            let tx = match req.parse_response(body)? {
+                RpcResponse::Transaction(Some(x)) => x,
+                _ => panic!("Wrong response type"),
+            };
+
+            let raw = hex::decode(raw)?;
+            assert_eq!(tx.encode(false).to_vec(), raw);
+            assert!(tx == Transaction::decode(&raw)?);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn reject_forged_transaction() {
+        let req = RpcRequest::GetTransactionByHash { hash: U256::zero() };
+        let body = std::str::from_utf8(include_bytes!(
+            "../../fixtures/rpc/get_transaction_legacy.json"
+        ))
+        .unwrap();
+
+        for forged in [
+            body.replace(
+                "0x690b9a9e9aa1c9db991c7721a92d351db4fac990",
+                "0x0000000000000000000000000000000000000001",
+            ),
+            body.replace(
+                "0xd103e725e13c9886eb787517e47647010d077b51bc3a0a8b7ae7fc5a9cf351e2",
+                "0x0000000000000000000000000000000000000000000000000000000000000001",
+            ),
+            body.replace("\"value\": \"0x5319b3e6ceb7bf\"", "\"value\": \"0x1\""),
+        ] {
+            assert!(req.parse_response(forged.as_bytes()).is_err());
+        }
+    }
+
+    #[test]
+    fn parse_receipt() -> Result<(), Box<dyn Error>> {
+        let req = RpcRequest::GetTransactionReceipt { hash: U256::zero() };
+
+        let receipt = match req.parse_response(include_bytes!(
+            "../../fixtures/rpc/get_transaction_receipt.json"
+        ))? {
+            RpcResponse::Receipt(Some(x)) => x,
+            _ => panic!("Wrong response type"),
+        };
+
+        assert_eq!(receipt.tx_type, 2);
+        assert_eq!(receipt.logs.len(), 1);
+        assert_eq!(receipt.logs[0].topics.len(), 3);
+        assert_eq!(
+            receipt.logs_bloom,
+            Bloom::from_logs(&receipt.logs).0.to_vec()
+        );
+
+        // type is not truncated to 0x02
+        let body = include_str!("../../fixtures/rpc/get_transaction_receipt.json")
+            .replace(r#""type": "0x2""#, r#""type": "0x102""#);
+        assert!(req.parse_response(body.as_bytes()).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_fee_history() -> Result<(), Box<dyn Error>> {
+        let req = RpcRequest::FeeHistory {
+            block_count: 3,
+            newest_block: BlockTag::Latest,
+            reward_percentiles: vec![10.0, 50.0, 90.0],
+        };
+
+        let history =
+            match req.parse_response(include_bytes!("../../fixtures/rpc/fee_history.json"))? {
+                RpcResponse::FeeHistory(x) => x,
+                _ => panic!("Wrong response type"),
+            };
+
+        assert_eq!(history.oldest_block, 0x10c4d68);
+        assert_eq!(history.base_fee_per_gas.len(), 4);
+        assert_eq!(history.reward[1][0], U256::from(100_000_000u64));
+        assert_eq!(
+            history.base_fee_per_gas[0],
+            hash("0x00000000000000000000000000000000000000000000000000000002540be400")
+        );
+
+        Ok(())
+    }
+}
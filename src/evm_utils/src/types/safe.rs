@@ -0,0 +1,376 @@
+use ic_cdk::export::candid::{CandidType, Deserialize};
+
+use super::{
+    abi::{self, AbiValue},
+    address::Address,
+    eip712::{self, Domain},
+    errors::SafeError,
+    num::{H256, U256},
+};
+use crate::utils::{keccak256, personal_message_hash, recover_address, y_parity};
+
+const SAFE_TX_TYPE: &str = "SafeTx(address to,uint256 value,bytes data,uint8 operation,uint256 safeTxGas,uint256 baseGas,uint256 gasPrice,address gasToken,address refundReceiver,uint256 nonce)";
+const EXEC_TRANSACTION: &str =
+    "execTransaction(address,uint256,bytes,uint8,uint256,uint256,uint256,address,address,bytes)";
+const MULTI_SEND: &str = "multiSend(bytes)";
+
+#[derive(CandidType, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Operation {
+    Call,
+    DelegateCall,
+}
+
+impl Operation {
+    fn as_u8(&self) -> u8 {
+        match self {
+            Operation::Call => 0,
+            Operation::DelegateCall => 1,
+        }
+    }
+}
+
+/// Transaction executed by a Safe with `execTransaction`
+#[derive(CandidType, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct SafeTransaction {
+    pub to: Address,
+    pub value: U256,
+    pub data: Vec<u8>,
+    pub operation: Operation,
+    pub safe_tx_gas: U256,
+    pub base_gas: U256,
+    pub gas_price: U256,
+    pub gas_token: Address,
+    pub refund_receiver: Address,
+    /// Current value of `nonce()` of the Safe
+    pub nonce: U256,
+}
+
+impl SafeTransaction {
+    /// `getTransactionHash` of the Safe, domain of Safe 1.3.0 and later (chain id and address)
+    pub fn hash(&self, safe: &Address, chain_id: u64) -> H256 {
+        let hash = eip712::hash_struct(
+            SAFE_TX_TYPE,
+            &[
+                AbiValue::Address(self.to.clone()),
+                AbiValue::Uint(self.value.clone()),
+                AbiValue::FixedBytes(keccak256(&[&self.data]).0.to_vec()),
+                AbiValue::Uint(U256::from(self.operation.as_u8() as u64)),
+                AbiValue::Uint(self.safe_tx_gas.clone()),
+                AbiValue::Uint(self.base_gas.clone()),
+                AbiValue::Uint(self.gas_price.clone()),
+                AbiValue::Address(self.gas_token.clone()),
+                AbiValue::Address(self.refund_receiver.clone()),
+                AbiValue::Uint(self.nonce.clone()),
+            ],
+        );
+
+        let domain = Domain {
+            chain_id: Some(chain_id),
+            verifying_contract: Some(safe.clone()),
+            ..Default::default()
+        };
+
+        eip712::digest(&domain, &hash)
+    }
+
+    /// Call data of `execTransaction`, signatures are given already encoded
+    pub fn exec_transaction(&self, signatures: &[u8]) -> Vec<u8> {
+        let mut data = abi::selector(EXEC_TRANSACTION).to_vec();
+        data.extend(abi::encode(&[
+            AbiValue::Address(self.to.clone()),
+            AbiValue::Uint(self.value.clone()),
+            AbiValue::Bytes(self.data.clone()),
+            AbiValue::Uint(U256::from(self.operation.as_u8() as u64)),
+            AbiValue::Uint(self.safe_tx_gas.clone()),
+            AbiValue::Uint(self.base_gas.clone()),
+            AbiValue::Uint(self.gas_price.clone()),
+            AbiValue::Address(self.gas_token.clone()),
+            AbiValue::Address(self.refund_receiver.clone()),
+            AbiValue::Bytes(signatures.to_vec()),
+        ]));
+        data
+    }
+}
+
+/// Confirmation of a Safe transaction by one of the owners
+#[derive(CandidType, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub enum SafeSignature {
+    /// 65 bytes signature of the transaction hash
+    Ecdsa(Vec<u8>),
+    /// 65 bytes `eth_sign` (EIP-191) signature of the transaction hash
+    EthSign(Vec<u8>),
+    /// Owner approved the hash with `approveHash` or is the sender of the transaction
+    ApprovedHash,
+    /// Signature checked by the owner contract with ERC-1271 `isValidSignature`
+    Contract(Vec<u8>),
+}
+
+#[derive(CandidType, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct OwnerSignature {
+    pub owner: Address,
+    pub signature: SafeSignature,
+}
+
+/// Checks that ECDSA signature of the hash was made by the owner, returns r ++ s and y-parity
+fn ecdsa(owner: &Address, signature: &[u8], hash: &[u8; 32]) -> Result<(Vec<u8>, u8), SafeError> {
+    if signature.len() != 65 {
+        return Err(SafeError::InvalidSignature);
+    }
+
+    match recover_address(signature, hash) {
+        Ok(signer) if signer == *owner => Ok((
+            signature[..64].to_vec(),
+            y_parity(signature[64] as u64) as u8,
+        )),
+        _ => Err(SafeError::InvalidSignature),
+    }
+}
+
+/// Encodes signatures as expected by `checkSignatures` of a Safe: 65 bytes per owner, sorted by
+/// owner address, followed by data of contract signatures. ECDSA signatures are checked to be
+/// made by their owners
+pub fn encode_signatures(hash: &H256, signatures: &[OwnerSignature]) -> Result<Vec<u8>, SafeError> {
+    let mut signatures = signatures.to_vec();
+    signatures.sort_by_key(|x| x.owner.0);
+    if signatures.windows(2).any(|x| x[0].owner == x[1].owner) {
+        return Err(SafeError::DuplicateOwner);
+    }
+
+    let owner_word = |x: &Address| {
+        let mut word = vec![0u8; 12];
+        word.extend(x.0);
+        word
+    };
+
+    let mut encoded = vec![];
+    let mut dynamic = vec![];
+
+    for OwnerSignature { owner, signature } in &signatures {
+        match signature {
+            SafeSignature::Ecdsa(signature) => {
+                let (rs, parity) = ecdsa(owner, signature, &hash.0)?;
+                encoded.extend(rs);
+                encoded.push(27 + parity);
+            }
+            SafeSignature::EthSign(signature) => {
+                // already adjusted signatures have v of 31 or 32
+                let mut signature = signature.clone();
+                if let Some(v) = signature.get_mut(64).filter(|x| **x > 30) {
+                    *v -= 4;
+                }
+
+                let (rs, parity) = ecdsa(owner, &signature, &personal_message_hash(&hash.0).0)?;
+                encoded.extend(rs);
+                encoded.push(31 + parity);
+            }
+            SafeSignature::ApprovedHash => {
+                encoded.extend(owner_word(owner));
+                encoded.extend([0u8; 32]);
+                encoded.push(1);
+            }
+            SafeSignature::Contract(data) => {
+                let offset = signatures.len() * 65 + dynamic.len();
+
+                encoded.extend(owner_word(owner));
+                encoded.extend(U256::from(offset as u64).0);
+                encoded.push(0);
+
+                dynamic.extend(U256::from(data.len() as u64).0);
+                dynamic.extend(data);
+            }
+        }
+    }
+
+    encoded.extend(dynamic);
+    Ok(encoded)
+}
+
+/// Transaction of a MultiSend batch
+#[derive(CandidType, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct MultiSendTransaction {
+    pub operation: Operation,
+    pub to: Address,
+    pub value: U256,
+    pub data: Vec<u8>,
+}
+
+/// Call data of `multiSend(bytes)`, transactions are packed as operation (1 byte), to (20 bytes),
+/// value (32 bytes), data length (32 bytes) and data. Has to be executed by the Safe with
+/// DelegateCall
+pub fn multi_send(transactions: &[MultiSendTransaction]) -> Vec<u8> {
+    let mut packed = vec![];
+    for tx in transactions {
+        packed.push(tx.operation.as_u8());
+        packed.extend(tx.to.0);
+        packed.extend(tx.value.0);
+        packed.extend(U256::from(tx.data.len() as u64).0);
+        packed.extend(&tx.data);
+    }
+
+    let mut data = abi::selector(MULTI_SEND).to_vec();
+    data.extend(abi::encode(&[AbiValue::Bytes(packed)]));
+    data
+}
+
+#[cfg(test)]
+mod test {
+    use std::error::Error;
+
+    use secp256k1::{Message, SecretKey, SECP256K1};
+
+    use super::{
+        encode_signatures, multi_send, MultiSendTransaction, Operation, OwnerSignature,
+        SafeSignature, SafeTransaction,
+    };
+    use crate::{
+        types::{
+            address::Address,
+            errors::SafeError,
+            num::{H256, U256},
+        },
+        utils::personal_message_hash,
+    };
+
+    fn transaction() -> SafeTransaction {
+        SafeTransaction {
+            to: Address([0x11; 20]),
+            value: U256::from(1_000_000_000_000_000_000u64),
+            data: vec![0xde, 0xad, 0xbe, 0xef],
+            operation: Operation::Call,
+            safe_tx_gas: U256::zero(),
+            base_gas: U256::zero(),
+            gas_price: U256::zero(),
+            gas_token: Address([0; 20]),
+            refund_receiver: Address([0; 20]),
+            nonce: U256::from(3u64),
+        }
+    }
+
+    fn sign(key: &SecretKey, hash: &[u8; 32]) -> Result<Vec<u8>, Box<dyn Error>> {
+        let (id, data) = SECP256K1
+            .sign_ecdsa_recoverable(&Message::from_slice(hash)?, key)
+            .serialize_compact();
+
+        let mut signature = data.to_vec();
+        signature.push(27 + id.to_i32() as u8);
+        Ok(signature)
+    }
+
+    #[test]
+    fn transaction_hash() {
+        let hash = transaction().hash(&Address([0x22; 20]), 1);
+
+        assert_eq!(
+            hex::encode(hash.0),
+            "079f03a1baaf812d0005edcaa57fdd3584fcbb65ce66c1891a855010c8396b7b"
+        );
+    }
+
+    #[test]
+    fn exec_transaction() {
+        let data = transaction().exec_transaction(&[1; 65]);
+
+        assert_eq!(data[..4], [0x6a, 0x76, 0x12, 0x02]);
+        // signatures are the last parameter, 65 bytes padded to 96
+        assert_eq!(data[data.len() - 96..data.len() - 31], [1; 65]);
+    }
+
+    #[test]
+    fn signatures() -> Result<(), Box<dyn Error>> {
+        let hash = H256([0x33; 32]);
+        let key = SecretKey::from_slice(&[0x46; 32])?;
+        let owner = Address::from(key.public_key(SECP256K1));
+        let eth_sign_key = SecretKey::from_slice(&[0x47; 32])?;
+        let eth_sign_owner = Address::from(eth_sign_key.public_key(SECP256K1));
+
+        let signature = sign(&key, &hash.0)?;
+        let eth_sign = sign(&eth_sign_key, &personal_message_hash(&hash.0).0)?;
+
+        let mut owners = vec![
+            OwnerSignature {
+                owner: Address([0xff; 20]),
+                signature: SafeSignature::Contract(vec![9; 3]),
+            },
+            OwnerSignature {
+                owner: owner.clone(),
+                signature: SafeSignature::Ecdsa(signature.clone()),
+            },
+            OwnerSignature {
+                owner: eth_sign_owner.clone(),
+                signature: SafeSignature::EthSign(eth_sign.clone()),
+            },
+            OwnerSignature {
+                owner: Address([0; 20]),
+                signature: SafeSignature::ApprovedHash,
+            },
+        ];
+        let encoded = encode_signatures(&hash, &owners)?;
+        assert_eq!(encoded.len(), 4 * 65 + 32 + 3);
+
+        // approved hash of the lowest address goes first
+        assert_eq!(encoded[..64], [0; 64]);
+        assert_eq!(encoded[64], 1);
+
+        let (first, second) = if owner.0 < eth_sign_owner.0 {
+            (65, 130)
+        } else {
+            (130, 65)
+        };
+        assert_eq!(encoded[first..first + 65], signature[..]);
+        assert_eq!(encoded[second..second + 64], eth_sign[..64]);
+        assert_eq!(encoded[second + 64], eth_sign[64] + 4);
+
+        // contract signature points to its data after static part
+        assert_eq!(encoded[195 + 12..195 + 32], [0xff; 20]);
+        assert_eq!(encoded[195 + 62..195 + 64], [1, 4]);
+        assert_eq!(encoded[259], 0);
+        assert_eq!(encoded[260 + 31], 3);
+        assert_eq!(encoded[292..], [9; 3]);
+
+        owners[1].owner = eth_sign_owner;
+        assert!(matches!(
+            encode_signatures(&hash, &owners),
+            Err(SafeError::DuplicateOwner)
+        ));
+
+        owners[1].owner = Address([1; 20]);
+        assert!(matches!(
+            encode_signatures(&hash, &owners),
+            Err(SafeError::InvalidSignature)
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn multi_send_data() {
+        let data = multi_send(&[
+            MultiSendTransaction {
+                operation: Operation::Call,
+                to: Address([0x11; 20]),
+                value: U256::from(5u64),
+                data: vec![],
+            },
+            MultiSendTransaction {
+                operation: Operation::DelegateCall,
+                to: Address([0x22; 20]),
+                value: U256::zero(),
+                data: vec![1, 2],
+            },
+        ]);
+
+        assert_eq!(data[..4], [0x8d, 0x80, 0xff, 0x0a]);
+        // offset and length of packed transactions
+        assert_eq!(data[4 + 31], 0x20);
+        assert_eq!(data[36 + 31], 85 + 87);
+
+        let packed = &data[68..68 + 172];
+        assert_eq!(packed[0], 0);
+        assert_eq!(packed[1..21], [0x11; 20]);
+        assert_eq!(packed[52], 5);
+        assert_eq!(packed[84], 0);
+        assert_eq!(packed[85], 1);
+        assert_eq!(packed[85 + 84], 2);
+        assert_eq!(packed[170..], [1, 2]);
+    }
+}
@@ -27,8 +27,10 @@ impl Signature {
 
         let bytes = tx.get_bytes(true);
 
-        let sender = _recover_public_key(&r, &s, v, &bytes)?;
-        let from = Some(Address::from(sender));
+        // sender is None for invalid signatures, transaction is rejected by `Transaction::decode`
+        let from = _recover_public_key(&r, &s, v, &bytes)
+            .ok()
+            .map(Address::from);
 
         let hash = keccak256(&[msg]);
 
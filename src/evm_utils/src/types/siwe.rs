@@ -0,0 +1,838 @@
+use std::{fmt::Display, str::FromStr};
+
+use candid::Principal;
+use ic_cdk::export::candid::{CandidType, Deserialize};
+
+use super::{address::Address, errors::SiweError};
+use crate::utils::{keccak256, personal_message_hash, recover_address};
+
+const HEADER_SUFFIX: &str = " wants you to sign in with your Ethereum account:";
+const NANOS: u64 = 1_000_000_000;
+/// Pending logins kept per principal, the oldest one is replaced when limit is reached
+pub const MAX_PENDING_PER_PRINCIPAL: usize = 4;
+/// Pending logins kept in total, they are saved in stable memory on upgrade. The oldest one
+/// is replaced when limit is reached, so principals created to fill it can not block logins
+pub const MAX_PENDING: usize = 10_000;
+/// Pending login can be evicted by others once they are prepared, so they have to be short lived
+pub const MAX_LOGIN_TTL: u64 = 300 * NANOS;
+
+/// Sign-In with Ethereum message (EIP-4361), timestamps are kept in RFC 3339 format as
+/// they appear in the signed text
+#[derive(CandidType, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct SiweMessage {
+    pub scheme: Option<String>,
+    pub domain: String,
+    pub address: Address,
+    pub statement: Option<String>,
+    pub uri: String,
+    pub version: String,
+    pub chain_id: u64,
+    pub nonce: String,
+    pub issued_at: String,
+    pub expiration_time: Option<String>,
+    pub not_before: Option<String>,
+    pub request_id: Option<String>,
+    pub resources: Vec<String>,
+}
+
+/// Lines of a message being parsed
+struct Lines<'a> {
+    lines: Vec<&'a str>,
+    pos: usize,
+}
+
+impl<'a> Lines<'a> {
+    fn next(&mut self) -> Result<&'a str, SiweError> {
+        let line = self.lines.get(self.pos).ok_or(SiweError::InvalidFormat)?;
+        self.pos += 1;
+
+        Ok(line)
+    }
+
+    fn field(&mut self, name: &str) -> Result<&'a str, SiweError> {
+        self.next()?
+            .strip_prefix(name)
+            .ok_or(SiweError::InvalidFormat)
+    }
+
+    fn optional(&mut self, name: &str) -> Option<&'a str> {
+        let value = self.lines.get(self.pos)?.strip_prefix(name)?;
+        self.pos += 1;
+
+        Some(value)
+    }
+}
+
+impl SiweMessage {
+    pub fn parse(message: &str) -> Result<Self, SiweError> {
+        let mut lines = Lines {
+            lines: message.split('\n').collect(),
+            pos: 0,
+        };
+
+        let header = lines
+            .next()?
+            .strip_suffix(HEADER_SUFFIX)
+            .ok_or(SiweError::InvalidFormat)?;
+        let (scheme, domain) = match header.split_once("://") {
+            Some((scheme, domain)) => (Some(scheme.to_string()), domain),
+            None => (None, header),
+        };
+
+        let address_text = lines.next()?;
+        let address = Address::from_str(address_text).map_err(|_| SiweError::InvalidAddress)?;
+        if address.to_checksum() != address_text {
+            return Err(SiweError::InvalidAddress);
+        }
+
+        if !lines.next()?.is_empty() {
+            return Err(SiweError::InvalidFormat);
+        }
+        let statement = match lines.next()? {
+            "" => None,
+            statement => {
+                if !lines.next()?.is_empty() {
+                    return Err(SiweError::InvalidFormat);
+                }
+                Some(statement.to_string())
+            }
+        };
+
+        let uri = lines.field("URI: ")?;
+        let version = lines.field("Version: ")?;
+        let chain_id = lines
+            .field("Chain ID: ")?
+            .parse()
+            .map_err(|_| SiweError::InvalidFormat)?;
+        let nonce = lines.field("Nonce: ")?;
+        let issued_at = lines.field("Issued At: ")?;
+        let expiration_time = lines.optional("Expiration Time: ");
+        let not_before = lines.optional("Not Before: ");
+        let request_id = lines.optional("Request ID: ");
+
+        let mut resources = vec![];
+        if lines.optional("Resources:") == Some("") {
+            while let Some(resource) = lines.optional("- ") {
+                resources.push(resource.to_string());
+            }
+        }
+
+        if lines.pos != lines.lines.len() {
+            return Err(SiweError::InvalidFormat);
+        }
+
+        let message = Self {
+            scheme,
+            domain: domain.to_string(),
+            address,
+            statement,
+            uri: uri.to_string(),
+            version: version.to_string(),
+            chain_id,
+            nonce: nonce.to_string(),
+            issued_at: issued_at.to_string(),
+            expiration_time: expiration_time.map(|x| x.to_string()),
+            not_before: not_before.map(|x| x.to_string()),
+            request_id: request_id.map(|x| x.to_string()),
+            resources,
+        };
+        message.validate_fields()?;
+
+        Ok(message)
+    }
+
+    /// Checks that fields can be written to a message and read back
+    pub fn validate_fields(&self) -> Result<(), SiweError> {
+        let single_line = |x: &str| !x.contains('\n');
+        let fields = [&self.domain, &self.uri, &self.issued_at];
+        let optional = [
+            &self.scheme,
+            &self.statement,
+            &self.expiration_time,
+            &self.not_before,
+            &self.request_id,
+        ];
+
+        if self.domain.is_empty()
+            || self.uri.is_empty()
+            || !fields.iter().all(|x| single_line(x))
+            || !optional
+                .iter()
+                .all(|x| x.as_deref().is_none_or(single_line))
+            || !self.resources.iter().all(|x| single_line(x))
+            || self.statement.as_deref() == Some("")
+        {
+            return Err(SiweError::InvalidFormat);
+        }
+
+        if self.version != "1" {
+            return Err(SiweError::UnsupportedVersion);
+        }
+
+        if self.nonce.len() < 8 || !self.nonce.chars().all(|x| x.is_ascii_alphanumeric()) {
+            return Err(SiweError::InvalidNonce);
+        }
+
+        parse_timestamp(&self.issued_at)?;
+        for time in [&self.expiration_time, &self.not_before]
+            .into_iter()
+            .flatten()
+        {
+            parse_timestamp(time)?;
+        }
+
+        Ok(())
+    }
+
+    /// Checks that message was made for the domain and is valid at given time (nanoseconds)
+    pub fn validate(&self, domain: &str, now: u64) -> Result<(), SiweError> {
+        if self.domain != domain {
+            return Err(SiweError::DomainMismatch);
+        }
+
+        if let Some(time) = &self.expiration_time {
+            if now >= parse_timestamp(time)? {
+                return Err(SiweError::Expired);
+            }
+        }
+
+        if let Some(time) = &self.not_before {
+            if now < parse_timestamp(time)? {
+                return Err(SiweError::NotYetValid);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Parses and validates message, checks that `personal_sign` signature was made by
+    /// the address of the message
+    pub fn verify(
+        message: &str,
+        signature: &[u8],
+        domain: &str,
+        now: u64,
+    ) -> Result<Self, SiweError> {
+        let parsed = Self::parse(message)?;
+        parsed.validate(domain, now)?;
+
+        let hash = personal_message_hash(message.as_bytes());
+        let signer =
+            recover_address(signature, &hash.0).map_err(|_| SiweError::InvalidSignature)?;
+        if signer != parsed.address {
+            return Err(SiweError::InvalidSignature);
+        }
+
+        Ok(parsed)
+    }
+}
+
+impl Display for SiweMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(scheme) = &self.scheme {
+            write!(f, "{scheme}://")?;
+        }
+        writeln!(f, "{}{HEADER_SUFFIX}", self.domain)?;
+        writeln!(f, "{}", self.address.to_checksum())?;
+        writeln!(f)?;
+        if let Some(statement) = &self.statement {
+            writeln!(f, "{statement}")?;
+        }
+        writeln!(f)?;
+
+        writeln!(f, "URI: {}", self.uri)?;
+        writeln!(f, "Version: {}", self.version)?;
+        writeln!(f, "Chain ID: {}", self.chain_id)?;
+        writeln!(f, "Nonce: {}", self.nonce)?;
+        write!(f, "Issued At: {}", self.issued_at)?;
+        if let Some(time) = &self.expiration_time {
+            write!(f, "\nExpiration Time: {time}")?;
+        }
+        if let Some(time) = &self.not_before {
+            write!(f, "\nNot Before: {time}")?;
+        }
+        if let Some(id) = &self.request_id {
+            write!(f, "\nRequest ID: {id}")?;
+        }
+        if !self.resources.is_empty() {
+            write!(f, "\nResources:")?;
+            for resource in &self.resources {
+                write!(f, "\n- {resource}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Days since unix epoch of a date in proleptic Gregorian calendar
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Date of a day since unix epoch, returns year, month and day
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    (year, month, day)
+}
+
+fn number(value: &str, min: i64, max: i64) -> Result<i64, SiweError> {
+    if value.is_empty() || !value.bytes().all(|x| x.is_ascii_digit()) {
+        return Err(SiweError::InvalidTimestamp);
+    }
+
+    match value.parse() {
+        Ok(x) if (min..=max).contains(&x) => Ok(x),
+        _ => Err(SiweError::InvalidTimestamp),
+    }
+}
+
+/// Parses RFC 3339 timestamp, like `2021-09-30T16:25:24Z`, to nanoseconds since unix epoch
+pub fn parse_timestamp(value: &str) -> Result<u64, SiweError> {
+    if value.len() < 20 || !value.is_char_boundary(19) {
+        return Err(SiweError::InvalidTimestamp);
+    }
+
+    let (datetime, rest) = value.split_at(19);
+    let separators = datetime.as_bytes();
+    if !datetime.is_ascii()
+        || separators[4] != b'-'
+        || separators[7] != b'-'
+        || !matches!(separators[10], b'T' | b't')
+        || separators[13] != b':'
+        || separators[16] != b':'
+    {
+        return Err(SiweError::InvalidTimestamp);
+    }
+
+    let year = number(&datetime[0..4], 1970, 9999)?;
+    let month = number(&datetime[5..7], 1, 12)?;
+    let day = number(&datetime[8..10], 1, 31)?;
+    let hour = number(&datetime[11..13], 0, 23)?;
+    let minute = number(&datetime[14..16], 0, 59)?;
+    let second = number(&datetime[17..19], 0, 59)?;
+
+    let (fraction, zone) = match rest.strip_prefix('.') {
+        Some(rest) => {
+            let len = rest
+                .find(|x: char| !x.is_ascii_digit())
+                .unwrap_or(rest.len());
+            if len == 0 {
+                return Err(SiweError::InvalidTimestamp);
+            }
+            rest.split_at(len)
+        }
+        None => ("", rest),
+    };
+    let mut nanos = 0;
+    for (i, digit) in fraction.bytes().take(9).enumerate() {
+        nanos += (digit - b'0') as u64 * 10u64.pow(8 - i as u32);
+    }
+
+    let offset = match zone {
+        "Z" | "z" => 0,
+        x if x.len() == 6 && x.is_ascii() && x.as_bytes()[3] == b':' => {
+            let offset = number(&x[1..3], 0, 23)? * 3_600 + number(&x[4..6], 0, 59)? * 60;
+            match x.as_bytes()[0] {
+                b'+' => offset,
+                b'-' => -offset,
+                _ => return Err(SiweError::InvalidTimestamp),
+            }
+        }
+        _ => return Err(SiweError::InvalidTimestamp),
+    };
+
+    let days = days_from_civil(year, month, day);
+    if civil_from_days(days) != (year, month, day) {
+        return Err(SiweError::InvalidTimestamp);
+    }
+
+    let seconds = days * 86_400 + hour * 3_600 + minute * 60 + second - offset;
+    if seconds < 0 {
+        return Err(SiweError::InvalidTimestamp);
+    }
+
+    // far future timestamps do not fit in u64 nanoseconds
+    (seconds as u64)
+        .checked_mul(NANOS)
+        .and_then(|x| x.checked_add(nanos))
+        .ok_or(SiweError::InvalidTimestamp)
+}
+
+/// Formats nanoseconds since unix epoch, like canister time, as RFC 3339 timestamp
+pub fn format_timestamp(nanos: u64) -> String {
+    let seconds = (nanos / NANOS) as i64;
+    let (year, month, day) = civil_from_days(seconds.div_euclid(86_400));
+    let time = seconds.rem_euclid(86_400);
+
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        time / 3_600,
+        time % 3_600 / 60,
+        time % 60
+    )
+}
+
+/// Messages prepared by the canister, times are in nanoseconds
+#[derive(CandidType, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct SiweConfig {
+    pub domain: String,
+    pub uri: String,
+    pub chain_id: u64,
+    pub statement: Option<String>,
+    /// Time to sign the message after it is prepared
+    pub login_ttl: u64,
+    pub session_ttl: u64,
+}
+
+/// Nonce given to an address, it can be used only once by the principal that prepared it
+#[derive(CandidType, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct PendingLogin {
+    pub principal: Principal,
+    pub address: Address,
+    pub nonce: String,
+    pub expires_at: u64,
+}
+
+impl SiweConfig {
+    pub fn validate(&self) -> Result<(), SiweError> {
+        if self.login_ttl > MAX_LOGIN_TTL {
+            return Err(SiweError::LoginTtlTooLong);
+        }
+
+        Ok(())
+    }
+}
+
+/// Principal authenticated as owner of the address
+#[derive(CandidType, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct SiweSession {
+    pub principal: Principal,
+    pub address: Address,
+    pub expires_at: u64,
+}
+
+/// Nonces and sessions managed by the canister
+#[derive(CandidType, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct SiweState {
+    pub config: SiweConfig,
+    pub pending: Vec<PendingLogin>,
+    pub sessions: Vec<SiweSession>,
+    /// Number of prepared messages, makes nonces unique
+    pub counter: u64,
+}
+
+impl SiweState {
+    pub fn new(config: SiweConfig) -> Self {
+        Self {
+            config,
+            pending: vec![],
+            sessions: vec![],
+            counter: 0,
+        }
+    }
+
+    /// Removes expired nonces and sessions
+    fn prune(&mut self, now: u64) {
+        self.pending.retain(|x| x.expires_at > now);
+        self.sessions.retain(|x| x.expires_at > now);
+    }
+
+    /// Builds message with a new nonce for the address, it has to be signed and passed to
+    /// `login` by the same principal before it expires. Seed has to be random and secret, it is
+    /// mixed in to the nonce, so it can not be guessed from time alone
+    pub fn prepare(
+        &mut self,
+        principal: Principal,
+        address: &Address,
+        now: u64,
+        seed: &[u8],
+    ) -> Result<SiweMessage, SiweError> {
+        if principal == Principal::anonymous() {
+            return Err(SiweError::AnonymousPrincipal);
+        }
+
+        self.prune(now);
+
+        let count = self
+            .pending
+            .iter()
+            .filter(|x| x.principal == principal)
+            .count();
+        if count >= MAX_PENDING_PER_PRINCIPAL {
+            if let Some(index) = self.pending.iter().position(|x| x.principal == principal) {
+                self.pending.remove(index);
+            }
+        } else if self.pending.len() >= MAX_PENDING {
+            // logins are kept in order they were prepared
+            self.pending.remove(0);
+        }
+
+        self.counter += 1;
+
+        let hash = keccak256(&[
+            seed,
+            principal.as_slice(),
+            &now.to_be_bytes(),
+            &self.counter.to_be_bytes(),
+            &address.0,
+        ]);
+        let nonce = hex::encode(&hash.0[..8]);
+        let expires_at = now.saturating_add(self.config.login_ttl);
+
+        self.pending.push(PendingLogin {
+            principal,
+            address: address.clone(),
+            nonce: nonce.clone(),
+            expires_at,
+        });
+
+        Ok(SiweMessage {
+            scheme: None,
+            domain: self.config.domain.clone(),
+            address: address.clone(),
+            statement: self.config.statement.clone(),
+            uri: self.config.uri.clone(),
+            version: "1".to_string(),
+            chain_id: self.config.chain_id,
+            nonce,
+            issued_at: format_timestamp(now),
+            expiration_time: Some(format_timestamp(expires_at)),
+            not_before: None,
+            request_id: None,
+            resources: vec![],
+        })
+    }
+
+    /// Verifies signed message prepared for the address by the principal and creates its
+    /// session, nonce of the message is consumed
+    pub fn login(
+        &mut self,
+        principal: Principal,
+        message: &str,
+        signature: &[u8],
+        now: u64,
+    ) -> Result<SiweSession, SiweError> {
+        if principal == Principal::anonymous() {
+            return Err(SiweError::AnonymousPrincipal);
+        }
+
+        self.prune(now);
+
+        let message = SiweMessage::verify(message, signature, &self.config.domain, now)?;
+        if message.chain_id != self.config.chain_id || message.uri != self.config.uri {
+            return Err(SiweError::ConfigMismatch);
+        }
+
+        let index = self
+            .pending
+            .iter()
+            .position(|x| {
+                x.nonce == message.nonce && x.address == message.address && x.principal == principal
+            })
+            .ok_or(SiweError::InvalidNonce)?;
+        self.pending.remove(index);
+
+        let session = SiweSession {
+            principal,
+            address: message.address,
+            expires_at: now.saturating_add(self.config.session_ttl),
+        };
+        self.sessions.retain(|x| x.principal != principal);
+        self.sessions.push(session.clone());
+
+        Ok(session)
+    }
+
+    pub fn session(&self, principal: &Principal, now: u64) -> Option<&SiweSession> {
+        self.sessions
+            .iter()
+            .find(|x| x.principal == *principal && x.expires_at > now)
+    }
+
+    pub fn logout(&mut self, principal: &Principal) {
+        self.sessions.retain(|x| x.principal != *principal);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::error::Error;
+
+    use candid::Principal;
+    use secp256k1::{Message, SecretKey, SECP256K1};
+
+    use super::{
+        format_timestamp, parse_timestamp, SiweConfig, SiweMessage, SiweState, MAX_LOGIN_TTL,
+        MAX_PENDING, MAX_PENDING_PER_PRINCIPAL,
+    };
+    use crate::{
+        types::{address::Address, errors::SiweError},
+        utils::personal_message_hash,
+    };
+
+    // Example from EIP-4361
+    const MESSAGE: &str = "service.invalid wants you to sign in with your Ethereum account:
+0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2
+
+I accept the ServiceOrg Terms of Service: https://service.invalid/tos
+
+URI: https://service.invalid/login
+Version: 1
+Chain ID: 1
+Nonce: 32891756
+Issued At: 2021-09-30T16:25:24Z
+Resources:
+- ipfs://bafybeiemxf5abjwjbikoz4mc3a3dla6ual3jsgpdr4cjr3oz3evfyavhwq/
+- https://example.com/my-web2-claim.json";
+
+    const SECOND: u64 = 1_000_000_000;
+
+    fn sign(key: &SecretKey, message: &str) -> Result<Vec<u8>, Box<dyn Error>> {
+        let hash = personal_message_hash(message.as_bytes());
+        let (id, data) = SECP256K1
+            .sign_ecdsa_recoverable(&Message::from_slice(&hash.0)?, key)
+            .serialize_compact();
+
+        let mut signature = data.to_vec();
+        signature.push(27 + id.to_i32() as u8);
+        Ok(signature)
+    }
+
+    #[test]
+    fn parse_message() -> Result<(), Box<dyn Error>> {
+        let message = SiweMessage::parse(MESSAGE)?;
+
+        assert_eq!(message.domain, "service.invalid");
+        assert_eq!(
+            message.statement.as_deref(),
+            Some("I accept the ServiceOrg Terms of Service: https://service.invalid/tos")
+        );
+        assert_eq!(message.chain_id, 1);
+        assert_eq!(message.nonce, "32891756");
+        assert_eq!(message.resources.len(), 2);
+        assert_eq!(message.to_string(), MESSAGE);
+
+        let mut message = message;
+        message.statement = None;
+        message.resources = vec![];
+        message.expiration_time = Some("2021-10-01T00:00:00.000+02:00".to_string());
+        assert_eq!(SiweMessage::parse(&message.to_string())?, message);
+
+        let lowercase = MESSAGE.replace(
+            "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2",
+            "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2",
+        );
+        assert!(matches!(
+            SiweMessage::parse(&lowercase),
+            Err(SiweError::InvalidAddress)
+        ));
+        assert!(matches!(
+            SiweMessage::parse(&MESSAGE.replace("Version: 1", "Version: 2")),
+            Err(SiweError::UnsupportedVersion)
+        ));
+        assert!(SiweMessage::parse(&format!("{MESSAGE}\n")).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn timestamps() -> Result<(), Box<dyn Error>> {
+        assert_eq!(
+            parse_timestamp("2021-09-30T16:25:24Z")?,
+            1_633_019_124 * SECOND
+        );
+        assert_eq!(
+            parse_timestamp("2021-09-30T18:25:24.5+02:00")?,
+            1_633_019_124 * SECOND + SECOND / 2
+        );
+        assert_eq!(
+            parse_timestamp("2000-02-29T00:00:00Z")?,
+            951_782_400 * SECOND
+        );
+        assert!(parse_timestamp("2001-02-29T00:00:00Z").is_err());
+        assert!(parse_timestamp("2021-09-30 16:25:24Z").is_err());
+        assert!(parse_timestamp("2021-09-30T16:25:24").is_err());
+        assert!(parse_timestamp("2021-09-30T16:25:24\u{e9}0:00").is_err());
+        assert!(parse_timestamp("2\u{e9}21-09-30T16:25:24Z").is_err());
+        assert!(parse_timestamp("2554-07-21T23:34:33.709551615Z").is_ok());
+        assert!(parse_timestamp("2554-07-21T23:34:33.709551616Z").is_err());
+        assert!(parse_timestamp("9999-12-31T23:59:59Z").is_err());
+        assert_eq!(
+            format_timestamp(1_633_019_124 * SECOND + 1),
+            "2021-09-30T16:25:24Z"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn verify() -> Result<(), Box<dyn Error>> {
+        let key = SecretKey::from_slice(&[0x46; 32])?;
+        let address = Address::from(key.public_key(SECP256K1));
+
+        let mut message = SiweMessage::parse(MESSAGE)?;
+        message.address = address.clone();
+        message.expiration_time = Some("2021-10-01T00:00:00Z".to_string());
+        message.not_before = Some("2021-09-30T16:00:00Z".to_string());
+        let text = message.to_string();
+        let signature = sign(&key, &text)?;
+        let now = 1_633_019_124 * SECOND;
+
+        assert_eq!(
+            SiweMessage::verify(&text, &signature, "service.invalid", now)?,
+            message
+        );
+        assert!(matches!(
+            SiweMessage::verify(&text, &signature, "other.invalid", now),
+            Err(SiweError::DomainMismatch)
+        ));
+        assert!(matches!(
+            SiweMessage::verify(&text, &signature, "service.invalid", now + 86_400 * SECOND),
+            Err(SiweError::Expired)
+        ));
+        assert!(matches!(
+            SiweMessage::verify(&text, &signature, "service.invalid", now - 3_600 * SECOND),
+            Err(SiweError::NotYetValid)
+        ));
+
+        let other = sign(&SecretKey::from_slice(&[0x47; 32])?, &text)?;
+        assert!(matches!(
+            SiweMessage::verify(&text, &other, "service.invalid", now),
+            Err(SiweError::InvalidSignature)
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn sessions() -> Result<(), Box<dyn Error>> {
+        let key = SecretKey::from_slice(&[0x46; 32])?;
+        let address = Address::from(key.public_key(SECP256K1));
+        let principal = Principal::from_slice(&[1; 29]);
+        let other = Principal::from_slice(&[2; 29]);
+        let now = 1_700_000_000 * SECOND;
+
+        let mut state = SiweState::new(SiweConfig {
+            domain: "app.invalid".to_string(),
+            uri: "https://app.invalid".to_string(),
+            chain_id: 1,
+            statement: Some("Sign in".to_string()),
+            login_ttl: 300 * SECOND,
+            session_ttl: 3_600 * SECOND,
+        });
+
+        assert!(matches!(
+            state.prepare(Principal::anonymous(), &address, now, b"seed"),
+            Err(SiweError::AnonymousPrincipal)
+        ));
+
+        let message = state
+            .prepare(principal, &address, now, b"seed")?
+            .to_string();
+        let signature = sign(&key, &message)?;
+
+        // nonce is bound to the principal that prepared the message
+        assert!(matches!(
+            state.login(other, &message, &signature, now + SECOND),
+            Err(SiweError::InvalidNonce)
+        ));
+        assert!(matches!(
+            state.login(Principal::anonymous(), &message, &signature, now + SECOND),
+            Err(SiweError::AnonymousPrincipal)
+        ));
+
+        let session = state.login(principal, &message, &signature, now + SECOND)?;
+        assert_eq!(session.address, address);
+        assert_eq!(state.session(&principal, now + 2 * SECOND), Some(&session));
+        assert_eq!(state.session(&principal, now + 3_601 * SECOND), None);
+
+        // Nonce can be used only once
+        assert!(matches!(
+            state.login(principal, &message, &signature, now + SECOND),
+            Err(SiweError::InvalidNonce)
+        ));
+
+        // only the latest pending logins of a principal are kept
+        let first = state
+            .prepare(principal, &address, now, b"seed")?
+            .to_string();
+        for _ in 0..MAX_PENDING_PER_PRINCIPAL {
+            state.prepare(principal, &address, now, b"seed")?;
+        }
+        assert_eq!(state.pending.len(), MAX_PENDING_PER_PRINCIPAL);
+        assert!(matches!(
+            state.login(principal, &first, &sign(&key, &first)?, now + SECOND),
+            Err(SiweError::InvalidNonce)
+        ));
+
+        state.logout(&principal);
+        assert_eq!(state.session(&principal, now + 2 * SECOND), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn pending_limit() -> Result<(), Box<dyn Error>> {
+        let address = Address([1; 20]);
+        let now = 1_700_000_000 * SECOND;
+        let mut state = SiweState::new(SiweConfig {
+            domain: "app.invalid".to_string(),
+            uri: "https://app.invalid".to_string(),
+            chain_id: 1,
+            statement: None,
+            login_ttl: 300 * SECOND,
+            session_ttl: 3_600 * SECOND,
+        });
+
+        for i in 0..MAX_PENDING as u64 {
+            state.prepare(
+                Principal::from_slice(&i.to_be_bytes()),
+                &address,
+                now,
+                b"seed",
+            )?;
+        }
+
+        // the oldest login is replaced
+        let principal = Principal::from_slice(&[0xff; 29]);
+        state.prepare(principal, &address, now, b"seed")?;
+        assert_eq!(state.pending.len(), MAX_PENDING);
+        assert_eq!(
+            state.pending[0].principal,
+            Principal::from_slice(&1u64.to_be_bytes())
+        );
+        assert_eq!(state.pending[MAX_PENDING - 1].principal, principal);
+
+        // expired logins make room for new ones
+        state.prepare(principal, &address, now + 300 * SECOND, b"seed")?;
+        assert_eq!(state.pending.len(), 1);
+
+        let mut config = state.config.clone();
+        assert!(config.validate().is_ok());
+        config.login_ttl = MAX_LOGIN_TTL + 1;
+        assert!(matches!(config.validate(), Err(SiweError::LoginTtlTooLong)));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,314 @@
+use ic_cdk::export::candid::{CandidType, Deserialize};
+use primitive_types::U256 as Uint;
+
+use super::{
+    errors::StorageError,
+    num::{H256, U256},
+};
+use crate::utils::keccak256;
+
+pub const SLOT_SIZE: u32 = 32;
+
+/// Key of a Solidity mapping, encoded the same way as by the compiler
+#[derive(CandidType, Deserialize, Clone, PartialEq, Eq)]
+pub enum MappingKey {
+    /// Integers, addresses and bools, left padded to 32 bytes. Negative integers
+    /// have to be passed sign extended to 32 bytes
+    Word(Vec<u8>),
+    /// bytes1 to bytes32, right padded to 32 bytes
+    FixedBytes(Vec<u8>),
+    /// string and bytes keys, hashed without padding
+    Bytes(Vec<u8>),
+}
+
+impl MappingKey {
+    pub fn encode(&self) -> Result<Vec<u8>, StorageError> {
+        let (data, left) = match self {
+            MappingKey::Word(x) => (x, true),
+            MappingKey::FixedBytes(x) => (x, false),
+            MappingKey::Bytes(x) => return Ok(x.clone()),
+        };
+
+        if data.len() > 32 {
+            return Err(StorageError::KeyTooLong);
+        }
+
+        let mut buf = vec![0u8; 32];
+        if left {
+            buf[32 - data.len()..].copy_from_slice(data);
+        } else {
+            buf[..data.len()].copy_from_slice(data);
+        }
+
+        Ok(buf)
+    }
+}
+
+/// Position of a value in contract storage
+#[derive(CandidType, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct StorageLocation {
+    pub slot: H256,
+    /// Bytes from the lowest order byte of the slot, values smaller than 32 bytes share slots
+    pub offset: u32,
+}
+
+/// Slot increased by n, wraps around like in the EVM
+fn add_slot(slot: &H256, n: Uint) -> H256 {
+    let (slot, _) = Uint::from_big_endian(&slot.0).overflowing_add(n);
+
+    U256::from(slot).into()
+}
+
+/// Storage slot of a mapping value, keccak256(key . slot)
+pub fn mapping_slot(key: &MappingKey, slot: &H256) -> Result<H256, StorageError> {
+    Ok(keccak256(&[&key.encode()?, &slot.0]).into())
+}
+
+/// Storage slot of a value in nested mapping, keys are given from the outermost one
+pub fn nested_mapping_slot(keys: &[MappingKey], slot: &H256) -> Result<H256, StorageError> {
+    keys.iter()
+        .try_fold(slot.clone(), |slot, key| mapping_slot(key, &slot))
+}
+
+/// Location of an element of a dynamic array, elements are stored from keccak256(slot).
+/// Elements smaller than 32 bytes are packed, structs and arrays should be given
+/// with size of 32 bytes times number of their slots
+pub fn array_element(
+    slot: &H256,
+    index: &U256,
+    element_size: u32,
+) -> Result<StorageLocation, StorageError> {
+    if element_size == 0 {
+        return Err(StorageError::InvalidSize);
+    }
+
+    let start: H256 = keccak256(&[&slot.0]).into();
+    let index = Uint::from(index);
+
+    if element_size < SLOT_SIZE {
+        let per_slot = Uint::from(SLOT_SIZE / element_size);
+
+        Ok(StorageLocation {
+            slot: add_slot(&start, index / per_slot),
+            offset: (index % per_slot).as_u32() * element_size,
+        })
+    } else {
+        let slots = Uint::from(element_size.div_ceil(SLOT_SIZE));
+
+        Ok(StorageLocation {
+            slot: add_slot(&start, index.overflowing_mul(slots).0),
+            offset: 0,
+        })
+    }
+}
+
+/// Locations of struct fields (or consecutive state variables) starting at slot. Fields
+/// are packed in to one slot while they fit, structs and arrays always start a new slot,
+/// their size should be given as 32 bytes times number of their slots
+pub fn struct_fields(slot: &H256, sizes: &[u32]) -> Result<Vec<StorageLocation>, StorageError> {
+    let mut result = vec![];
+    let mut current = 0u64;
+    let mut used = 0u32;
+
+    for size in sizes {
+        if *size == 0 {
+            return Err(StorageError::InvalidSize);
+        }
+
+        // value does not fit in to partially used slot
+        if used > 0 && (*size >= SLOT_SIZE || used + size > SLOT_SIZE) {
+            current += 1;
+            used = 0;
+        }
+
+        result.push(StorageLocation {
+            slot: add_slot(slot, Uint::from(current)),
+            offset: used,
+        });
+
+        if *size >= SLOT_SIZE {
+            current += size.div_ceil(SLOT_SIZE) as u64;
+        } else {
+            used += size;
+        }
+    }
+
+    Ok(result)
+}
+
+/// Splits value of a slot in to packed fields, first field is stored in the lowest order bytes.
+/// Fields are returned big endian, like values of the ABI
+pub fn decode_packed(value: &H256, sizes: &[u32]) -> Result<Vec<Vec<u8>>, StorageError> {
+    let mut result = vec![];
+    let mut offset = 0u32;
+
+    for size in sizes {
+        if *size == 0 {
+            return Err(StorageError::InvalidSize);
+        }
+        let next = offset
+            .checked_add(*size)
+            .filter(|x| *x <= SLOT_SIZE)
+            .ok_or(StorageError::SlotOverflow)?;
+
+        let end = (SLOT_SIZE - offset) as usize;
+        result.push(value.0[end - *size as usize..end].to_vec());
+        offset = next;
+    }
+
+    Ok(result)
+}
+
+/// Proxy slot defined by EIP-1967, keccak256(name) - 1, e.g. `eip1967.proxy.implementation`
+pub fn eip1967_slot(name: &str) -> H256 {
+    let hash = Uint::from(&keccak256(&[name.as_bytes()]));
+
+    U256::from(hash.overflowing_sub(Uint::one()).0).into()
+}
+
+/// Root slot of a namespace defined by EIP-7201,
+/// keccak256(keccak256(namespace) - 1) & ~0xff
+pub fn erc7201_slot(namespace: &str) -> H256 {
+    let mut slot = keccak256(&[&eip1967_slot(namespace).0]).0;
+    slot[31] = 0;
+
+    H256(slot)
+}
+
+#[cfg(test)]
+mod test {
+    use std::error::Error;
+
+    use super::{
+        array_element, decode_packed, eip1967_slot, erc7201_slot, mapping_slot,
+        nested_mapping_slot, struct_fields, MappingKey,
+    };
+    use crate::types::num::{H256, U256};
+
+    fn slot(x: u64) -> H256 {
+        U256::from(x).into()
+    }
+
+    #[test]
+    fn mapping() -> Result<(), Box<dyn Error>> {
+        // balance of an address in USDC, balances mapping is at slot 9
+        let key = MappingKey::Word(hex::decode("5b1578681d43931030fffe066a072133842dde43")?);
+        assert_eq!(
+            hex::encode(mapping_slot(&key, &slot(9))?.0),
+            "b045dba423fa043f3b3e90a48520c2012ffa903028adf034092d9dbe5c80b38c"
+        );
+
+        let key = MappingKey::Bytes(b"hello".to_vec());
+        assert_eq!(
+            hex::encode(mapping_slot(&key, &slot(2))?.0),
+            "98cc3604479d1233834ea19a78b22cff641ec62dc88921ba3f1f66a37957a4f8"
+        );
+
+        let key = MappingKey::FixedBytes(vec![0x12, 0x34, 0x56, 0x78]);
+        assert_eq!(
+            hex::encode(mapping_slot(&key, &slot(1))?.0),
+            "d61b4b4c4215a3b160e238a68f0842f5b49688e7c37f01f944c1453922187815"
+        );
+
+        assert!(mapping_slot(&MappingKey::Word(vec![1u8; 33]), &slot(0)).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn nested_mapping() -> Result<(), Box<dyn Error>> {
+        let owner = MappingKey::Word(vec![1u8; 20]);
+        let spender = MappingKey::Word(vec![2u8; 20]);
+
+        let inner = mapping_slot(&owner, &slot(10))?;
+        assert_eq!(
+            nested_mapping_slot(&[owner, spender.clone()], &slot(10))?,
+            mapping_slot(&spender, &inner)?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn array() -> Result<(), Box<dyn Error>> {
+        let location = array_element(&slot(2), &U256::from(3u64), 32)?;
+        assert_eq!(
+            hex::encode(location.slot.0),
+            "405787fa12a823e0f2b7631cc41b3ba8828b3321ca811111fa75cd3aa3bb5ad1"
+        );
+        assert_eq!(location.offset, 0);
+
+        // four uint64 values per slot
+        let location = array_element(&slot(2), &U256::from(5u64), 8)?;
+        assert_eq!(
+            hex::encode(location.slot.0),
+            "405787fa12a823e0f2b7631cc41b3ba8828b3321ca811111fa75cd3aa3bb5acf"
+        );
+        assert_eq!(location.offset, 8);
+
+        // struct of two slots
+        let location = array_element(&slot(2), &U256::from(1u64), 64)?;
+        assert_eq!(
+            hex::encode(location.slot.0),
+            "405787fa12a823e0f2b7631cc41b3ba8828b3321ca811111fa75cd3aa3bb5ad0"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn struct_layout() -> Result<(), Box<dyn Error>> {
+        // address, uint64, uint64, uint256, bool, uint128[2], uint8
+        let fields = struct_fields(&slot(5), &[20, 8, 8, 32, 1, 64, 1])?;
+
+        let layout: Vec<(H256, u32)> = fields.into_iter().map(|x| (x.slot, x.offset)).collect();
+        assert_eq!(
+            layout,
+            vec![
+                (slot(5), 0),
+                (slot(5), 20),
+                (slot(6), 0),
+                (slot(7), 0),
+                (slot(8), 0),
+                (slot(9), 0),
+                (slot(11), 0),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn packed_value() -> Result<(), Box<dyn Error>> {
+        let mut value = [0u8; 32];
+        value[31] = 1;
+        value[11..31].copy_from_slice(&[0xaa; 20]);
+        value[3..11].copy_from_slice(&7u64.to_be_bytes());
+
+        let fields = decode_packed(&H256(value), &[1, 20, 8])?;
+        assert_eq!(fields[0], vec![1]);
+        assert_eq!(fields[1], vec![0xaa; 20]);
+        assert_eq!(fields[2], 7u64.to_be_bytes().to_vec());
+
+        assert!(decode_packed(&H256(value), &[20, 20]).is_err());
+        assert!(decode_packed(&H256(value), &[1, u32::MAX]).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn namespaced_slots() {
+        assert_eq!(
+            hex::encode(eip1967_slot("eip1967.proxy.implementation").0),
+            "360894a13ba1a3210667c828492db98dca3e2076cc3735a920a3ca505d382bbc"
+        );
+        assert_eq!(
+            hex::encode(eip1967_slot("eip1967.proxy.admin").0),
+            "b53127684a568b3173ae13b9f8a6016e243e63b6e8ee1178d6a717850b5d6103"
+        );
+        assert_eq!(
+            hex::encode(erc7201_slot("example.main").0),
+            "183a6125c38840424c4a85fa12bab2ab606c4b6d0e7cc73c0c06ba5300eab500"
+        );
+    }
+}
@@ -0,0 +1,321 @@
+use ic_cdk::export::candid::{CandidType, Deserialize};
+
+use super::{
+    abi::{self, AbiValue, ParamType},
+    address::Address,
+    num::{H256, U256},
+};
+
+/// Signatures of token methods recognized in transactions
+const SIGNATURES: [&str; 8] = [
+    "transfer(address,uint256)",
+    "approve(address,uint256)",
+    "transferFrom(address,address,uint256)",
+    "safeTransferFrom(address,address,uint256)",
+    "safeTransferFrom(address,address,uint256,bytes)",
+    "safeTransferFrom(address,address,uint256,uint256,bytes)",
+    "setApprovalForAll(address,bool)",
+    "permit(address,address,uint256,uint256,uint8,bytes32,bytes32)",
+];
+
+/// Call of ERC-20, ERC-721 or ERC-1155 token method
+#[derive(CandidType, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub enum TokenCall {
+    /// ERC-20 `transfer`
+    Transfer { to: Address, amount: U256 },
+    /// ERC-20 `approve`, for ERC-721 amount is id of the token
+    Approve { spender: Address, amount: U256 },
+    /// ERC-20 and ERC-721 `transferFrom`, for ERC-721 amount is id of the token
+    TransferFrom {
+        from: Address,
+        to: Address,
+        amount: U256,
+    },
+    /// ERC-721 `safeTransferFrom`, with data if it is set
+    SafeTransferFrom {
+        from: Address,
+        to: Address,
+        token_id: U256,
+        data: Option<Vec<u8>>,
+    },
+    /// ERC-1155 `safeTransferFrom`
+    Erc1155SafeTransferFrom {
+        from: Address,
+        to: Address,
+        id: U256,
+        amount: U256,
+        data: Vec<u8>,
+    },
+    /// ERC-721 and ERC-1155 `setApprovalForAll`
+    SetApprovalForAll { operator: Address, approved: bool },
+    /// EIP-2612 `permit`
+    Permit {
+        owner: Address,
+        spender: Address,
+        value: U256,
+        deadline: U256,
+        v: u8,
+        r: H256,
+        s: H256,
+    },
+}
+
+/// Token call found in a transaction
+#[derive(CandidType, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct TokenIntent {
+    /// Contract of the token, recipient of the transaction
+    pub token: Address,
+    pub call: TokenCall,
+}
+
+fn param_types(signature: &str) -> Vec<ParamType> {
+    let start = signature.find('(').unwrap_or_default();
+    signature[start + 1..signature.len() - 1]
+        .split(',')
+        .filter_map(|x| ParamType::parse(x, None).ok())
+        .collect()
+}
+
+impl TokenCall {
+    fn signature(&self) -> &'static str {
+        match self {
+            TokenCall::Transfer { .. } => SIGNATURES[0],
+            TokenCall::Approve { .. } => SIGNATURES[1],
+            TokenCall::TransferFrom { .. } => SIGNATURES[2],
+            TokenCall::SafeTransferFrom { data: None, .. } => SIGNATURES[3],
+            TokenCall::SafeTransferFrom { data: Some(_), .. } => SIGNATURES[4],
+            TokenCall::Erc1155SafeTransferFrom { .. } => SIGNATURES[5],
+            TokenCall::SetApprovalForAll { .. } => SIGNATURES[6],
+            TokenCall::Permit { .. } => SIGNATURES[7],
+        }
+    }
+
+    fn params(&self) -> Vec<AbiValue> {
+        let address = |x: &Address| AbiValue::Address(x.clone());
+        let uint = |x: &U256| AbiValue::Uint(x.clone());
+
+        match self {
+            TokenCall::Transfer { to, amount } => vec![address(to), uint(amount)],
+            TokenCall::Approve { spender, amount } => vec![address(spender), uint(amount)],
+            TokenCall::TransferFrom { from, to, amount } => {
+                vec![address(from), address(to), uint(amount)]
+            }
+            TokenCall::SafeTransferFrom {
+                from,
+                to,
+                token_id,
+                data,
+            } => {
+                let mut params = vec![address(from), address(to), uint(token_id)];
+                if let Some(data) = data {
+                    params.push(AbiValue::Bytes(data.clone()));
+                }
+                params
+            }
+            TokenCall::Erc1155SafeTransferFrom {
+                from,
+                to,
+                id,
+                amount,
+                data,
+            } => vec![
+                address(from),
+                address(to),
+                uint(id),
+                uint(amount),
+                AbiValue::Bytes(data.clone()),
+            ],
+            TokenCall::SetApprovalForAll { operator, approved } => {
+                vec![address(operator), AbiValue::Bool(*approved)]
+            }
+            TokenCall::Permit {
+                owner,
+                spender,
+                value,
+                deadline,
+                v,
+                r,
+                s,
+            } => vec![
+                address(owner),
+                address(spender),
+                uint(value),
+                uint(deadline),
+                uint(&U256::from(*v as u64)),
+                AbiValue::FixedBytes(r.0.to_vec()),
+                AbiValue::FixedBytes(s.0.to_vec()),
+            ],
+        }
+    }
+
+    /// Encodes call data, selector followed by parameters
+    pub fn encode(&self) -> Vec<u8> {
+        let mut data = abi::selector(self.signature()).to_vec();
+        data.extend(abi::encode(&self.params()));
+
+        data
+    }
+
+    /// Recognizes token call from call data, None if it is not a token call or parameters
+    /// are malformed
+    pub fn decode(data: &[u8]) -> Option<Self> {
+        if data.len() < 4 {
+            return None;
+        }
+
+        let (selector, params) = data.split_at(4);
+        let index = SIGNATURES
+            .iter()
+            .position(|x| abi::selector(x) == selector)?;
+        let values = abi::decode(&param_types(SIGNATURES[index]), params).ok()?;
+
+        let word = |x: &[u8]| {
+            let mut buf = [0u8; 32];
+            buf.copy_from_slice(x);
+            H256(buf)
+        };
+
+        use AbiValue::{Address as A, Bool, Bytes, FixedBytes as F, Uint};
+        let call = match (index, values.as_slice()) {
+            (0, [A(to), Uint(amount)]) => TokenCall::Transfer {
+                to: to.clone(),
+                amount: amount.clone(),
+            },
+            (1, [A(spender), Uint(amount)]) => TokenCall::Approve {
+                spender: spender.clone(),
+                amount: amount.clone(),
+            },
+            (2, [A(from), A(to), Uint(amount)]) => TokenCall::TransferFrom {
+                from: from.clone(),
+                to: to.clone(),
+                amount: amount.clone(),
+            },
+            (3, [A(from), A(to), Uint(token_id)]) => TokenCall::SafeTransferFrom {
+                from: from.clone(),
+                to: to.clone(),
+                token_id: token_id.clone(),
+                data: None,
+            },
+            (4, [A(from), A(to), Uint(token_id), Bytes(data)]) => TokenCall::SafeTransferFrom {
+                from: from.clone(),
+                to: to.clone(),
+                token_id: token_id.clone(),
+                data: Some(data.clone()),
+            },
+            (5, [A(from), A(to), Uint(id), Uint(amount), Bytes(data)]) => {
+                TokenCall::Erc1155SafeTransferFrom {
+                    from: from.clone(),
+                    to: to.clone(),
+                    id: id.clone(),
+                    amount: amount.clone(),
+                    data: data.clone(),
+                }
+            }
+            (6, [A(operator), Bool(approved)]) => TokenCall::SetApprovalForAll {
+                operator: operator.clone(),
+                approved: *approved,
+            },
+            (7, [A(owner), A(spender), Uint(value), Uint(deadline), Uint(v), F(r), F(s)]) => {
+                TokenCall::Permit {
+                    owner: owner.clone(),
+                    spender: spender.clone(),
+                    value: value.clone(),
+                    deadline: deadline.clone(),
+                    v: v.0[31],
+                    r: word(r),
+                    s: word(s),
+                }
+            }
+            _ => return None,
+        };
+
+        Some(call)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::error::Error;
+
+    use super::TokenCall;
+    use crate::types::{
+        address::Address,
+        num::{H256, U256},
+    };
+
+    #[test]
+    fn transfer() -> Result<(), Box<dyn Error>> {
+        let call = TokenCall::Transfer {
+            to: Address([0x11; 20]),
+            amount: U256::from(1_000_000),
+        };
+
+        let data = call.encode();
+
+        assert_eq!(
+            hex::encode(&data),
+            "a9059cbb000000000000000000000000111111111111111111111111111111111111111100000000000000000000000000000000000000000000000000000000000f4240"
+        );
+        assert_eq!(TokenCall::decode(&data), Some(call));
+
+        Ok(())
+    }
+
+    #[test]
+    fn roundtrip() {
+        let calls = vec![
+            TokenCall::Approve {
+                spender: Address([0x22; 20]),
+                amount: U256([0xff; 32]),
+            },
+            TokenCall::TransferFrom {
+                from: Address([0x11; 20]),
+                to: Address([0x22; 20]),
+                amount: U256::from(5),
+            },
+            TokenCall::SafeTransferFrom {
+                from: Address([0x11; 20]),
+                to: Address([0x22; 20]),
+                token_id: U256::from(7),
+                data: None,
+            },
+            TokenCall::SafeTransferFrom {
+                from: Address([0x11; 20]),
+                to: Address([0x22; 20]),
+                token_id: U256::from(7),
+                data: Some(vec![1, 2, 3]),
+            },
+            TokenCall::Erc1155SafeTransferFrom {
+                from: Address([0x11; 20]),
+                to: Address([0x22; 20]),
+                id: U256::from(1),
+                amount: U256::from(10),
+                data: vec![],
+            },
+            TokenCall::SetApprovalForAll {
+                operator: Address([0x33; 20]),
+                approved: true,
+            },
+            TokenCall::Permit {
+                owner: Address([0x11; 20]),
+                spender: Address([0x22; 20]),
+                value: U256::from(100),
+                deadline: U256::from(1_700_000_000),
+                v: 27,
+                r: H256([0x44; 32]),
+                s: H256([0x55; 32]),
+            },
+        ];
+
+        for call in calls {
+            assert_eq!(TokenCall::decode(&call.encode()), Some(call));
+        }
+    }
+
+    #[test]
+    fn not_token_call() {
+        assert_eq!(TokenCall::decode(&[]), None);
+        assert_eq!(TokenCall::decode(&[0xa9, 0x05, 0x9c, 0xbb, 0x00]), None);
+        assert_eq!(TokenCall::decode(&[0x12, 0x34, 0x56, 0x78]), None);
+    }
+}
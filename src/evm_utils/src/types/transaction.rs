@@ -3,11 +3,16 @@ use std::{error::Error, vec};
 use bytes::BytesMut;
 use ic_cdk::export::candid::{CandidType, Deserialize};
 
+use super::access_list::AccessList;
+use super::address::Address;
 use super::errors::TransactionError;
-use super::signature::Signable;
+use super::num::U256;
+use super::signature::{Signable, Signature};
+use super::token::{TokenCall, TokenIntent};
 use super::transaction_1559::Transaction1559;
 use super::transaction_2930::Transaction2930;
 use super::transaction_legacy::TransactionLegacy;
+use crate::utils::{keccak256, y_parity};
 
 #[derive(CandidType, Deserialize, Clone, PartialEq, Eq)]
 pub enum Transaction {
@@ -17,15 +22,32 @@ pub enum Transaction {
 }
 
 impl Transaction {
+    /// Returns type of transaction envelope, 0 for legacy transactions
+    pub fn envelope_type(hex_raw_tx: &[u8]) -> Result<u8, Box<dyn Error>> {
+        match hex_raw_tx.first() {
+            Some(x) if *x > 0x7f => Ok(0),
+            Some(x) if *x == 0x01 || *x == 0x02 => Ok(*x),
+            _ => Err(Box::new(TransactionError::InvalidType)),
+        }
+    }
+
+    /// Decodes transaction, fails if sender can not be recovered from its signature
     pub fn decode(hex_raw_tx: &[u8]) -> Result<Transaction, Box<dyn Error>> {
-        if hex_raw_tx[0] > 0x7f {
-            Ok(Self::Legacy(rlp::decode(hex_raw_tx)?))
-        } else if hex_raw_tx[0] == 0x01 {
-            Ok(Self::EIP2930(rlp::decode(&hex_raw_tx[1..])?))
-        } else if hex_raw_tx[0] == 0x02 {
-            Ok(Self::EIP1559(rlp::decode(&hex_raw_tx[1..])?))
-        } else {
-            Err(Box::new(TransactionError::InvalidType))
+        let tx = Self::decode_unchecked(hex_raw_tx)?;
+        if tx.signature().is_some_and(|x| x.from.is_none()) {
+            return Err(Box::new(TransactionError::InvalidSignature));
+        }
+
+        Ok(tx)
+    }
+
+    /// Decodes transaction without checking its signature, sender is None when it can not
+    /// be recovered
+    pub fn decode_unchecked(hex_raw_tx: &[u8]) -> Result<Transaction, Box<dyn Error>> {
+        match Self::envelope_type(hex_raw_tx)? {
+            0x01 => Ok(Self::EIP2930(rlp::decode(&hex_raw_tx[1..])?)),
+            0x02 => Ok(Self::EIP1559(rlp::decode(&hex_raw_tx[1..])?)),
+            _ => Ok(Self::Legacy(rlp::decode(hex_raw_tx)?)),
         }
     }
 
@@ -36,4 +58,238 @@ impl Transaction {
             Transaction::EIP2930(a) => a.get_bytes(for_signing),
         }
     }
+
+    pub fn tx_type(&self) -> u8 {
+        match self {
+            Transaction::Legacy(_) => 0,
+            Transaction::EIP2930(_) => 1,
+            Transaction::EIP1559(_) => 2,
+        }
+    }
+
+    pub fn to(&self) -> Option<&Address> {
+        match self {
+            Transaction::Legacy(a) => a.to.as_ref(),
+            Transaction::EIP1559(a) => a.to.as_ref(),
+            Transaction::EIP2930(a) => a.to.as_ref(),
+        }
+    }
+
+    pub fn value(&self) -> &U256 {
+        match self {
+            Transaction::Legacy(a) => &a.value,
+            Transaction::EIP1559(a) => &a.value,
+            Transaction::EIP2930(a) => &a.value,
+        }
+    }
+
+    pub fn data(&self) -> &[u8] {
+        match self {
+            Transaction::Legacy(a) => &a.data,
+            Transaction::EIP1559(a) => &a.data,
+            Transaction::EIP2930(a) => &a.data,
+        }
+    }
+
+    pub fn gas_limit(&self) -> &U256 {
+        match self {
+            Transaction::Legacy(a) => &a.gas_limit,
+            Transaction::EIP1559(a) => &a.gas_limit,
+            Transaction::EIP2930(a) => &a.gas_limit,
+        }
+    }
+
+    /// Maximum price per unit of gas, gas price for Legacy and EIP2930 transactions
+    pub fn max_fee_per_gas(&self) -> &U256 {
+        match self {
+            Transaction::Legacy(a) => &a.gas_price,
+            Transaction::EIP1559(a) => &a.max_fee_per_gas,
+            Transaction::EIP2930(a) => &a.gas_price,
+        }
+    }
+
+    pub fn access_list(&self) -> &[AccessList] {
+        match self {
+            Transaction::Legacy(_) => &[],
+            Transaction::EIP1559(a) => &a.access_list,
+            Transaction::EIP2930(a) => &a.access_list,
+        }
+    }
+
+    pub fn chain_id(&self) -> u64 {
+        match self {
+            Transaction::Legacy(a) => a.chain_id,
+            Transaction::EIP1559(a) => a.chain_id,
+            Transaction::EIP2930(a) => a.chain_id,
+        }
+    }
+
+    pub fn nonce(&self) -> &U256 {
+        match self {
+            Transaction::Legacy(a) => &a.nonce,
+            Transaction::EIP1559(a) => &a.nonce,
+            Transaction::EIP2930(a) => &a.nonce,
+        }
+    }
+
+    pub fn signature(&self) -> Option<&Signature> {
+        match self {
+            Transaction::Legacy(a) => a.sign.as_ref(),
+            Transaction::EIP1559(a) => a.sign.as_ref(),
+            Transaction::EIP2930(a) => a.sign.as_ref(),
+        }
+    }
+
+    /// Checks if signature of transaction commits to chain id, typed transactions always do
+    pub fn is_eip155(&self) -> bool {
+        match self {
+            Transaction::Legacy(a) => match a.sign.as_ref() {
+                Some(sign) => sign.v >= 35,
+                None => a.chain_id != 0,
+            },
+            _ => true,
+        }
+    }
+}
+
+/// Decoded transaction together with data derived from its raw form
+#[derive(CandidType, Deserialize, Clone, PartialEq, Eq)]
+pub struct ParsedTransaction {
+    pub transaction: Transaction,
+    pub tx_type: u8,
+    /// Hash of transaction as it was provided
+    pub hash: U256,
+    /// Hash that is signed by the sender
+    pub signing_hash: U256,
+    pub from: Option<Address>,
+    pub y_parity: Option<u8>,
+    pub eip155: bool,
+    /// Token transfer or approval made by the transaction
+    pub token_call: Option<TokenIntent>,
+}
+
+impl ParsedTransaction {
+    pub fn decode(hex_raw_tx: &[u8]) -> Result<Self, Box<dyn Error>> {
+        let transaction = Transaction::decode(hex_raw_tx)?;
+        let signature = transaction.signature();
+
+        let from = signature.and_then(|x| x.from.clone());
+        let y_parity = signature.map(|x| y_parity(x.v) as u8);
+
+        Ok(Self {
+            tx_type: transaction.tx_type(),
+            hash: keccak256(&[hex_raw_tx]),
+            signing_hash: keccak256(&[&transaction.encode(true)]),
+            from,
+            y_parity,
+            eip155: transaction.is_eip155(),
+            token_call: transaction.to().and_then(|token| {
+                Some(TokenIntent {
+                    token: token.clone(),
+                    call: TokenCall::decode(transaction.data())?,
+                })
+            }),
+            transaction,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::error::Error;
+
+    use secp256k1::{Message, SecretKey, SECP256K1};
+
+    use super::{ParsedTransaction, Transaction};
+    use crate::types::signature::Signature;
+    use crate::utils::keccak256;
+
+    // Example from EIP-155, signed with private key 0x4646...46
+    const EIP155_TX: &str = "f86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83";
+    const EIP155_SENDER: &str = "0x9d8a62f656a8d1615c1294fd71e9cfb3e4855a4f";
+
+    #[test]
+    fn parse_eip155_transaction() -> Result<(), Box<dyn Error>> {
+        let data = hex::decode(EIP155_TX)?;
+        let parsed = ParsedTransaction::decode(&data)?;
+
+        assert_eq!(parsed.tx_type, 0);
+        assert!(parsed.eip155);
+        assert_eq!(parsed.y_parity, Some(0));
+        assert_eq!(format!("{}", parsed.from.unwrap()), EIP155_SENDER);
+        assert!(parsed.token_call.is_none());
+        assert_eq!(
+            format!("{}", parsed.signing_hash),
+            "0xdaf5a779ae972f972197303d7b574746c7ef83eadac0f2791ad23db92e4c8e53"
+        );
+        assert_eq!(parsed.hash, keccak256(&[&data]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_pre_eip155_transaction() -> Result<(), Box<dyn Error>> {
+        let data = hex::decode(EIP155_TX)?;
+        let mut tx = match Transaction::decode(&data)? {
+            Transaction::Legacy(x) => x,
+            _ => panic!("Wrong transaction type"),
+        };
+
+        tx.chain_id = 0;
+        tx.sign = None;
+
+        let unsigned = Transaction::Legacy(tx.clone());
+        let signing_hash = keccak256(&[&unsigned.encode(true)]);
+
+        let key = SecretKey::from_slice(&[0x46u8; 32])?;
+        let (rec_id, sign) = SECP256K1
+            .sign_ecdsa_recoverable(&Message::from_slice(&signing_hash.0)?, &key)
+            .serialize_compact();
+
+        tx.sign = Some(Signature {
+            v: 27 + rec_id.to_i32() as u64,
+            r: sign[..32].to_vec(),
+            s: sign[32..].to_vec(),
+            from: None,
+            hash: signing_hash.clone(),
+        });
+
+        let raw = Transaction::Legacy(tx).encode(false);
+        let parsed = ParsedTransaction::decode(&raw)?;
+
+        assert!(!parsed.eip155);
+        assert_eq!(parsed.signing_hash, signing_hash);
+        assert_eq!(parsed.y_parity, Some(rec_id.to_i32() as u8));
+        assert_eq!(format!("{}", parsed.from.unwrap()), EIP155_SENDER);
+
+        match parsed.transaction {
+            Transaction::Legacy(x) => assert_eq!(x.chain_id, 0),
+            _ => panic!("Wrong transaction type"),
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_unsigned_transaction() -> Result<(), Box<dyn Error>> {
+        let data = "e10182271082271094e94f1fa4f27d9d288ffea234bb62e1fbc086ca0c8080018080";
+        let data = hex::decode(data)?;
+
+        let parsed = ParsedTransaction::decode(&data)?;
+
+        assert!(parsed.eip155);
+        assert!(parsed.from.is_none());
+        assert!(parsed.y_parity.is_none());
+        assert_eq!(parsed.hash, parsed.signing_hash);
+
+        Ok(())
+    }
+
+    #[test]
+    fn envelope_type() {
+        assert_eq!(Transaction::envelope_type(&[0xf8]).unwrap(), 0);
+        assert_eq!(Transaction::envelope_type(&[0x02]).unwrap(), 2);
+        assert!(Transaction::envelope_type(&[0x05]).is_err());
+        assert!(Transaction::envelope_type(&[]).is_err());
+    }
 }
@@ -16,7 +16,7 @@ pub struct Transaction1559 {
     pub max_priority_fee_per_gas: U256,
     pub gas_limit: U256,
     pub max_fee_per_gas: U256,
-    pub to: Address,
+    pub to: Option<Address>,
     pub value: U256,
     pub data: Vec<u8>,
     pub access_list: Vec<AccessList>,
@@ -39,7 +39,7 @@ impl Decodable for Transaction1559 {
         let max_fee_per_gas: U256 = rlp.val_at(3)?;
         let gas_limit: U256 = rlp.val_at(4)?;
 
-        let to = rlp.val_at(5)?;
+        let to = Address::decode_optional(&rlp.at(5)?)?;
 
         let value: U256 = rlp.val_at(6)?;
         let data: Vec<u8> = rlp.val_at(7)?;
@@ -93,7 +93,7 @@ impl Signable for Transaction1559 {
         rlp.append(&self.max_priority_fee_per_gas);
         rlp.append(&self.max_fee_per_gas);
         rlp.append(&self.gas_limit);
-        rlp.append(&self.to);
+        Address::append_optional(&self.to, rlp);
         rlp.append(&self.value);
         rlp.append(&self.data);
         rlp.append_list(&self.access_list);
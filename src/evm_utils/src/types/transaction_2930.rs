@@ -15,7 +15,7 @@ pub struct Transaction2930 {
     pub nonce: U256,
     pub gas_price: U256,
     pub gas_limit: U256,
-    pub to: Address,
+    pub to: Option<Address>,
     pub value: U256,
     pub data: Vec<u8>,
     pub access_list: Vec<AccessList>,
@@ -37,7 +37,7 @@ impl Decodable for Transaction2930 {
         let gas_price: U256 = rlp.val_at(2)?;
         let gas_limit: U256 = rlp.val_at(3)?;
 
-        let to = rlp.val_at(4)?;
+        let to = Address::decode_optional(&rlp.at(4)?)?;
 
         let value: U256 = rlp.val_at(5)?;
         let data: Vec<u8> = rlp.val_at(6)?;
@@ -89,7 +89,7 @@ impl Signable for Transaction2930 {
         rlp.append(&self.nonce);
         rlp.append(&self.gas_price);
         rlp.append(&self.gas_limit);
-        rlp.append(&self.to);
+        Address::append_optional(&self.to, rlp);
         rlp.append(&self.value);
         rlp.append(&self.data);
         rlp.append_list(&self.access_list);
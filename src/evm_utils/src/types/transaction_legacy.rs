@@ -14,7 +14,7 @@ pub struct TransactionLegacy {
     pub nonce: U256,
     pub gas_price: U256,
     pub gas_limit: U256,
-    pub to: Address,
+    pub to: Option<Address>,
     pub value: U256,
     pub data: Vec<u8>,
     pub sign: Option<Signature>,
@@ -34,7 +34,7 @@ impl Decodable for TransactionLegacy {
         let gas_price: U256 = rlp.val_at(1)?;
         let gas_limit: U256 = rlp.val_at(2)?;
 
-        let to = rlp.val_at(3)?;
+        let to = Address::decode_optional(&rlp.at(3)?)?;
 
         let value: U256 = rlp.val_at(4)?;
         let data: Vec<u8> = rlp.val_at(5)?;
@@ -53,14 +53,21 @@ impl Decodable for TransactionLegacy {
         if item_count == 9 {
             let v: u64 = rlp.val_at(6)?;
             let r: Vec<u8> = rlp.val_at(7)?;
+            let s: Vec<u8> = rlp.val_at(8)?;
+
+            // unsigned EIP-155 transactions have empty r and s
+            let signed = !r.is_empty() || !s.is_empty();
 
             if v >= 35 {
                 item.chain_id = (v - 35) / 2;
+            } else if signed && (v == 27 || v == 28) {
+                // signed before EIP-155, chain id is not a part of the signature
+                item.chain_id = 0;
             } else {
                 item.chain_id = v;
             }
 
-            if !r.is_empty() {
+            if signed {
                 let signature = Signature::create(&item, rlp, rlp.as_raw(), 6)
                     .map_err(|_| rlp::DecoderError::Custom("Error while recovering signature"))?;
 
@@ -89,7 +96,7 @@ impl Signable for TransactionLegacy {
         rlp.append(&self.nonce);
         rlp.append(&self.gas_price);
         rlp.append(&self.gas_limit);
-        rlp.append(&self.to);
+        Address::append_optional(&self.to, rlp);
         rlp.append(&self.value);
         rlp.append(&self.data);
 
@@ -99,7 +106,7 @@ impl Signable for TransactionLegacy {
                 rlp.append(&sign.r);
                 rlp.append(&sign.s);
             }
-        } else {
+        } else if self.chain_id != 0 {
             rlp.append(&self.chain_id);
             rlp.append(&"");
             rlp.append(&"");
@@ -173,4 +180,26 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn encode_decode_contract_creation() -> Result<(), Box<dyn Error>> {
+        let data = "e10182271082271094e94f1fa4f27d9d288ffea234bb62e1fbc086ca0c8080018080";
+        let data = hex::decode(data)?;
+
+        let mut tx = match Transaction::decode(&data)? {
+            Transaction::Legacy(x) => x,
+            _ => panic!("Wrong transaction type"),
+        };
+        tx.to = None;
+
+        let encoded = Transaction::Legacy(tx).encode(true);
+
+        match Transaction::decode(&encoded)? {
+            Transaction::Legacy(x) => {
+                assert!(x.to.is_none());
+                Ok(())
+            }
+            _ => panic!("Wrong transaction type"),
+        }
+    }
 }
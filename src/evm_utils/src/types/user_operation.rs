@@ -0,0 +1,439 @@
+use ic_cdk::export::candid::{CandidType, Deserialize};
+use primitive_types::U256 as Uint;
+
+use super::{
+    abi::{self, AbiValue, ParamType},
+    address::Address,
+    num::{H256, U256},
+};
+use crate::utils::keccak256;
+
+/// EntryPoint v0.6 deployment, same address on all chains
+pub const ENTRY_POINT_V06: &str = "5FF137D4b0FDCD49DcA30c7CF57E578a026d2789";
+/// EntryPoint v0.7 deployment, same address on all chains
+pub const ENTRY_POINT_V07: &str = "0000000071727De22E5E9d8BAf0edAc6f37da032";
+
+/// User operation of EntryPoint v0.6
+#[derive(CandidType, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct UserOperation {
+    pub sender: Address,
+    pub nonce: U256,
+    pub init_code: Vec<u8>,
+    pub call_data: Vec<u8>,
+    pub call_gas_limit: U256,
+    pub verification_gas_limit: U256,
+    pub pre_verification_gas: U256,
+    pub max_fee_per_gas: U256,
+    pub max_priority_fee_per_gas: U256,
+    pub paymaster_and_data: Vec<u8>,
+    pub signature: Vec<u8>,
+}
+
+/// User operation of EntryPoint v0.7, gas limits and fees are packed in to 32 bytes
+#[derive(CandidType, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct PackedUserOperation {
+    pub sender: Address,
+    pub nonce: U256,
+    pub init_code: Vec<u8>,
+    pub call_data: Vec<u8>,
+    /// verificationGasLimit (16 bytes) ++ callGasLimit (16 bytes)
+    pub account_gas_limits: H256,
+    pub pre_verification_gas: U256,
+    /// maxPriorityFeePerGas (16 bytes) ++ maxFeePerGas (16 bytes)
+    pub gas_fees: H256,
+    pub paymaster_and_data: Vec<u8>,
+    pub signature: Vec<u8>,
+}
+
+/// Operations passed to `handleOps` of an EntryPoint
+#[derive(CandidType, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub enum Bundle {
+    V06(Vec<UserOperation>),
+    V07(Vec<PackedUserOperation>),
+}
+
+/// Decoded `handleOps` call
+#[derive(CandidType, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct HandleOps {
+    pub bundle: Bundle,
+    pub beneficiary: Address,
+}
+
+fn hash(data: &[u8]) -> AbiValue {
+    AbiValue::FixedBytes(keccak256(&[data]).0.to_vec())
+}
+
+fn word(value: &H256) -> AbiValue {
+    AbiValue::FixedBytes(value.0.to_vec())
+}
+
+/// Hash of packed operation signed by the account, binds it to the EntryPoint and chain
+fn user_operation_hash(packed: &[u8], entry_point: &Address, chain_id: u64) -> H256 {
+    let encoded = abi::encode(&[
+        hash(packed),
+        AbiValue::Address(entry_point.clone()),
+        AbiValue::Uint(U256::from(chain_id)),
+    ]);
+
+    keccak256(&[&encoded]).into()
+}
+
+/// Packs two values that fit in 128 bits in to one word, high ++ low
+pub fn pack_uints(high: &U256, low: &U256) -> Option<H256> {
+    let max = Uint::from(u128::MAX);
+    let (high, low) = (Uint::from(high), Uint::from(low));
+    if high > max || low > max {
+        return None;
+    }
+
+    Some(U256::from((high << 128) | low).into())
+}
+
+impl UserOperation {
+    fn param_type() -> ParamType {
+        use ParamType::{Address as A, Bytes as B};
+        let uint = || ParamType::Uint(256);
+
+        ParamType::Tuple(vec![
+            A,
+            uint(),
+            B,
+            B,
+            uint(),
+            uint(),
+            uint(),
+            uint(),
+            uint(),
+            B,
+            B,
+        ])
+    }
+
+    fn to_value(&self) -> AbiValue {
+        AbiValue::Tuple(vec![
+            AbiValue::Address(self.sender.clone()),
+            AbiValue::Uint(self.nonce.clone()),
+            AbiValue::Bytes(self.init_code.clone()),
+            AbiValue::Bytes(self.call_data.clone()),
+            AbiValue::Uint(self.call_gas_limit.clone()),
+            AbiValue::Uint(self.verification_gas_limit.clone()),
+            AbiValue::Uint(self.pre_verification_gas.clone()),
+            AbiValue::Uint(self.max_fee_per_gas.clone()),
+            AbiValue::Uint(self.max_priority_fee_per_gas.clone()),
+            AbiValue::Bytes(self.paymaster_and_data.clone()),
+            AbiValue::Bytes(self.signature.clone()),
+        ])
+    }
+
+    fn from_value(value: &AbiValue) -> Option<Self> {
+        use AbiValue::{Address as A, Bytes as B, Uint as U};
+
+        match value {
+            AbiValue::Tuple(x) => match x.as_slice() {
+                [A(sender), U(nonce), B(init_code), B(call_data), U(call_gas_limit), U(verification_gas_limit), U(pre_verification_gas), U(max_fee_per_gas), U(max_priority_fee_per_gas), B(paymaster_and_data), B(signature)] => {
+                    Some(Self {
+                        sender: sender.clone(),
+                        nonce: nonce.clone(),
+                        init_code: init_code.clone(),
+                        call_data: call_data.clone(),
+                        call_gas_limit: call_gas_limit.clone(),
+                        verification_gas_limit: verification_gas_limit.clone(),
+                        pre_verification_gas: pre_verification_gas.clone(),
+                        max_fee_per_gas: max_fee_per_gas.clone(),
+                        max_priority_fee_per_gas: max_priority_fee_per_gas.clone(),
+                        paymaster_and_data: paymaster_and_data.clone(),
+                        signature: signature.clone(),
+                    })
+                }
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Encodes operation without signature, dynamic fields are replaced with their hashes
+    pub fn pack(&self) -> Vec<u8> {
+        abi::encode(&[
+            AbiValue::Address(self.sender.clone()),
+            AbiValue::Uint(self.nonce.clone()),
+            hash(&self.init_code),
+            hash(&self.call_data),
+            AbiValue::Uint(self.call_gas_limit.clone()),
+            AbiValue::Uint(self.verification_gas_limit.clone()),
+            AbiValue::Uint(self.pre_verification_gas.clone()),
+            AbiValue::Uint(self.max_fee_per_gas.clone()),
+            AbiValue::Uint(self.max_priority_fee_per_gas.clone()),
+            hash(&self.paymaster_and_data),
+        ])
+    }
+
+    /// `getUserOpHash` of the EntryPoint
+    pub fn hash(&self, entry_point: &Address, chain_id: u64) -> H256 {
+        user_operation_hash(&self.pack(), entry_point, chain_id)
+    }
+}
+
+impl PackedUserOperation {
+    fn param_type() -> ParamType {
+        use ParamType::{Address as A, Bytes as B};
+
+        ParamType::Tuple(vec![
+            A,
+            ParamType::Uint(256),
+            B,
+            B,
+            ParamType::FixedBytes(32),
+            ParamType::Uint(256),
+            ParamType::FixedBytes(32),
+            B,
+            B,
+        ])
+    }
+
+    fn to_value(&self) -> AbiValue {
+        AbiValue::Tuple(vec![
+            AbiValue::Address(self.sender.clone()),
+            AbiValue::Uint(self.nonce.clone()),
+            AbiValue::Bytes(self.init_code.clone()),
+            AbiValue::Bytes(self.call_data.clone()),
+            word(&self.account_gas_limits),
+            AbiValue::Uint(self.pre_verification_gas.clone()),
+            word(&self.gas_fees),
+            AbiValue::Bytes(self.paymaster_and_data.clone()),
+            AbiValue::Bytes(self.signature.clone()),
+        ])
+    }
+
+    fn from_value(value: &AbiValue) -> Option<Self> {
+        use AbiValue::{Address as A, Bytes as B, FixedBytes as F, Uint as U};
+
+        let word = |x: &[u8]| Some(H256(x.try_into().ok()?));
+
+        match value {
+            AbiValue::Tuple(x) => match x.as_slice() {
+                [A(sender), U(nonce), B(init_code), B(call_data), F(account_gas_limits), U(pre_verification_gas), F(gas_fees), B(paymaster_and_data), B(signature)] => {
+                    Some(Self {
+                        sender: sender.clone(),
+                        nonce: nonce.clone(),
+                        init_code: init_code.clone(),
+                        call_data: call_data.clone(),
+                        account_gas_limits: word(account_gas_limits)?,
+                        pre_verification_gas: pre_verification_gas.clone(),
+                        gas_fees: word(gas_fees)?,
+                        paymaster_and_data: paymaster_and_data.clone(),
+                        signature: signature.clone(),
+                    })
+                }
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Encodes operation without signature, dynamic fields are replaced with their hashes
+    pub fn pack(&self) -> Vec<u8> {
+        abi::encode(&[
+            AbiValue::Address(self.sender.clone()),
+            AbiValue::Uint(self.nonce.clone()),
+            hash(&self.init_code),
+            hash(&self.call_data),
+            word(&self.account_gas_limits),
+            AbiValue::Uint(self.pre_verification_gas.clone()),
+            word(&self.gas_fees),
+            hash(&self.paymaster_and_data),
+        ])
+    }
+
+    /// `getUserOpHash` of the EntryPoint
+    pub fn hash(&self, entry_point: &Address, chain_id: u64) -> H256 {
+        user_operation_hash(&self.pack(), entry_point, chain_id)
+    }
+}
+
+impl Bundle {
+    fn param_type(&self) -> ParamType {
+        let operation = match self {
+            Bundle::V06(_) => UserOperation::param_type(),
+            Bundle::V07(_) => PackedUserOperation::param_type(),
+        };
+
+        ParamType::Array(Box::new(operation))
+    }
+
+    fn signature(&self) -> String {
+        format!("handleOps({},address)", self.param_type().signature())
+    }
+}
+
+impl HandleOps {
+    /// Call data of `handleOps(ops, beneficiary)`
+    pub fn encode(&self) -> Vec<u8> {
+        let operations = match &self.bundle {
+            Bundle::V06(x) => x.iter().map(|x| x.to_value()).collect(),
+            Bundle::V07(x) => x.iter().map(|x| x.to_value()).collect(),
+        };
+
+        let mut data = abi::selector(&self.bundle.signature()).to_vec();
+        data.extend(abi::encode(&[
+            AbiValue::Array(operations),
+            AbiValue::Address(self.beneficiary.clone()),
+        ]));
+        data
+    }
+
+    /// Decodes `handleOps` call data of v0.6 or v0.7 EntryPoint, returns None for other calls
+    pub fn decode(data: &[u8]) -> Option<Self> {
+        if data.len() < 4 {
+            return None;
+        }
+
+        let (selector, params) = data.split_at(4);
+        let bundle = [Bundle::V06(vec![]), Bundle::V07(vec![])]
+            .into_iter()
+            .find(|x| abi::selector(&x.signature()) == selector)?;
+        let values = abi::decode(&[bundle.param_type(), ParamType::Address], params).ok()?;
+
+        let (operations, beneficiary) = match values.as_slice() {
+            [AbiValue::Array(operations), AbiValue::Address(beneficiary)] => {
+                (operations, beneficiary.clone())
+            }
+            _ => return None,
+        };
+
+        let bundle = match bundle {
+            Bundle::V06(_) => Bundle::V06(
+                operations
+                    .iter()
+                    .map(UserOperation::from_value)
+                    .collect::<Option<_>>()?,
+            ),
+            Bundle::V07(_) => Bundle::V07(
+                operations
+                    .iter()
+                    .map(PackedUserOperation::from_value)
+                    .collect::<Option<_>>()?,
+            ),
+        };
+
+        Some(Self {
+            bundle,
+            beneficiary,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{error::Error, str::FromStr};
+
+    use super::{
+        pack_uints, Bundle, HandleOps, PackedUserOperation, UserOperation, ENTRY_POINT_V06,
+        ENTRY_POINT_V07,
+    };
+    use crate::types::{
+        address::Address,
+        num::{H256, U256},
+    };
+
+    fn operation() -> UserOperation {
+        UserOperation {
+            sender: Address([0x11; 20]),
+            nonce: U256::from(7u64),
+            init_code: vec![],
+            call_data: vec![0xb6, 0x1d, 0x27, 0xf6],
+            call_gas_limit: U256::from(100_000u64),
+            verification_gas_limit: U256::from(200_000u64),
+            pre_verification_gas: U256::from(50_000u64),
+            max_fee_per_gas: U256::from(30_000_000_000u64),
+            max_priority_fee_per_gas: U256::from(1_000_000_000u64),
+            paymaster_and_data: vec![],
+            signature: vec![0xaa; 65],
+        }
+    }
+
+    fn packed_operation() -> Result<PackedUserOperation, Box<dyn Error>> {
+        let op = operation();
+
+        Ok(PackedUserOperation {
+            sender: op.sender,
+            nonce: op.nonce,
+            init_code: op.init_code,
+            call_data: op.call_data,
+            account_gas_limits: pack_uints(&op.verification_gas_limit, &op.call_gas_limit)
+                .ok_or("Gas limit overflow")?,
+            pre_verification_gas: op.pre_verification_gas,
+            gas_fees: pack_uints(&op.max_priority_fee_per_gas, &op.max_fee_per_gas)
+                .ok_or("Fee overflow")?,
+            paymaster_and_data: op.paymaster_and_data,
+            signature: op.signature,
+        })
+    }
+
+    #[test]
+    fn user_operation_hash() -> Result<(), Box<dyn Error>> {
+        let entry_point = Address::from_str(ENTRY_POINT_V06)?;
+
+        assert_eq!(
+            operation().hash(&entry_point, 1),
+            H256(
+                hex::decode("64ac56f951952c9d1d9f20d74dc196f80f4a523f520abb740c651ebbb3b21453")?
+                    .try_into()
+                    .map_err(|_| "Invalid hash")?
+            )
+        );
+        // signature is not part of the hash
+        let mut op = operation();
+        op.signature = vec![];
+        assert_eq!(op.hash(&entry_point, 1), operation().hash(&entry_point, 1));
+        assert_ne!(op.hash(&entry_point, 5), operation().hash(&entry_point, 1));
+
+        Ok(())
+    }
+
+    #[test]
+    fn packed_user_operation_hash() -> Result<(), Box<dyn Error>> {
+        let entry_point = Address::from_str(ENTRY_POINT_V07)?;
+        let op = packed_operation()?;
+
+        assert_eq!(
+            hex::encode(op.account_gas_limits.0),
+            "00000000000000000000000000030d40000000000000000000000000000186a0"
+        );
+        assert_eq!(
+            op.hash(&entry_point, 1),
+            H256(
+                hex::decode("f3148e6b782de048afb3d0944df59be7b81b3a596429573a28cad6bc33c09f07")?
+                    .try_into()
+                    .map_err(|_| "Invalid hash")?
+            )
+        );
+        assert_eq!(pack_uints(&U256([0xff; 32]), &U256::zero()), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn handle_ops() -> Result<(), Box<dyn Error>> {
+        let beneficiary = Address([0x22; 20]);
+
+        let call = HandleOps {
+            bundle: Bundle::V06(vec![operation(), operation()]),
+            beneficiary: beneficiary.clone(),
+        };
+        let data = call.encode();
+        assert_eq!(data[..4], [0x1f, 0xad, 0x94, 0x8c]);
+        assert_eq!(HandleOps::decode(&data), Some(call));
+
+        let call = HandleOps {
+            bundle: Bundle::V07(vec![packed_operation()?]),
+            beneficiary,
+        };
+        let data = call.encode();
+        assert_eq!(data[..4], [0x76, 0x5e, 0x82, 0x7f]);
+        assert_eq!(HandleOps::decode(&data), Some(call));
+
+        assert_eq!(HandleOps::decode(&data[..100]), None);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,246 @@
+use ic_cdk::export::candid::{CandidType, Deserialize};
+
+use super::{num::U256, signature::Signature, transaction::Transaction};
+
+/// Maximum size of init code, as introduced in EIP-3860
+pub const MAX_INIT_CODE_SIZE: u64 = 49_152;
+
+/// Half of the secp256k1 curve order, signatures with higher s are rejected by EIP-2
+const SECP256K1_HALF_N: [u8; 32] = [
+    0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    0x5d, 0x57, 0x6e, 0x73, 0x57, 0xa4, 0x50, 0x1d, 0xdf, 0xe9, 0x2f, 0x46, 0x68, 0x1b, 0x20, 0xa0,
+];
+
+/// Rules of a chain that transaction is validated against
+#[derive(CandidType, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct ValidationRules {
+    /// Expected chain id, not checked if not provided
+    pub chain_id: Option<u64>,
+    /// Maximum size of init code, defaults to the EIP-3860 limit
+    pub max_init_code_size: Option<u64>,
+    /// Reject legacy transactions signed without chain id
+    pub eip155_required: bool,
+}
+
+#[derive(CandidType, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub enum Violation {
+    UnsupportedType {
+        tx_type: u8,
+    },
+    ChainIdMismatch {
+        expected: u64,
+        actual: u64,
+    },
+    NotReplayProtected,
+    PriorityFeeAboveMaxFee,
+    NonceTooLarge,
+    GasLimitTooLarge,
+    InitCodeTooLarge {
+        size: u64,
+        limit: u64,
+    },
+    InvalidR,
+    InvalidS,
+    HighS,
+    /// r and s are in range, but sender can not be recovered
+    InvalidSignature,
+}
+
+/// Checks transaction against rules enforced by nodes, returns all found violations
+pub fn validate(tx: &Transaction, rules: &ValidationRules) -> Vec<Violation> {
+    let mut violations = vec![];
+
+    if let Some(expected) = rules.chain_id {
+        let actual = tx.chain_id();
+        if actual != expected {
+            violations.push(Violation::ChainIdMismatch { expected, actual });
+        }
+    }
+
+    if rules.eip155_required && !tx.is_eip155() {
+        violations.push(Violation::NotReplayProtected);
+    }
+
+    if let Transaction::EIP1559(x) = tx {
+        if x.max_priority_fee_per_gas > x.max_fee_per_gas {
+            violations.push(Violation::PriorityFeeAboveMaxFee);
+        }
+    }
+
+    // EIP-2681, nonce is limited to 2^64-1
+    if tx.nonce().as_u64().is_none() {
+        violations.push(Violation::NonceTooLarge);
+    }
+
+    if tx.gas_limit().as_u64().is_none() {
+        violations.push(Violation::GasLimitTooLarge);
+    }
+
+    let limit = rules.max_init_code_size.unwrap_or(MAX_INIT_CODE_SIZE);
+    let size = tx.data().len() as u64;
+    if tx.to().is_none() && size > limit {
+        violations.push(Violation::InitCodeTooLarge { size, limit });
+    }
+
+    if let Some(sign) = tx.signature() {
+        violations.extend(validate_signature(sign));
+    }
+
+    violations
+}
+
+/// Checks r and s ranges (EIP-2) and that sender was recovered
+pub fn validate_signature(sign: &Signature) -> Vec<Violation> {
+    let mut violations = vec![];
+
+    if sign.r.is_empty() || sign.r.len() > 32 || sign.r.iter().all(|x| *x == 0) {
+        violations.push(Violation::InvalidR);
+    }
+
+    if sign.s.is_empty() || sign.s.len() > 32 || sign.s.iter().all(|x| *x == 0) {
+        violations.push(Violation::InvalidS);
+    } else {
+        let mut s = [0u8; 32];
+        s[32 - sign.s.len()..].copy_from_slice(&sign.s);
+
+        if U256(s) > U256(SECP256K1_HALF_N) {
+            violations.push(Violation::HighS);
+        }
+    }
+
+    if violations.is_empty() && sign.from.is_none() {
+        violations.push(Violation::InvalidSignature);
+    }
+
+    violations
+}
+
+#[cfg(test)]
+mod test {
+    use std::error::Error;
+
+    use super::{validate, ValidationRules, Violation, SECP256K1_HALF_N};
+    use crate::types::{
+        address::Address, num::U256, signature::Signature, transaction::Transaction,
+        transaction_1559::Transaction1559,
+    };
+
+    const SIGNED_TX: &str = "02f8b10108840f7f4900850519f0118083055845943fe65692bfcd0e6cf84cb1e7d24108e434a7587e80b8447050ccd90000000000000000000000005b1578681d43931030fffe066a072133842dde430000000000000000000000000000000000000000000000000000000000000001c001a0cac74d8e73874331e4ba78809a7b30574c76d35c43ea00983c76752b65a0632aa04b6caeac4c693f082b8bd4d2ad349848bc71b9d32a419e33a5a303a3b3f4e8c8";
+
+    /// Encodes signed transaction with replaced r and s, as received from outside
+    fn with_signature(r: Vec<u8>, s: Vec<u8>) -> Result<Vec<u8>, Box<dyn Error>> {
+        let mut tx = match Transaction::decode(&hex::decode(SIGNED_TX)?)? {
+            Transaction::EIP1559(x) => x,
+            _ => return Err("Wrong transaction type".into()),
+        };
+
+        if let Some(sign) = tx.sign.as_mut() {
+            sign.r = r;
+            sign.s = s;
+        }
+
+        Ok(Transaction::EIP1559(tx).encode(false).to_vec())
+    }
+
+    fn rules() -> ValidationRules {
+        ValidationRules {
+            chain_id: Some(1),
+            max_init_code_size: None,
+            eip155_required: true,
+        }
+    }
+
+    #[test]
+    fn validate_valid_transaction() -> Result<(), Box<dyn Error>> {
+        let tx = Transaction::decode(&hex::decode(SIGNED_TX)?)?;
+
+        assert!(validate(&tx, &rules()).is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn validate_invalid_transaction() {
+        let mut high_s = SECP256K1_HALF_N.to_vec();
+        high_s[31] += 1;
+
+        let tx = Transaction::EIP1559(Transaction1559 {
+            chain_id: 5,
+            nonce: U256([0xffu8; 32]),
+            max_priority_fee_per_gas: U256::from(2u64),
+            max_fee_per_gas: U256::from(1u64),
+            gas_limit: U256([0xffu8; 32]),
+            to: None,
+            value: U256::zero(),
+            data: vec![0u8; 50_000],
+            access_list: vec![],
+            sign: Some(Signature {
+                v: 0,
+                r: vec![],
+                s: high_s,
+                from: Some(Address([0u8; 20])),
+                hash: U256::zero(),
+            }),
+        });
+
+        let violations = validate(&tx, &rules());
+
+        assert_eq!(
+            violations,
+            vec![
+                Violation::ChainIdMismatch {
+                    expected: 1,
+                    actual: 5
+                },
+                Violation::PriorityFeeAboveMaxFee,
+                Violation::NonceTooLarge,
+                Violation::GasLimitTooLarge,
+                Violation::InitCodeTooLarge {
+                    size: 50_000,
+                    limit: 49_152
+                },
+                Violation::InvalidR,
+                Violation::HighS,
+            ]
+        );
+    }
+
+    #[test]
+    fn validate_raw_signature() -> Result<(), Box<dyn Error>> {
+        let validate_raw = |data: &[u8]| -> Result<Vec<Violation>, Box<dyn Error>> {
+            let mut rules = rules();
+            rules.eip155_required = false;
+
+            // sender of these transactions can not be recovered
+            assert!(Transaction::decode(data).is_err());
+            Ok(validate(&Transaction::decode_unchecked(data)?, &rules))
+        };
+
+        let zero = with_signature(vec![0; 32], vec![1])?;
+        assert_eq!(validate_raw(&zero)?, vec![Violation::InvalidR]);
+
+        let long = with_signature(vec![1; 33], vec![0; 33])?;
+        assert_eq!(
+            validate_raw(&long)?,
+            vec![Violation::InvalidR, Violation::InvalidS]
+        );
+
+        let empty = with_signature(vec![], vec![1])?;
+        assert_eq!(validate_raw(&empty)?, vec![Violation::InvalidR]);
+
+        // legacy transaction with empty r and non empty s is signed
+        let legacy = hex::decode(
+            "e50182271082271094e94f1fa4f27d9d288ffea234bb62e1fbc086ca0c8080258084deadbeef",
+        )?;
+        assert_eq!(validate_raw(&legacy)?, vec![Violation::InvalidR]);
+
+        // r has valid length, but exceeds curve order
+        let unrecoverable = with_signature(vec![0xff; 32], vec![1])?;
+        assert_eq!(
+            validate_raw(&unrecoverable)?,
+            vec![Violation::InvalidSignature]
+        );
+
+        Ok(())
+    }
+}
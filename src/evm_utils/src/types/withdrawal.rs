@@ -0,0 +1,72 @@
+use ic_cdk::export::candid::{CandidType, Deserialize};
+use rlp::{Decodable, Encodable, RlpStream};
+
+use super::{address::Address, num::U256};
+
+/// Withdrawal from the beacon chain, introduced in Shanghai (EIP-4895)
+#[derive(CandidType, Deserialize, Clone, PartialEq, Eq)]
+pub struct Withdrawal {
+    pub index: u64,
+    pub validator_index: u64,
+    pub address: Address,
+    /// Amount in gwei
+    pub amount: U256,
+}
+
+impl Decodable for Withdrawal {
+    fn decode(rlp: &rlp::Rlp) -> Result<Self, rlp::DecoderError> {
+        let item_count = rlp.item_count()?;
+        if item_count != 4 {
+            return Err(rlp::DecoderError::Custom(
+                "Invalid parameters for withdrawal",
+            ));
+        }
+
+        Ok(Self {
+            index: rlp.val_at(0)?,
+            validator_index: rlp.val_at(1)?,
+            address: rlp.val_at(2)?,
+            amount: rlp.val_at(3)?,
+        })
+    }
+}
+
+impl Encodable for Withdrawal {
+    fn rlp_append(&self, rlp: &mut RlpStream) {
+        rlp.begin_list(4);
+        rlp.append(&self.index);
+        rlp.append(&self.validator_index);
+        rlp.append(&self.address);
+        rlp.append(&self.amount);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::error::Error;
+    use std::str::FromStr;
+
+    use super::Withdrawal;
+    use crate::types::{address::Address, num::U256};
+
+    #[test]
+    fn encode_decode_withdrawal() -> Result<(), Box<dyn Error>> {
+        let item = Withdrawal {
+            index: 0x1a2b,
+            validator_index: 0x3c,
+            address: Address::from_str("0x388c818ca8b9251b393131c08a736a67ccb19297")?,
+            amount: U256::from(0x0f4240u64),
+        };
+
+        let encoded = rlp::encode(&item);
+        assert_eq!(
+            hex::encode(&encoded),
+            "dd821a2b3c94388c818ca8b9251b393131c08a736a67ccb19297830f4240"
+        );
+
+        let decoded: Withdrawal = rlp::decode(&encoded)?;
+        assert!(decoded == item);
+
+        Ok(())
+    }
+}
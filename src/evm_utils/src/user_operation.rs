@@ -0,0 +1,61 @@
+use std::str::FromStr;
+
+use candid::candid_method;
+use ic_cdk_macros::query;
+
+use crate::types::{
+    address::Address,
+    num::{H256, U256},
+    user_operation::{
+        pack_uints, HandleOps, PackedUserOperation, UserOperation, ENTRY_POINT_V06, ENTRY_POINT_V07,
+    },
+};
+
+/// Returns given EntryPoint or its canonical deployment
+fn entry_point(entry_point: Option<Address>, default: &str) -> Address {
+    entry_point.unwrap_or_else(|| Address::from_str(default).expect("Invalid EntryPoint address"))
+}
+
+/// Calculates `getUserOpHash` of EntryPoint v0.6 for the operation, canonical EntryPoint
+/// is used when it is not given
+#[query]
+#[candid_method(query)]
+fn user_operation_hash(
+    operation: UserOperation,
+    entry_point: Option<Address>,
+    chain_id: u64,
+) -> H256 {
+    operation.hash(&self::entry_point(entry_point, ENTRY_POINT_V06), chain_id)
+}
+
+/// Calculates `getUserOpHash` of EntryPoint v0.7 for the operation, canonical EntryPoint
+/// is used when it is not given
+#[query]
+#[candid_method(query)]
+fn packed_user_operation_hash(
+    operation: PackedUserOperation,
+    entry_point: Option<Address>,
+    chain_id: u64,
+) -> H256 {
+    operation.hash(&self::entry_point(entry_point, ENTRY_POINT_V07), chain_id)
+}
+
+/// Packs two 128 bit values, like verification and call gas limits, in to one word
+#[query]
+#[candid_method(query)]
+fn pack_gas_values(high: U256, low: U256) -> Result<H256, String> {
+    pack_uints(&high, &low).ok_or(String::from("Value does not fit in 128 bits"))
+}
+
+#[query]
+#[candid_method(query)]
+fn handle_ops_call_data(call: HandleOps) -> Vec<u8> {
+    call.encode()
+}
+
+/// Decodes user operations of a bundle, returns None when data is not a `handleOps` call
+#[query]
+#[candid_method(query)]
+fn decode_handle_ops(data: Vec<u8>) -> Option<HandleOps> {
+    HandleOps::decode(&data)
+}
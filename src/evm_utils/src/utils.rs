@@ -22,6 +22,25 @@ pub fn keccak256(data: &[&[u8]]) -> U256 {
     U256::from(hasher.finalize().as_ref())
 }
 
+/// Hash of a message signed with `personal_sign` (EIP-191),
+/// keccak256("\x19Ethereum Signed Message:\n" ++ len(message) ++ message)
+pub fn personal_message_hash(message: &[u8]) -> U256 {
+    keccak256(&[
+        b"\x19Ethereum Signed Message:\n",
+        message.len().to_string().as_bytes(),
+        message,
+    ])
+}
+
+/// Returns y-parity (recovery id) of a signature, supports raw, pre EIP-155 and EIP-155 v values
+pub fn y_parity(v: u64) -> u64 {
+    match v {
+        27 | 28 => v - 27,
+        v if v >= 35 => (v - 35) % 2,
+        v => v,
+    }
+}
+
 /// Recovers public key of a message signer
 pub fn _recover_public_key(
     r: &[u8],
@@ -29,26 +48,51 @@ pub fn _recover_public_key(
     v: u64,
     msg: &[u8],
 ) -> Result<PublicKey, Box<dyn Error>> {
-    let mut sign = [0u8; 64];
-
-    sign[..32].copy_from_slice(&r[..32]);
-    sign[32..].copy_from_slice(&s[..32]);
+    recover_from_hash(r, s, v, &keccak256(&[msg]).0)
+}
 
-    let mut rec_id = v;
-    if rec_id > 1 {
-        rec_id -= 37;
+/// Recovers public key of a signer of already hashed message, like EIP-712 digest
+pub fn recover_from_hash(
+    r: &[u8],
+    s: &[u8],
+    v: u64,
+    hash: &[u8; 32],
+) -> Result<PublicKey, Box<dyn Error>> {
+    if r.len() > 32 || s.len() > 32 {
+        return Err(Box::new(secp256k1::Error::InvalidSignature));
     }
 
-    let rec_id = RecoveryId::from_i32(rec_id as i32)?;
+    // r and s can be shorter than 32 bytes when encoded in rlp, as leading zeros are stripped
+    let mut sign = [0u8; 64];
+
+    sign[32 - r.len()..32].copy_from_slice(r);
+    sign[64 - s.len()..].copy_from_slice(s);
+
+    let rec_id = RecoveryId::from_i32(y_parity(v) as i32)?;
     let rec_sig = RecoverableSignature::from_compact(&sign, rec_id)?;
 
-    let hash = keccak256(&[msg]);
-    let msg = Message::from_slice(&hash.0)?;
+    let msg = Message::from_slice(hash)?;
 
     let pub_k = rec_sig.recover(&msg)?;
     Ok(pub_k)
 }
 
+/// Recovers address of a signer from 65 bytes signature (r, s, v) of a hash
+pub fn recover_address(signature: &[u8], hash: &[u8; 32]) -> Result<Address, Box<dyn Error>> {
+    if signature.len() != 65 {
+        return Err(Box::new(secp256k1::Error::InvalidSignature));
+    }
+
+    let key = recover_from_hash(
+        &signature[..32],
+        &signature[32..64],
+        signature[64] as u64,
+        hash,
+    )?;
+
+    Ok(Address::from(key))
+}
+
 #[query]
 #[candid_method(query)]
 fn recover_public_key(signature: Vec<u8>, msg: Vec<u8>) -> Result<Vec<u8>, String> {
@@ -67,38 +111,79 @@ fn recover_public_key(signature: Vec<u8>, msg: Vec<u8>) -> Result<Vec<u8>, Strin
     }
 }
 
+/// Reads SEC1 compressed (33 bytes), uncompressed (65 bytes) or raw (64 bytes, without 0x04
+/// prefix) public key
+pub fn parse_public_key(public_key: &[u8]) -> Result<PublicKey, secp256k1::Error> {
+    if public_key.len() == 64 {
+        let mut key = [0u8; 65];
+        key[0] = 0x04;
+        key[1..].copy_from_slice(public_key);
+
+        return PublicKey::from_slice(&key);
+    }
+
+    PublicKey::from_slice(public_key)
+}
+
+/// Accepts compressed, uncompressed or raw public key
 #[query]
 #[candid_method(query)]
 fn pub_to_address(public_key: Vec<u8>) -> Result<Vec<u8>, String> {
-    let pub_k = PublicKey::from_slice(&public_key[..])
-        .map_err(|x| format!("Error while reading public key {x}"))?;
+    let pub_k =
+        parse_public_key(&public_key).map_err(|x| format!("Error while reading public key {x}"))?;
 
     let addr = Address::from(pub_k);
 
     Ok(addr.0.to_vec())
 }
 
+/// Accepts compressed, uncompressed or raw public key
 #[query]
 #[candid_method(query)]
 fn is_valid_public(public_key: Vec<u8>) -> Result<(), String> {
-    PublicKey::from_slice(&public_key[..])
-        .map_err(|x| format!("Error while reading public key {x}"))?;
+    parse_public_key(&public_key).map_err(|x| format!("Error while reading public key {x}"))?;
 
     Ok(())
 }
 
+/// Converts public key to 33 bytes SEC1 compressed form, like keys of threshold ECDSA
+#[query]
+#[candid_method(query)]
+fn compress_public_key(public_key: Vec<u8>) -> Result<Vec<u8>, String> {
+    let key =
+        parse_public_key(&public_key).map_err(|x| format!("Error while reading public key {x}"))?;
+
+    Ok(key.serialize().to_vec())
+}
+
+/// Converts public key to 65 bytes SEC1 uncompressed form, with 0x04 prefix
+#[query]
+#[candid_method(query)]
+fn decompress_public_key(public_key: Vec<u8>) -> Result<Vec<u8>, String> {
+    let key =
+        parse_public_key(&public_key).map_err(|x| format!("Error while reading public key {x}"))?;
+
+    Ok(key.serialize_uncompressed().to_vec())
+}
+
+/// Converts public key to 64 bytes raw form, uncompressed key without 0x04 prefix
+#[query]
+#[candid_method(query)]
+fn raw_public_key(public_key: Vec<u8>) -> Result<Vec<u8>, String> {
+    let key =
+        parse_public_key(&public_key).map_err(|x| format!("Error while reading public key {x}"))?;
+
+    Ok(key.serialize_uncompressed()[1..].to_vec())
+}
+
 #[query]
 #[candid_method(query)]
 fn is_valid_signature(signature: Vec<u8>) -> Result<(), String> {
     if signature.len() != 65 {
         Err(String::from("Invalid signature length!, should be 65"))
     } else {
-        let mut rec_id = signature[64];
-        if rec_id > 1 {
-            rec_id -= 37;
-        }
-        let rec_id =
-            RecoveryId::from_i32(rec_id as i32).map_err(|x| format!("Recovery Id error {x}"))?;
+        let rec_id = RecoveryId::from_i32(y_parity(signature[64] as u64) as i32)
+            .map_err(|x| format!("Recovery Id error {x}"))?;
 
         RecoverableSignature::from_compact(&signature[..64], rec_id)
             .map_err(|x| format!("Recoverable signature error {x}"))?;
@@ -106,3 +191,38 @@ fn is_valid_signature(signature: Vec<u8>) -> Result<(), String> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::error::Error;
+
+    use secp256k1::{SecretKey, SECP256K1};
+
+    use super::{
+        compress_public_key, decompress_public_key, is_valid_public, pub_to_address, raw_public_key,
+    };
+
+    #[test]
+    fn public_key_forms() -> Result<(), Box<dyn Error>> {
+        let key = SecretKey::from_slice(&[0x46; 32])?.public_key(SECP256K1);
+        let compressed = key.serialize().to_vec();
+        let uncompressed = key.serialize_uncompressed().to_vec();
+        let raw = uncompressed[1..].to_vec();
+
+        for form in [&compressed, &uncompressed, &raw] {
+            assert_eq!(compress_public_key(form.clone())?, compressed);
+            assert_eq!(decompress_public_key(form.clone())?, uncompressed);
+            assert_eq!(raw_public_key(form.clone())?, raw);
+            assert_eq!(
+                pub_to_address(form.clone())?,
+                pub_to_address(uncompressed.clone())?
+            );
+            is_valid_public(form.clone())?;
+        }
+
+        assert!(is_valid_public(vec![0x04; 64]).is_err());
+        assert!(compress_public_key(compressed[1..].to_vec()).is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,18 @@
+use candid::candid_method;
+use ic_cdk_macros::query;
+
+use crate::types::{
+    transaction::Transaction,
+    validation::{validate, ValidationRules, Violation},
+};
+
+/// Validates raw transaction against chain rules, returns list of found violations.
+/// Transaction is valid if the list is empty
+#[query]
+#[candid_method(query)]
+fn validate_transaction(data: Vec<u8>, rules: ValidationRules) -> Result<Vec<Violation>, String> {
+    let tx = Transaction::decode_unchecked(&data)
+        .map_err(|x| format!("Error while decoding transaction {x}"))?;
+
+    Ok(validate(&tx, &rules))
+}
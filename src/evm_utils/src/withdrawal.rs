@@ -0,0 +1,28 @@
+use candid::candid_method;
+use ic_cdk_macros::query;
+
+use crate::{
+    tree::ordered_trie_root,
+    types::{num::U256, requests::ExecutionRequests, withdrawal::Withdrawal},
+};
+
+/// Calculates withdrawalsRoot of block header from list of withdrawals
+#[query]
+#[candid_method(query)]
+fn withdrawals_root(withdrawals: Vec<Withdrawal>) -> Result<U256, String> {
+    let values: Vec<Vec<u8>> = withdrawals
+        .iter()
+        .map(|x| rlp::encode(x).to_vec())
+        .collect();
+
+    ordered_trie_root(&values).map_err(|x| format!("Error while calculating root {x}"))
+}
+
+/// Calculates requestsHash of block header from execution layer requests (EIP-7685)
+#[query]
+#[candid_method(query)]
+fn requests_hash(requests: ExecutionRequests) -> Result<U256, String> {
+    requests
+        .hash()
+        .map_err(|x| format!("Error while calculating requests hash {x}"))
+}